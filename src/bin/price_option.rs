@@ -0,0 +1,126 @@
+//! Prices a European option under Black-Scholes from the command line.
+//!
+//! ```text
+//! price_option --spot 100 --strike 100 --time 1.0 --rate 0.05 --vol 0.2 --type call
+//! price_option --spot 100 --strike 100 --time 1.0 --rate 0.05 --type call \
+//!     --implied-vol --market-price 10.45
+//! ```
+
+use rustyfin::options::{black_scholes, black_scholes_greeks, try_implied_volatility};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: price_option --spot S --strike K --time T --rate R \
+(--vol SIGMA | --implied-vol --market-price P) [--type call|put]";
+
+struct Args {
+    spot: f64,
+    strike: f64,
+    time: f64,
+    rate: f64,
+    vol: Option<f64>,
+    market_price: Option<f64>,
+    implied_vol: bool,
+    is_call: bool,
+}
+
+fn parse_args(mut argv: impl Iterator<Item = String>) -> Result<Args, String> {
+    let (mut spot, mut strike, mut time, mut rate) = (None, None, None, None);
+    let mut args = Args {
+        spot: 0.0,
+        strike: 0.0,
+        time: 0.0,
+        rate: 0.0,
+        vol: None,
+        market_price: None,
+        implied_vol: false,
+        is_call: true,
+    };
+    while let Some(flag) = argv.next() {
+        if flag == "--implied-vol" {
+            args.implied_vol = true;
+            continue;
+        }
+        let value = argv
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let number = || {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number for {}: {}", flag, value))
+        };
+        match flag.as_str() {
+            "--spot" => spot = Some(number()?),
+            "--strike" => strike = Some(number()?),
+            "--time" => time = Some(number()?),
+            "--rate" => rate = Some(number()?),
+            "--vol" => args.vol = Some(number()?),
+            "--market-price" => args.market_price = Some(number()?),
+            "--type" => {
+                args.is_call = match value.as_str() {
+                    "call" => true,
+                    "put" => false,
+                    _ => return Err(format!("--type must be call or put, got {}", value)),
+                }
+            }
+            _ => return Err(format!("unknown argument {}", flag)),
+        }
+    }
+    args.spot = spot.ok_or("--spot is required")?;
+    args.strike = strike.ok_or("--strike is required")?;
+    args.time = time.ok_or("--time is required")?;
+    args.rate = rate.ok_or("--rate is required")?;
+    if args.spot <= 0.0 || args.strike <= 0.0 {
+        return Err("spot and strike must be positive".to_string());
+    }
+    if args.time <= 0.0 {
+        return Err("time must be positive".to_string());
+    }
+    if args.implied_vol {
+        if args.market_price.is_none() {
+            return Err("--implied-vol needs --market-price".to_string());
+        }
+    } else {
+        match args.vol {
+            Some(v) if v > 0.0 => {}
+            Some(_) => return Err("vol must be positive".to_string()),
+            None => return Err("--vol is required".to_string()),
+        }
+    }
+    Ok(args)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("error: {}\n{}", msg, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let (s, k, t, r) = (args.spot, args.strike, args.time, args.rate);
+    let sigma = if args.implied_vol {
+        let p = args.market_price.unwrap_or_default();
+        match try_implied_volatility(p, s, k, t, r, args.is_call) {
+            Ok(sigma) => {
+                println!("implied_vol {}", sigma);
+                sigma
+            }
+            Err(e) => {
+                eprintln!("error: implied volatility failed: {:?}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        args.vol.unwrap_or_default()
+    };
+    let (call, put) = black_scholes(s, k, t, r, sigma);
+    let greeks = black_scholes_greeks(s, k, t, r, sigma, args.is_call);
+    println!("call {}", call);
+    println!("put {}", put);
+    println!("delta {}", greeks.delta);
+    println!("gamma {}", greeks.gamma);
+    println!("vega {}", greeks.vega);
+    println!("theta {}", greeks.theta);
+    println!("rho {}", greeks.rho);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,7 @@
+pub mod cds;
+pub mod hazard;
+pub mod xva;
+
+pub use cds::{cds_price, cds_spread};
+pub use hazard::{bootstrap_hazard_rates, default_probability, survival_probability};
+pub use xva::cva_simple;
@@ -0,0 +1,116 @@
+use crate::fixed_income::yield_curve::interpolate_df;
+
+/// Risky annuity and protection leg per unit loss of a CDS for a given survival curve.
+///
+/// Premiums are paid at the end of each period and accrue to the default time, assumed to
+/// fall mid-period; protection is paid at the end of the period of default.
+///
+/// # Returns
+/// (risky_annuity, protection_per_unit_loss)
+pub(crate) fn cds_legs(
+    survival: impl Fn(f64) -> f64,
+    discount_factors: &[(f64, f64)],
+    payment_times: &[f64],
+) -> (f64, f64) {
+    let mut annuity = 0.0;
+    let mut protection = 0.0;
+    let mut prev_t = 0.0;
+    let mut prev_q = 1.0;
+    for &t in payment_times {
+        let q = survival(t);
+        let df = interpolate_df(discount_factors, t);
+        let accrual = t - prev_t;
+        let default_prob = prev_q - q;
+        annuity += accrual * df * (q + 0.5 * default_prob);
+        protection += df * default_prob;
+        prev_t = t;
+        prev_q = q;
+    }
+    (annuity, protection)
+}
+
+/// Values a CDS from the protection seller's side with a constant hazard rate.
+///
+/// Survival to `t` is `exp(-hazard_rate * t)`. The premium leg pays `coupon` on each period's
+/// accrual, including accrued premium on default; the protection leg pays `1 - recovery` on
+/// default. See [`cds_spread`] for the par coupon.
+///
+/// # Arguments
+///  - notional: notional of the contract
+///  - coupon: annual premium rate
+///  - hazard_rate: constant default intensity
+///  - recovery: recovery rate as a fraction of notional
+///  - discount_factors: `(time, discount_factor)` pairs, log-linearly interpolated
+///  - payment_times: premium payment times in years, the last being maturity
+///
+/// # Returns
+/// Premium leg PV minus protection leg PV
+pub fn cds_price(
+    notional: f64,
+    coupon: f64,
+    hazard_rate: f64,
+    recovery: f64,
+    discount_factors: &[(f64, f64)],
+    payment_times: &[f64],
+) -> f64 {
+    let survival = |t: f64| (-hazard_rate * t).exp();
+    let (annuity, protection) = cds_legs(survival, discount_factors, payment_times);
+    notional * (coupon * annuity - (1.0 - recovery) * protection)
+}
+
+/// Par spread of a CDS with a constant hazard rate: the coupon at which [`cds_price`] is zero.
+///
+/// Close to the credit triangle `hazard_rate * (1 - recovery)`.
+pub fn cds_spread(
+    hazard_rate: f64,
+    recovery: f64,
+    discount_factors: &[(f64, f64)],
+    payment_times: &[f64],
+) -> f64 {
+    let survival = |t: f64| (-hazard_rate * t).exp();
+    let (annuity, protection) = cds_legs(survival, discount_factors, payment_times);
+    (1.0 - recovery) * protection / annuity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn market() -> (Vec<(f64, f64)>, Vec<f64>) {
+        let dfs = vec![(0.5, 0.985), (1.0, 0.97), (3.0, 0.91), (5.0, 0.85)];
+        let payments = (1..=20).map(|i| i as f64 * 0.25).collect();
+        (dfs, payments)
+    }
+
+    #[test]
+    fn test_zero_value_at_par_spread() {
+        let (dfs, payments) = market();
+        let spread = cds_spread(0.02, 0.4, &dfs, &payments);
+        assert_relative_eq!(
+            cds_price(1e7, spread, 0.02, 0.4, &dfs, &payments),
+            0.0,
+            epsilon = 1e-6
+        );
+        // Credit triangle
+        assert_relative_eq!(spread, 0.02 * 0.6, max_relative = 1e-2);
+    }
+
+    #[test]
+    fn test_spread_increases_with_hazard() {
+        let (dfs, payments) = market();
+        let spreads: Vec<f64> = [0.005, 0.01, 0.03, 0.1]
+            .iter()
+            .map(|&h| cds_spread(h, 0.4, &dfs, &payments))
+            .collect();
+        assert!(spreads.windows(2).all(|w| w[1] > w[0]), "{:?}", spreads);
+    }
+
+    #[test]
+    fn test_interpolated_discount_factors() {
+        let (dfs, _) = market();
+        for &(t, df) in &dfs {
+            assert_relative_eq!(interpolate_df(&dfs, t), df, max_relative = 1e-14);
+        }
+    }
+}
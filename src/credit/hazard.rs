@@ -0,0 +1,165 @@
+use crate::credit::cds::cds_legs;
+use crate::optimization::secant;
+
+/// Survival probability to `t` under a piecewise-constant hazard curve of `(end_time, hazard)`
+/// pairs, `exp(-integral_0^t lambda(u) du)`.
+///
+/// Each hazard applies from the previous end time (0 for the first) to its own; the last one
+/// is extended flat beyond the final node.
+pub fn survival_probability(hazard_curve: &[(f64, f64)], t: f64) -> f64 {
+    let mut integrated = 0.0;
+    let mut start = 0.0;
+    for (i, &(end, hazard)) in hazard_curve.iter().enumerate() {
+        let last = i + 1 == hazard_curve.len();
+        let stop = if last { t } else { t.min(end) };
+        if stop > start {
+            integrated += hazard * (stop - start);
+        }
+        if t <= end {
+            break;
+        }
+        start = end;
+    }
+    (-integrated).exp()
+}
+
+/// Probability of default in `(t1, t2]`, `S(t1) - S(t2)`, seen from today.
+pub fn default_probability(hazard_curve: &[(f64, f64)], t1: f64, t2: f64) -> f64 {
+    survival_probability(hazard_curve, t1) - survival_probability(hazard_curve, t2)
+}
+
+/// Quarterly premium dates counted back from `maturity`, with any short stub at the front.
+fn quarterly_schedule(maturity: f64) -> Vec<f64> {
+    let mut times = Vec::new();
+    let mut t = maturity;
+    while t > 1e-9 {
+        times.push(t);
+        t -= 0.25;
+    }
+    times.reverse();
+    times
+}
+
+/// Bootstraps a piecewise-constant hazard curve from par CDS spreads.
+///
+/// Maturities are processed in increasing order. For each one the hazard rate on the segment
+/// since the previous maturity is solved with the secant method, holding earlier segments
+/// fixed, so that a CDS with quarterly premiums has the quoted par spread.
+///
+/// # Arguments
+///  - maturities: increasing CDS maturities in years
+///  - cds_spreads: par spreads, as annual rates, for each maturity
+///  - recovery: recovery rate as a fraction of notional
+///  - discount_factors: `(time, discount_factor)` pairs, log-linearly interpolated
+///
+/// # Returns
+/// `(maturity, hazard_rate)` pairs; a hazard rate is `NaN` if its root search fails
+pub fn bootstrap_hazard_rates(
+    maturities: &[f64],
+    cds_spreads: &[f64],
+    recovery: f64,
+    discount_factors: &[(f64, f64)],
+) -> Vec<(f64, f64)> {
+    let mut curve: Vec<(f64, f64)> = Vec::with_capacity(maturities.len());
+    for (&maturity, &spread) in maturities.iter().zip(cds_spreads) {
+        let schedule = quarterly_schedule(maturity);
+        let par_error = |hazard: f64| {
+            let mut trial = curve.clone();
+            trial.push((maturity, hazard));
+            let (annuity, protection) = cds_legs(
+                |t| survival_probability(&trial, t),
+                discount_factors,
+                &schedule,
+            );
+            (1.0 - recovery) * protection / annuity - spread
+        };
+        // Credit triangle as the initial guess.
+        let guess = spread / (1.0 - recovery);
+        let hazard = secant(par_error, guess, 1.1 * guess + 1e-4, 1e-12, 1e-14, 100)
+            .map_or(f64::NAN, |res| res.root);
+        curve.push((maturity, hazard));
+    }
+    curve
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const DFS: [(f64, f64); 4] = [(0.5, 0.985), (1.0, 0.97), (3.0, 0.91), (10.0, 0.7)];
+
+    #[test]
+    fn test_bootstrap_reprices_quotes() {
+        let maturities = [1.0, 3.0, 5.0, 7.0, 10.0];
+        let spreads = [0.006, 0.009, 0.012, 0.013, 0.0135];
+        let curve = bootstrap_hazard_rates(&maturities, &spreads, 0.4, &DFS);
+        for (&maturity, &spread) in maturities.iter().zip(&spreads) {
+            let (annuity, protection) = cds_legs(
+                |t| survival_probability(&curve, t),
+                &DFS,
+                &quarterly_schedule(maturity),
+            );
+            assert_relative_eq!(0.6 * protection / annuity, spread, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_hazard_rates_non_negative() {
+        for spreads in [
+            [0.001, 0.002, 0.003],
+            [0.02, 0.015, 0.012],
+            [0.05, 0.05, 0.05],
+        ] {
+            let curve = bootstrap_hazard_rates(&[1.0, 2.0, 5.0], &spreads, 0.4, &DFS);
+            assert!(curve.iter().all(|&(_, h)| h >= 0.0), "{:?}", curve);
+        }
+    }
+
+    #[test]
+    fn test_flat_spreads_give_flat_hazard() {
+        let curve = bootstrap_hazard_rates(&[1.0, 3.0, 5.0], &[0.01, 0.01, 0.01], 0.4, &DFS);
+        for w in curve.windows(2) {
+            assert_relative_eq!(w[0].1, w[1].1, max_relative = 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_survival_probability_properties() {
+        let curve = [(1.0, 0.01), (3.0, 0.02), (5.0, 0.035)];
+        assert_eq!(survival_probability(&curve, 0.0), 1.0);
+        let grid: Vec<f64> = (0..=80).map(|i| i as f64 * 0.1).collect();
+        assert!(grid
+            .windows(2)
+            .all(|w| survival_probability(&curve, w[1]) < survival_probability(&curve, w[0])));
+        let total: f64 = grid
+            .windows(2)
+            .map(|w| default_probability(&curve, w[0], w[1]))
+            .sum();
+        assert_relative_eq!(
+            total + survival_probability(&curve, 8.0),
+            1.0,
+            epsilon = 1e-14
+        );
+        // Integrated hazard: 0.01 + 2 * 0.02 + 0.035 * 1.5
+        assert_relative_eq!(
+            survival_probability(&curve, 4.5),
+            (-0.1025_f64).exp(),
+            epsilon = 1e-14
+        );
+    }
+
+    #[test]
+    fn test_constant_hazard_is_exponential() {
+        let lambda = 0.03;
+        for &curve in &[&[(10.0, lambda)][..], &[(1.0, lambda), (2.0, lambda)][..]] {
+            for &t in &[0.5, 1.0, 2.5, 12.0] {
+                assert_relative_eq!(
+                    survival_probability(curve, t),
+                    (-lambda * t).exp(),
+                    epsilon = 1e-15
+                );
+            }
+        }
+    }
+}
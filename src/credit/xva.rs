@@ -0,0 +1,85 @@
+/// Unilateral credit value adjustment of a derivative portfolio,
+/// `CVA = LGD * sum_i EE(t_i) * (SP(t_{i-1}) - SP(t_i))`, with `SP(t_0) = 1` today.
+///
+/// Each period's expected exposure is lost, net of recovery, if the counterparty defaults
+/// within it. Exposures should be discounted to today for the result to be a present value.
+///
+/// # Arguments
+///  - expected_exposure: `(time, discounted expected positive exposure)` pairs, increasing in time
+///  - survival_probs: `(time, counterparty survival probability)` pairs at the same times
+///  - lgd: loss given default, one minus the recovery rate
+///
+/// # Panics
+/// If the two grids differ in length.
+pub fn cva_simple(
+    expected_exposure: &[(f64, f64)],
+    survival_probs: &[(f64, f64)],
+    lgd: f64,
+) -> f64 {
+    assert_eq!(
+        expected_exposure.len(),
+        survival_probs.len(),
+        "exposure and survival grids must have the same length"
+    );
+    let mut prev_sp = 1.0;
+    let mut cva = 0.0;
+    for (&(t, ee), &(t_sp, sp)) in expected_exposure.iter().zip(survival_probs) {
+        debug_assert_eq!(t, t_sp, "exposure and survival times must coincide");
+        cva += ee * (prev_sp - sp);
+        prev_sp = sp;
+    }
+    lgd * cva
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Quarterly exposure profile of a swap-like trade, rising then amortising.
+    fn exposure() -> Vec<(f64, f64)> {
+        (1..=20)
+            .map(|i| {
+                let t = 0.25 * i as f64;
+                (t, 1e6 * t * (5.0 - t) / 6.25)
+            })
+            .collect()
+    }
+
+    fn survival(hazard: f64) -> Vec<(f64, f64)> {
+        exposure()
+            .iter()
+            .map(|&(t, _)| (t, (-hazard * t).exp()))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_default_no_cva() {
+        assert_eq!(cva_simple(&exposure(), &survival(0.0), 0.6), 0.0);
+    }
+
+    #[test]
+    fn test_certain_default_loses_first_exposure() {
+        let ee = exposure();
+        let defaulted: Vec<(f64, f64)> = ee.iter().map(|&(t, _)| (t, 0.0)).collect();
+        assert_relative_eq!(cva_simple(&ee, &defaulted, 0.6), 0.6 * ee[0].1);
+    }
+
+    #[test]
+    fn test_increasing_in_hazard_rate() {
+        let ee = exposure();
+        let mut prev = 0.0;
+        for &hazard in &[0.001, 0.01, 0.02, 0.05, 0.1] {
+            let cva = cva_simple(&ee, &survival(hazard), 0.6);
+            assert!(cva > prev, "hazard={} cva={}", hazard, cva);
+            prev = cva;
+        }
+        // A small hazard rate gives roughly LGD * hazard * integral of the exposure.
+        let integral: f64 = ee.iter().map(|&(_, e)| 0.25 * e).sum();
+        assert_relative_eq!(
+            cva_simple(&ee, &survival(1e-4), 0.6),
+            0.6 * 1e-4 * integral,
+            max_relative = 1e-3
+        );
+    }
+}
@@ -0,0 +1,26 @@
+pub mod bonds;
+pub mod cms;
+pub mod day_count;
+pub mod futures;
+pub mod hull_white;
+pub mod inflation;
+pub mod ois;
+pub mod swaps;
+pub mod swaptions;
+pub mod yield_curve;
+
+pub use bonds::{
+    callable_bond_oas, callable_bond_price, frn_discount_margin, frn_price, hull_white_bond_price,
+    key_rate_durations,
+};
+pub use cms::cms_convexity_adjustment;
+pub use day_count::{Date, DayCount};
+pub use futures::bond_futures_price;
+pub use hull_white::{hw2f_bond_price, HullWhite2F};
+pub use inflation::{breakeven_inflation, tips_price, Tips};
+pub use ois::{adjust_for_ois, ois_discount_factor, DualCurve};
+pub use swaps::{fixed_leg_pv, par_swap_rate, swap_pvbp};
+pub use swaptions::{swaption_black, swaption_implied_vol_black};
+pub use yield_curve::{
+    fit_nelson_siegel, flat_forward_interpolate, log_linear_discount, nelson_siegel_yield,
+};
@@ -0,0 +1,618 @@
+use crate::fixed_income::yield_curve::{flat_forward_interpolate, interpolate_df};
+use crate::optimization::{secant, SecantError};
+
+/// Trinomial lattice for the Hull-White short rate `dr = kappa * (theta - r) dt + sigma dW`.
+///
+/// The tree is built for the zero-mean process `dx = -kappa * x dt + sigma dW` and shifted by
+/// the expected short rate `alpha(t) = theta + (r0 - theta) * exp(-kappa * t)`, so that
+/// `r(i, j) = alpha(i * dt) + j * dx`.
+struct HullWhiteTree {
+    dt: f64,
+    dx: f64,
+    j_max: i64,
+    kappa: f64,
+    theta: f64,
+    r0: f64,
+}
+
+impl HullWhiteTree {
+    fn new(kappa: f64, theta: f64, sigma: f64, r0: f64, maturity: f64, n_steps: usize) -> Self {
+        let dt = maturity / n_steps as f64;
+        let dx = sigma * (3.0 * dt).sqrt();
+        // Standard Hull-White truncation; the lattice never needs more nodes than steps.
+        let j_max = ((0.1835 / (kappa * dt)).ceil() as i64).clamp(1, n_steps.max(1) as i64);
+        Self {
+            dt,
+            dx,
+            j_max,
+            kappa,
+            theta,
+            r0,
+        }
+    }
+
+    fn width(&self, step: usize) -> i64 {
+        (step as i64).min(self.j_max)
+    }
+
+    fn short_rate(&self, step: usize, j: i64) -> f64 {
+        let t = step as f64 * self.dt;
+        let alpha = self.theta + (self.r0 - self.theta) * (-self.kappa * t).exp();
+        alpha + j as f64 * self.dx
+    }
+
+    /// Returns the successor nodes `(up, mid, down)` and their probabilities for node `j`.
+    fn branch(&self, j: i64) -> ([i64; 3], [f64; 3]) {
+        let m = -self.kappa * self.dt;
+        let jm = j as f64 * m;
+        let jm2 = jm * jm;
+        if j >= self.j_max {
+            (
+                [j, j - 1, j - 2],
+                [
+                    7.0 / 6.0 + 0.5 * (jm2 + 3.0 * jm),
+                    -1.0 / 3.0 - jm2 - 2.0 * jm,
+                    1.0 / 6.0 + 0.5 * (jm2 + jm),
+                ],
+            )
+        } else if j <= -self.j_max {
+            (
+                [j + 2, j + 1, j],
+                [
+                    1.0 / 6.0 + 0.5 * (jm2 - jm),
+                    -1.0 / 3.0 - jm2 + 2.0 * jm,
+                    7.0 / 6.0 + 0.5 * (jm2 - 3.0 * jm),
+                ],
+            )
+        } else {
+            (
+                [j + 1, j, j - 1],
+                [
+                    1.0 / 6.0 + 0.5 * (jm2 + jm),
+                    2.0 / 3.0 - jm2,
+                    1.0 / 6.0 + 0.5 * (jm2 - jm),
+                ],
+            )
+        }
+    }
+}
+
+/// Backward induction of an annual-coupon bond through a Hull-White tree.
+///
+/// `call` is an optional `(call_price, call_dates)` pair; at each call date the issuer redeems
+/// whenever the continuation value exceeds the call price. `spread` is added to every short
+/// rate when discounting (used for OAS).
+#[allow(clippy::too_many_arguments)]
+fn bond_tree_value(
+    face: f64,
+    coupon_rate: f64,
+    maturity: f64,
+    call: Option<(f64, &[f64])>,
+    kappa: f64,
+    theta: f64,
+    sigma: f64,
+    r0: f64,
+    n_steps: usize,
+    spread: f64,
+) -> f64 {
+    if maturity <= 0.0 || n_steps == 0 {
+        return face;
+    }
+    let tree = HullWhiteTree::new(kappa, theta, sigma, r0, maturity, n_steps);
+    let step_of = |t: f64| (t / tree.dt).round() as usize;
+
+    // Annual coupons counted back from maturity.
+    let mut coupons = vec![0.0; n_steps + 1];
+    let mut t = maturity;
+    while t > 1e-12 {
+        coupons[step_of(t)] += face * coupon_rate;
+        t -= 1.0;
+    }
+
+    let mut callable = vec![false; n_steps + 1];
+    if let Some((_, dates)) = call {
+        for &d in dates.iter().filter(|&&d| d > 0.0 && d <= maturity) {
+            callable[step_of(d)] = true;
+        }
+    }
+
+    let width = tree.width(n_steps);
+    let mut values: Vec<f64> = (-width..=width)
+        .map(|_| match call {
+            Some((call_price, _)) if callable[n_steps] => face.min(call_price),
+            _ => face,
+        } + coupons[n_steps])
+        .collect();
+
+    for step in (0..n_steps).rev() {
+        let w = tree.width(step);
+        let next_w = tree.width(step + 1);
+        values = (-w..=w)
+            .map(|j| {
+                let (succ, probs) = tree.branch(j);
+                let expected: f64 = succ
+                    .iter()
+                    .zip(probs.iter())
+                    .map(|(&jn, &p)| p * values[(jn + next_w) as usize])
+                    .sum();
+                let disc = (-(tree.short_rate(step, j) + spread) * tree.dt).exp();
+                let mut v = disc * expected;
+                if let Some((call_price, _)) = call {
+                    if callable[step] && step > 0 {
+                        v = v.min(call_price);
+                    }
+                }
+                if step > 0 {
+                    v += coupons[step];
+                }
+                v
+            })
+            .collect();
+    }
+    values[0]
+}
+
+/// Prices a straight (non-callable) annual-coupon bond in the Hull-White trinomial tree.
+///
+/// # Arguments
+///  - face: face value repaid at maturity
+///  - coupon_rate: annual coupon rate (coupons paid yearly, counted back from maturity)
+///  - maturity: time to maturity in years
+///  - kappa: mean-reversion speed of the short rate
+///  - theta: long-run level of the short rate
+///  - sigma: short rate volatility
+///  - r0: current short rate
+///  - n_steps: number of time steps in the tree
+///
+/// # Returns
+/// Dirty price of the bond
+#[allow(clippy::too_many_arguments)]
+pub fn hull_white_bond_price(
+    face: f64,
+    coupon_rate: f64,
+    maturity: f64,
+    kappa: f64,
+    theta: f64,
+    sigma: f64,
+    r0: f64,
+    n_steps: usize,
+) -> f64 {
+    bond_tree_value(
+        face,
+        coupon_rate,
+        maturity,
+        None,
+        kappa,
+        theta,
+        sigma,
+        r0,
+        n_steps,
+        0.0,
+    )
+}
+
+/// Prices a callable annual-coupon bond using a Hull-White trinomial tree.
+///
+/// The issuer may redeem the bond at `call_price` on each of `call_dates`, which it does
+/// whenever the continuation value of the bond exceeds the call price.
+///
+/// # Arguments
+///  - face: face value repaid at maturity
+///  - coupon_rate: annual coupon rate (coupons paid yearly, counted back from maturity)
+///  - maturity: time to maturity in years
+///  - call_price: redemption price paid by the issuer on exercise
+///  - call_dates: times (in years) at which the bond may be called
+///  - kappa: mean-reversion speed of the short rate
+///  - theta: long-run level of the short rate
+///  - sigma: short rate volatility
+///  - r0: current short rate
+///  - n_steps: number of time steps in the tree
+///
+/// # Returns
+/// Dirty price of the callable bond
+#[allow(clippy::too_many_arguments)]
+pub fn callable_bond_price(
+    face: f64,
+    coupon_rate: f64,
+    maturity: f64,
+    call_price: f64,
+    call_dates: &[f64],
+    kappa: f64,
+    theta: f64,
+    sigma: f64,
+    r0: f64,
+    n_steps: usize,
+) -> f64 {
+    bond_tree_value(
+        face,
+        coupon_rate,
+        maturity,
+        Some((call_price, call_dates)),
+        kappa,
+        theta,
+        sigma,
+        r0,
+        n_steps,
+        0.0,
+    )
+}
+
+/// Solves for the option-adjusted spread of a callable bond.
+///
+/// The OAS is the constant spread added to every short rate in the tree such that the model
+/// price of the callable bond equals `market_price`.
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]> with the spread as a continuously compounded rate
+#[allow(clippy::too_many_arguments)]
+pub fn callable_bond_oas(
+    market_price: f64,
+    face: f64,
+    coupon_rate: f64,
+    maturity: f64,
+    call_price: f64,
+    call_dates: &[f64],
+    kappa: f64,
+    theta: f64,
+    sigma: f64,
+    r0: f64,
+    n_steps: usize,
+) -> Result<f64, SecantError> {
+    let f = |spread: f64| {
+        bond_tree_value(
+            face,
+            coupon_rate,
+            maturity,
+            Some((call_price, call_dates)),
+            kappa,
+            theta,
+            sigma,
+            r0,
+            n_steps,
+            spread,
+        ) - market_price
+    };
+    secant(f, 0.0, 0.01, 1e-10, 1e-10, 100).map(|res| res.root)
+}
+
+/// Value of a floating-rate note whose coupons are projected off the curve and discounted at
+/// the curve plus a continuously compounded `margin`.
+fn frn_value(
+    notional: f64,
+    spread: f64,
+    payment_times: &[f64],
+    discount_factors: &[(f64, f64)],
+    margin: f64,
+) -> f64 {
+    let mut value = 0.0;
+    let mut prev_t = 0.0;
+    let mut prev_df = 1.0;
+    for &t in payment_times {
+        let df = interpolate_df(discount_factors, t);
+        let tau = t - prev_t;
+        // Simply compounded forward rate fixed at the start of the period.
+        let forward = (prev_df / df - 1.0) / tau;
+        value += notional * (forward + spread) * tau * df * (-margin * t).exp();
+        prev_t = t;
+        prev_df = df;
+    }
+    value + notional * prev_df * (-margin * prev_t).exp()
+}
+
+/// Prices a floating-rate note paying the curve's forward rate plus `spread` in a single-curve
+/// framework.
+///
+/// Each coupon `notional * (L_i + spread) * tau_i` is paid at the end of its accrual period,
+/// with the floating rate `L_i` implied by the discount curve, and the notional is repaid at
+/// the last payment time. A zero-spread note is therefore worth par at a reset date.
+///
+/// # Arguments
+///  - notional: face value of the note
+///  - spread: quoted margin over the floating index, simply compounded
+///  - payment_times: coupon times in years, increasing; the first period starts today
+///  - discount_factors: `(time, discount_factor)` pairs, log-linearly interpolated
+///  - par: true to quote the price per 100 of notional instead of in currency
+///
+/// # Returns
+/// Dirty price of the note
+pub fn frn_price(
+    notional: f64,
+    spread: f64,
+    payment_times: &[f64],
+    discount_factors: &[(f64, f64)],
+    par: bool,
+) -> f64 {
+    let value = frn_value(notional, spread, payment_times, discount_factors, 0.0);
+    if par {
+        100.0 * value / notional
+    } else {
+        value
+    }
+}
+
+/// Solves for the discount margin of a floating-rate note.
+///
+/// The discount margin is the constant, continuously compounded spread added to the discount
+/// curve, with coupons still projected off the curve, that makes the note's value equal to
+/// `price`. It is zero when the note prices as in [`frn_price`]. A note trading at par has a
+/// margin close to its quoted `spread`.
+///
+/// # Arguments
+///  - price: market price, per 100 of notional if `par` is true and in currency otherwise
+///  - notional, spread, payment_times, discount_factors, par: as in [`frn_price`]
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]> with the discount margin
+pub fn frn_discount_margin(
+    price: f64,
+    notional: f64,
+    spread: f64,
+    payment_times: &[f64],
+    discount_factors: &[(f64, f64)],
+    par: bool,
+) -> Result<f64, SecantError> {
+    let target = if par { price * notional / 100.0 } else { price };
+    let f =
+        |margin: f64| frn_value(notional, spread, payment_times, discount_factors, margin) - target;
+    secant(f, 0.0, 0.01, 1e-12, 1e-10, 100).map(|res| res.root)
+}
+
+/// Weight of key rate `i` in the shift of the zero rate at maturity `t`.
+///
+/// Each key rate moves the curve by a tent that is 1 at its own maturity and falls linearly to
+/// 0 at the neighbouring keys. The first and last keys are extended flat to the ends of the
+/// curve, so the weights sum to 1 at every maturity.
+fn key_rate_weight(key_rates: &[f64], i: usize, t: f64) -> f64 {
+    let key = key_rates[i];
+    if t <= key {
+        match i.checked_sub(1).map(|j| key_rates[j]) {
+            Some(prev) if t > prev => (t - prev) / (key - prev),
+            Some(_) => 0.0,
+            None => 1.0,
+        }
+    } else {
+        match key_rates.get(i + 1) {
+            Some(&next) if t < next => (next - t) / (next - key),
+            Some(_) => 0.0,
+            None => 1.0,
+        }
+    }
+}
+
+/// Key rate durations of a stream of fixed cash flows.
+///
+/// The zero curve is interpolated with [`flat_forward_interpolate`]. Shifting key rate `i` moves
+/// the zero rate at maturity `t` by the tent weight `w_i(t)`: 1 at the key, falling linearly to
+/// 0 at the neighbouring keys, and flat beyond the first and last keys. Since the weights sum to
+/// one, the key rate durations add up to the duration under a parallel shift of the zero curve.
+///
+/// # Arguments
+///  - bond_cash_flows: `(time, amount)` pairs
+///  - zero_curve: `(maturity, continuously compounded zero rate)` pairs, increasing in maturity
+///  - key_rates: key maturities in years, increasing
+///
+/// # Returns
+/// `-(1/P) * dP/dy_i` for each key rate `y_i`
+pub fn key_rate_durations(
+    bond_cash_flows: &[(f64, f64)],
+    zero_curve: &[(f64, f64)],
+    key_rates: &[f64],
+) -> Vec<f64> {
+    let (maturities, zero_rates): (Vec<f64>, Vec<f64>) = zero_curve.iter().copied().unzip();
+    // (time, present value) of every cash flow.
+    let pvs: Vec<(f64, f64)> = bond_cash_flows
+        .iter()
+        .map(|&(t, amount)| {
+            let zero = flat_forward_interpolate(&maturities, &zero_rates, t);
+            (t, amount * (-zero * t).exp())
+        })
+        .collect();
+    let price: f64 = pvs.iter().map(|&(_, pv)| pv).sum();
+    (0..key_rates.len())
+        .map(|i| {
+            pvs.iter()
+                .map(|&(t, pv)| t * pv * key_rate_weight(key_rates, i, t))
+                .sum::<f64>()
+                / price
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_callable_below_straight() {
+        let call_dates = [2.0, 3.0, 4.0];
+        for &(coupon, r0, sigma) in &[(0.05, 0.05, 0.01), (0.08, 0.03, 0.015), (0.03, 0.06, 0.02)] {
+            let straight = hull_white_bond_price(100.0, coupon, 5.0, 0.1, 0.05, sigma, r0, 100);
+            let callable = callable_bond_price(
+                100.0,
+                coupon,
+                5.0,
+                100.0,
+                &call_dates,
+                0.1,
+                0.05,
+                sigma,
+                r0,
+                100,
+            );
+            assert!(
+                callable <= straight + 1e-12,
+                "callable={} straight={}",
+                callable,
+                straight
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_vol_matches_deterministic_discounting() {
+        // With sigma = 0 and r0 = theta the short rate is constant.
+        let price = hull_white_bond_price(100.0, 0.0, 5.0, 0.1, 0.04, 0.0, 0.04, 50);
+        assert_relative_eq!(price, 100.0 * (-0.04_f64 * 5.0).exp(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_oas_round_trip() {
+        let call_dates = [2.0, 3.0, 4.0];
+        let target = bond_tree_value(
+            100.0,
+            0.06,
+            5.0,
+            Some((100.0, &call_dates)),
+            0.1,
+            0.05,
+            0.01,
+            0.05,
+            100,
+            0.0125,
+        );
+        let oas = callable_bond_oas(
+            target,
+            100.0,
+            0.06,
+            5.0,
+            100.0,
+            &call_dates,
+            0.1,
+            0.05,
+            0.01,
+            0.05,
+            100,
+        )
+        .expect("OAS should converge");
+        assert_relative_eq!(oas, 0.0125, epsilon = 1e-6);
+    }
+
+    fn flat_curve(rate: f64) -> Vec<(f64, f64)> {
+        (1..=20)
+            .map(|i| {
+                let t = 0.5 * i as f64;
+                (t, (-rate * t).exp())
+            })
+            .collect()
+    }
+
+    fn quarterly(years: usize) -> Vec<f64> {
+        (1..=4 * years).map(|i| 0.25 * i as f64).collect()
+    }
+
+    #[test]
+    fn test_zero_spread_frn_prices_at_par() {
+        let times = quarterly(5);
+        for &rate in &[0.0, 0.02, 0.06] {
+            assert_relative_eq!(
+                frn_price(1_000_000.0, 0.0, &times, &flat_curve(rate), false),
+                1_000_000.0,
+                max_relative = 1e-12
+            );
+            assert_relative_eq!(
+                frn_price(1_000_000.0, 0.0, &times, &flat_curve(rate), true),
+                100.0,
+                epsilon = 1e-10
+            );
+        }
+        // Also on an upward-sloping curve.
+        let curve: Vec<(f64, f64)> = (1..=10)
+            .map(|i| (i as f64, (-(0.01 + 0.004 * i as f64) * i as f64).exp()))
+            .collect();
+        assert_relative_eq!(
+            frn_price(100.0, 0.0, &times, &curve, true),
+            100.0,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_positive_spread_adds_value() {
+        let (times, curve) = (quarterly(5), flat_curve(0.04));
+        let at_par = frn_price(100.0, 0.0, &times, &curve, true);
+        let with_spread = frn_price(100.0, 0.01, &times, &curve, true);
+        assert!(with_spread > at_par);
+        // The spread is worth its annuity: 0.01 * sum(tau_i * df_i) per unit of notional.
+        let annuity: f64 = times.iter().map(|&t| 0.25 * (-0.04 * t).exp()).sum();
+        assert_relative_eq!(
+            with_spread - at_par,
+            100.0 * 0.01 * annuity,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_discount_margin() {
+        let (times, curve) = (quarterly(5), flat_curve(0.03));
+        let dm = frn_discount_margin(100.0, 100.0, 0.0, &times, &curve, true).unwrap();
+        assert!(dm.abs() < 1e-10, "dm={}", dm);
+        // Round trip through a price below par.
+        let price = frn_value(100.0, 0.005, &times, &curve, 0.012);
+        let dm = frn_discount_margin(price, 100.0, 0.005, &times, &curve, false).unwrap();
+        assert_relative_eq!(dm, 0.012, epsilon = 1e-9);
+    }
+
+    const ZERO_CURVE: [(f64, f64); 6] = [
+        (0.5, 0.030),
+        (1.0, 0.032),
+        (2.0, 0.035),
+        (5.0, 0.038),
+        (10.0, 0.041),
+        (30.0, 0.043),
+    ];
+    const KEY_RATES: [f64; 5] = [1.0, 2.0, 5.0, 10.0, 30.0];
+
+    fn bond_price(cash_flows: &[(f64, f64)], shift: impl Fn(f64) -> f64) -> f64 {
+        let (maturities, rates): (Vec<f64>, Vec<f64>) = ZERO_CURVE.iter().copied().unzip();
+        cash_flows
+            .iter()
+            .map(|&(t, cf)| {
+                cf * (-(flat_forward_interpolate(&maturities, &rates, t) + shift(t)) * t).exp()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_key_rate_durations_sum_to_parallel_duration() {
+        // 7-year 5% semi-annual bond.
+        let cash_flows: Vec<(f64, f64)> = (1..=14)
+            .map(|i| (0.5 * i as f64, if i == 14 { 102.5 } else { 2.5 }))
+            .collect();
+        let krd = key_rate_durations(&cash_flows, &ZERO_CURVE, &KEY_RATES);
+        let h = 1e-5;
+        let price = bond_price(&cash_flows, |_| 0.0);
+        let modified =
+            -(bond_price(&cash_flows, |_| h) - bond_price(&cash_flows, |_| -h)) / (2.0 * h * price);
+        assert_relative_eq!(krd.iter().sum::<f64>(), modified, max_relative = 1e-8);
+
+        // Each key rate duration matches a bump of that key alone.
+        for (i, &d) in krd.iter().enumerate() {
+            let bump = |sign: f64| {
+                bond_price(&cash_flows, |t| {
+                    sign * h * key_rate_weight(&KEY_RATES, i, t)
+                })
+            };
+            assert_relative_eq!(
+                d,
+                -(bump(1.0) - bump(-1.0)) / (2.0 * h * price),
+                epsilon = 1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_rate_duration_local_to_cash_flows() {
+        // A 2-year zero only responds to the 2-year key.
+        let krd = key_rate_durations(&[(2.0, 100.0)], &ZERO_CURVE, &KEY_RATES);
+        assert_relative_eq!(krd[1], 2.0, epsilon = 1e-12);
+        for &i in &[0, 2, 3, 4] {
+            assert!(krd[i].abs() < 1e-12, "key {} krd {}", KEY_RATES[i], krd[i]);
+        }
+        // Between keys the exposure is split linearly.
+        let krd = key_rate_durations(&[(3.5, 100.0)], &ZERO_CURVE, &KEY_RATES);
+        assert_relative_eq!(krd[1], 0.5 * 3.5, epsilon = 1e-12);
+        assert_relative_eq!(krd[2], 0.5 * 3.5, epsilon = 1e-12);
+        assert!(krd[4].abs() < 1e-12);
+    }
+}
@@ -0,0 +1,157 @@
+/// Calendar date in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Creates a date from year, month (1-12) and day of month.
+    ///
+    /// # Returns
+    /// `None` if the day does not exist in that month
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Calendar year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Month of the year, 1-12.
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Day of the month, starting at 1.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Days since 1970-01-01 (Hinnant's `days_from_civil`).
+    fn ordinal(&self) -> i64 {
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = i64::from(self.month);
+        let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Number of calendar days from `self` to `other`, negative if `other` is earlier.
+    pub fn days_until(&self, other: Date) -> i64 {
+        other.ordinal() - self.ordinal()
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Day count convention turning a pair of dates into an accrual fraction of a year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayCount {
+    /// 30/360 bond basis: months of 30 days, years of 360 days.
+    Thirty360,
+    /// Actual days over 360.
+    Actual360,
+    /// Actual days over 365.
+    Actual365Fixed,
+    /// Actual/Actual (ISDA): days in leap years over 366, other days over 365.
+    ActualActual,
+}
+
+impl DayCount {
+    /// Accrual fraction of a year between `start` and `end`.
+    pub fn year_fraction(&self, start: Date, end: Date) -> f64 {
+        match self {
+            DayCount::Thirty360 => {
+                let d1 = start.day.min(30);
+                let d2 = if d1 == 30 { end.day.min(30) } else { end.day };
+                let days = 360 * (end.year - start.year)
+                    + 30 * (end.month as i32 - start.month as i32)
+                    + (d2 as i32 - d1 as i32);
+                days as f64 / 360.0
+            }
+            DayCount::Actual360 => start.days_until(end) as f64 / 360.0,
+            DayCount::Actual365Fixed => start.days_until(end) as f64 / 365.0,
+            DayCount::ActualActual => {
+                if end < start {
+                    return -self.year_fraction(end, start);
+                }
+                let mut fraction = 0.0;
+                let mut from = start;
+                for year in start.year..=end.year {
+                    let to = if year == end.year {
+                        end
+                    } else {
+                        Date::from_ymd(year + 1, 1, 1).unwrap()
+                    };
+                    let basis = if is_leap_year(year) { 366.0 } else { 365.0 };
+                    fraction += from.days_until(to) as f64 / basis;
+                    from = to;
+                }
+                fraction
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn date(y: i32, m: u32, d: u32) -> Date {
+        Date::from_ymd(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_days_between_dates() {
+        assert_eq!(date(1970, 1, 1).days_until(date(1970, 1, 2)), 1);
+        assert_eq!(date(2024, 1, 1).days_until(date(2025, 1, 1)), 366);
+        assert_eq!(date(2023, 3, 1).days_until(date(2023, 2, 28)), -1);
+        assert_eq!(date(2000, 2, 28).days_until(date(2000, 3, 1)), 2);
+        assert_eq!(date(1900, 2, 28).days_until(date(1900, 3, 1)), 1);
+        assert!(Date::from_ymd(2023, 2, 29).is_none());
+        assert!(Date::from_ymd(2023, 13, 1).is_none());
+    }
+
+    #[test]
+    fn test_year_fractions() {
+        let (start, end) = (date(2024, 1, 31), date(2024, 7, 31));
+        assert_relative_eq!(DayCount::Thirty360.year_fraction(start, end), 0.5);
+        assert_relative_eq!(DayCount::Actual360.year_fraction(start, end), 182.0 / 360.0);
+        assert_relative_eq!(
+            DayCount::Actual365Fixed.year_fraction(start, end),
+            182.0 / 365.0
+        );
+        // Across a year end: 2023 days over 365, 2024 days over 366.
+        let (start, end) = (date(2023, 7, 1), date(2024, 7, 1));
+        assert_relative_eq!(
+            DayCount::ActualActual.year_fraction(start, end),
+            184.0 / 365.0 + 182.0 / 366.0,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            DayCount::ActualActual.year_fraction(end, start),
+            -(184.0 / 365.0 + 182.0 / 366.0),
+            epsilon = 1e-15
+        );
+    }
+}
@@ -0,0 +1,109 @@
+/// Prices a bond futures contract from its deliverable basket and finds the cheapest to deliver.
+///
+/// Buying bond `i` today and financing it in repo until delivery costs `P_i * exp(repo * T)`,
+/// against which the short receives the invoice amount `CF_i * F + AI_i`. The futures price
+/// implied by bond `i` is therefore `(P_i * exp(repo * T) - AI_i) / CF_i`, and since the
+/// short chooses what to deliver, the futures price is the lowest of these. The bond
+/// attaining it is the cheapest to deliver (CTD). Its net basis
+/// `P_i * exp(repo * T) - AI_i - CF_i * F` is zero and every other bond's is positive.
+///
+/// No coupon is assumed to be paid before delivery, and the delivery option is not valued.
+///
+/// # Arguments
+///  - conversion_factors: conversion factor of each deliverable bond
+///  - bond_prices: dirty price of each bond today
+///  - accrued_interests: accrued interest of each bond at delivery
+///  - t: time to delivery in years (T)
+///  - repo_rate: continuously compounded repo rate to delivery, at which the carry is financed
+///
+/// # Returns
+/// (futures_price, ctd_index)
+///
+/// # Panics
+/// If the basket is empty or the three slices differ in length.
+pub fn bond_futures_price(
+    conversion_factors: &[f64],
+    bond_prices: &[f64],
+    accrued_interests: &[f64],
+    t: f64,
+    repo_rate: f64,
+) -> (f64, usize) {
+    assert!(
+        bond_prices.len() == conversion_factors.len()
+            && accrued_interests.len() == conversion_factors.len(),
+        "conversion factors, prices and accrued interests must have one entry per bond"
+    );
+    let carry = (repo_rate * t).exp();
+    conversion_factors
+        .iter()
+        .zip(bond_prices)
+        .zip(accrued_interests)
+        .map(|((&cf, &p), &ai)| (p * carry - ai) / cf)
+        .enumerate()
+        .map(|(i, f)| (f, i))
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .expect("the deliverable basket must not be empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const CF: [f64; 4] = [0.92, 1.05, 0.85, 1.12];
+    const PRICES: [f64; 4] = [99.5, 112.0, 91.0, 121.0];
+    const ACCRUED: [f64; 4] = [1.2, 0.4, 2.1, 0.9];
+
+    fn net_basis(f: f64, t: f64, repo: f64) -> Vec<f64> {
+        (0..CF.len())
+            .map(|i| PRICES[i] * (repo * t).exp() - ACCRUED[i] - CF[i] * f)
+            .collect()
+    }
+
+    #[test]
+    fn test_ctd_has_lowest_net_basis() {
+        let (f, ctd) = bond_futures_price(&CF, &PRICES, &ACCRUED, 0.25, 0.035);
+        let basis = net_basis(f, 0.25, 0.035);
+        assert_relative_eq!(basis[ctd], 0.0, epsilon = 1e-12);
+        for (i, &b) in basis.iter().enumerate() {
+            if i != ctd {
+                assert!(b > basis[ctd], "bond {} basis {}", i, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_basis_vanishes_at_delivery() {
+        let (f, ctd) = bond_futures_price(&CF, &PRICES, &ACCRUED, 0.0, 0.035);
+        // At delivery the CTD's clean price equals the invoice clean amount.
+        assert_relative_eq!(
+            PRICES[ctd] - ACCRUED[ctd] - CF[ctd] * f,
+            0.0,
+            epsilon = 1e-12
+        );
+        let mut prev = f64::INFINITY;
+        for &t in &[0.5, 0.25, 0.1, 0.01] {
+            let (f_t, ctd_t) = bond_futures_price(&CF, &PRICES, &ACCRUED, t, 0.035);
+            let gross = PRICES[ctd_t] - ACCRUED[ctd_t] - CF[ctd_t] * f_t;
+            assert!(gross.abs() < prev);
+            prev = gross.abs();
+        }
+    }
+
+    #[test]
+    fn test_repo_rate_sensitivity() {
+        let (t, repo, h) = (0.5, 0.03, 1e-6);
+        let (f, ctd) = bond_futures_price(&CF, &PRICES, &ACCRUED, t, repo);
+        let (up, ctd_up) = bond_futures_price(&CF, &PRICES, &ACCRUED, t, repo + h);
+        assert_eq!(ctd, ctd_up);
+        // Higher financing cost raises the futures price by the carry on the CTD.
+        let expected = t * PRICES[ctd] * (repo * t).exp() / CF[ctd];
+        assert_relative_eq!((up - f) / h, expected, max_relative = 1e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "one entry per bond")]
+    fn test_mismatched_basket_rejected() {
+        bond_futures_price(&[0.9, 1.0], &[99.0], &[1.0, 1.0], 0.5, 0.03);
+    }
+}
@@ -0,0 +1,132 @@
+/// Two-factor Hull-White (1994) short rate model
+///
+/// `dr = (u - a1 * r) dt + sigma1 dW1`, `du = -a2 * u dt + sigma2 dW2`, `dW1 dW2 = rho dt`,
+/// where the short rate `r` reverts towards a level `u / a1` that is itself stochastic.
+/// No term structure is fitted, so the deterministic drift `theta(t)` of the original paper is
+/// zero. The mean reversion speeds must differ, `a1 != a2`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HullWhite2F {
+    /// Mean-reversion speed of the short rate.
+    pub a1: f64,
+    /// Volatility of the short rate.
+    pub sigma1: f64,
+    /// Mean-reversion speed of the drift factor `u`.
+    pub a2: f64,
+    /// Volatility of the drift factor `u`.
+    pub sigma2: f64,
+    /// Correlation between the two Brownian motions.
+    pub rho: f64,
+}
+
+/// `B(a, t) = (1 - exp(-a t)) / a`, the integral of `exp(-a s)` over `[0, t]`.
+fn b(a: f64, t: f64) -> f64 {
+    (1.0 - (-a * t).exp()) / a
+}
+
+/// `int_0^t B(a, s) B(c, s) ds`.
+fn b_cross(a: f64, c: f64, t: f64) -> f64 {
+    (t - b(a, t) - b(c, t) + b(a + c, t)) / (a * c)
+}
+
+/// Prices a zero-coupon bond paying 1 at `t` in the two-factor Hull-White model.
+///
+/// Writing `r = x + y` with `y = u / (a1 - a2)` turns the model into two correlated
+/// Ornstein-Uhlenbeck factors, so `int_0^t r ds` is Gaussian and
+/// `P = exp(-E[int r] + Var[int r] / 2)` in closed form.
+///
+/// # Arguments
+///  - model: model parameters
+///  - r0: current short rate
+///  - x0: current value of the drift factor `u`
+///  - t: time to maturity in years
+///
+/// # Returns
+/// Discount factor `P(0, t)`
+pub fn hw2f_bond_price(model: &HullWhite2F, r0: f64, x0: f64, t: f64) -> f64 {
+    let HullWhite2F {
+        a1,
+        sigma1,
+        a2,
+        sigma2,
+        rho,
+    } = *model;
+    let gap = a1 - a2;
+    // Loading of int_0^t r ds on u(0) and on dW2.
+    let c = (b(a2, t) - b(a1, t)) / gap;
+    let mean = b(a1, t) * r0 + c * x0;
+    let i11 = b_cross(a1, a1, t);
+    let i12 = b_cross(a1, a2, t);
+    let i22 = b_cross(a2, a2, t);
+    let variance = sigma1 * sigma1 * i11
+        + (sigma2 / gap).powi(2) * (i22 - 2.0 * i12 + i11)
+        + 2.0 * rho * sigma1 * sigma2 / gap * (i12 - i11);
+    (-mean + 0.5 * variance).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed_income::bonds::hull_white_bond_price;
+    use approx::assert_relative_eq;
+
+    const MODEL: HullWhite2F = HullWhite2F {
+        a1: 0.3,
+        sigma1: 0.01,
+        a2: 0.05,
+        sigma2: 0.008,
+        rho: -0.4,
+    };
+
+    #[test]
+    fn test_reduces_to_one_factor() {
+        let model = HullWhite2F {
+            sigma2: 0.0,
+            ..MODEL
+        };
+        let (a, sigma, r0) = (model.a1, model.sigma1, 0.04);
+        for &t in &[0.5, 2.0, 10.0] {
+            let two_factor = hw2f_bond_price(&model, r0, 0.0, t);
+            // Vasicek with zero long-run level.
+            let bt = b(a, t);
+            let vasicek = (-bt * r0 + sigma * sigma / (2.0 * a * a) * (t - bt)
+                - sigma * sigma * bt * bt / (4.0 * a))
+                .exp();
+            assert_relative_eq!(two_factor, vasicek, epsilon = 1e-14);
+            // The tree discounts at the start of each step, an O(dt) bias.
+            let tree = hull_white_bond_price(1.0, 0.0, t, a, 0.0, sigma, r0, 2000);
+            assert_relative_eq!(two_factor, tree, epsilon = 2e-4);
+        }
+    }
+
+    #[test]
+    fn test_boundary_conditions() {
+        let (r0, x0) = (0.05, 0.01);
+        assert_eq!(hw2f_bond_price(&MODEL, r0, x0, 0.0), 1.0);
+        // dP/dt = -r0 at t = 0.
+        let h = 1e-6;
+        let slope = (hw2f_bond_price(&MODEL, r0, x0, h) - 1.0) / h;
+        assert_relative_eq!(slope, -r0, epsilon = 1e-5);
+        // Positive rates give a decreasing discount curve below one.
+        let mut prev = 1.0;
+        for i in 1..=10 {
+            let p = hw2f_bond_price(&MODEL, r0, x0, i as f64);
+            assert!(p > 0.0 && p < prev, "t={} p={}", i, p);
+            prev = p;
+        }
+    }
+
+    #[test]
+    fn test_yield_curve_smooth() {
+        let yield_at = |t: f64| -hw2f_bond_price(&MODEL, 0.02, 0.009, t).ln() / t;
+        let h = 0.05;
+        let mut t = 0.25;
+        while t < 20.0 {
+            let curvature = (yield_at(t + h) - 2.0 * yield_at(t) + yield_at(t - h)) / (h * h);
+            assert!(curvature.abs() < 0.05, "t={} curvature={}", t, curvature);
+            assert!((yield_at(t + h) - yield_at(t)).abs() < 1e-3);
+            t += h;
+        }
+        // The drift factor pulls the short rate up towards u / a1 = 3%.
+        assert!(yield_at(5.0) > yield_at(0.25));
+    }
+}
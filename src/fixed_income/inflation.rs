@@ -0,0 +1,114 @@
+/// Inflation-linked bond (TIPS, linker) with coupons and principal indexed to the CPI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tips {
+    /// Annual real coupon rate, paid on the inflation-adjusted face value.
+    pub real_coupon_rate: f64,
+    /// Reference CPI at issue.
+    pub base_cpi: f64,
+    /// Reference CPI today.
+    pub current_cpi: f64,
+    /// Face value before indexation.
+    pub face: f64,
+    /// Coupon times in years, increasing; the last one is the maturity.
+    pub payment_times: &'static [f64],
+}
+
+impl Tips {
+    /// Index ratio `current_cpi / base_cpi` applied to the face value.
+    pub fn indexation_factor(&self) -> f64 {
+        self.current_cpi / self.base_cpi
+    }
+}
+
+/// Prices an inflation-linked bond from its real yield.
+///
+/// Coupons of `real_coupon_rate * tau_i` per unit of inflation-adjusted face are discounted at
+/// the annually compounded `real_yield`, with the index held at today's ratio. The principal
+/// repaid at maturity is floored at the original face value, so after deflation
+/// (`current_cpi < base_cpi`) the bond is worth more than the index ratio times its real price.
+///
+/// # Arguments
+///  - tips: the bond
+///  - real_yield: annually compounded real yield
+///
+/// # Returns
+/// Dirty price in nominal terms
+pub fn tips_price(tips: &Tips, real_yield: f64) -> f64 {
+    let index = tips.indexation_factor();
+    let discount = |t: f64| (1.0 + real_yield).powf(-t);
+    let mut price = 0.0;
+    let mut prev_t = 0.0;
+    for &t in tips.payment_times {
+        price += tips.face * index * tips.real_coupon_rate * (t - prev_t) * discount(t);
+        prev_t = t;
+    }
+    price + tips.face * index.max(1.0) * discount(prev_t)
+}
+
+/// Breakeven inflation rate implied by nominal and real yields of the same maturity.
+///
+/// From the Fisher relation `(1 + nominal)^t = (1 + real)^t * (1 + breakeven)^t`, the breakeven
+/// is `(1 + nominal) / (1 + real) - 1`, approximately `nominal - real`. With annually
+/// compounded yields the maturity cancels.
+///
+/// # Arguments
+///  - nominal_yield: annually compounded nominal yield
+///  - real_yield: annually compounded real yield
+///  - _t: maturity in years
+///
+/// # Returns
+/// Annually compounded breakeven inflation rate
+pub fn breakeven_inflation(nominal_yield: f64, real_yield: f64, _t: f64) -> f64 {
+    (1.0 + nominal_yield) / (1.0 + real_yield) - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const SEMI_ANNUAL_5Y: [f64; 10] = [0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0, 4.5, 5.0];
+
+    fn tips(current_cpi: f64) -> Tips {
+        Tips {
+            real_coupon_rate: 0.0125,
+            base_cpi: 250.0,
+            current_cpi,
+            face: 100.0,
+            payment_times: &SEMI_ANNUAL_5Y,
+        }
+    }
+
+    #[test]
+    fn test_indexation_factor_scales_price() {
+        let bond = tips(275.0);
+        assert_relative_eq!(bond.indexation_factor(), 1.1, epsilon = 1e-15);
+        assert_relative_eq!(
+            tips_price(&bond, 0.01),
+            1.1 * tips_price(&tips(250.0), 0.01),
+            epsilon = 1e-12
+        );
+        // At a real yield equal to the coupon the unindexed bond is close to par.
+        assert_relative_eq!(tips_price(&tips(250.0), 0.0125), 100.0, epsilon = 0.05);
+    }
+
+    #[test]
+    fn test_deflation_floor_on_principal() {
+        let bond = tips(225.0);
+        let real_yield = 0.01;
+        let discount = (1.0_f64 + real_yield).powf(-5.0);
+        let unfloored = 0.9 * tips_price(&tips(250.0), real_yield);
+        let price = tips_price(&bond, real_yield);
+        assert!(price > unfloored);
+        // Only the principal is floored: the difference is the 10% index shortfall on it.
+        assert_relative_eq!(price - unfloored, 0.1 * 100.0 * discount, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_breakeven_inflation() {
+        let be = breakeven_inflation(0.045, 0.015, 10.0);
+        assert_relative_eq!(be, 0.045 - 0.015, epsilon = 1e-3);
+        assert_relative_eq!((1.0 + 0.015) * (1.0 + be), 1.045, epsilon = 1e-14);
+        assert_eq!(breakeven_inflation(0.02, 0.02, 5.0), 0.0);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::fixed_income::yield_curve::log_linear_discount;
+
+fn split(curve: &[(f64, f64)]) -> (Vec<f64>, Vec<f64>) {
+    curve.iter().copied().unzip()
+}
+
+/// Discount factor at `t` from an OIS zero curve.
+///
+/// # Arguments
+///  - ois_curve: `(maturity, continuously compounded zero rate)` pairs, increasing in maturity
+///  - t: maturity in years
+///
+/// # Returns
+/// Discount factor, log-linearly interpolated (see [`log_linear_discount`])
+pub fn ois_discount_factor(ois_curve: &[(f64, f64)], t: f64) -> f64 {
+    let (maturities, rates) = split(ois_curve);
+    log_linear_discount(&maturities, &rates, t)
+}
+
+/// Converts a value discounted at LIBOR to OIS discounting to first order.
+///
+/// The simple rates `libor_rate` and `ois_rate` for tenor `t` are converted to continuous
+/// compounding and the value is shifted by `duration * (libor - ois)`, where `duration` is the
+/// money duration `-dV/dr` of the position with respect to a parallel shift of the discount
+/// curve. Equal rates leave the price unchanged.
+///
+/// # Arguments
+///  - price: value under LIBOR discounting
+///  - libor_rate: simple LIBOR rate for tenor `t`
+///  - ois_rate: simple OIS rate for tenor `t`
+///  - t: tenor of the quoted rates in years
+///  - duration: money duration of the position
+///
+/// # Returns
+/// Approximate value under OIS discounting
+pub fn adjust_for_ois(price: f64, libor_rate: f64, ois_rate: f64, t: f64, duration: f64) -> f64 {
+    let spread = ((1.0 + libor_rate * t) / (1.0 + ois_rate * t)).ln() / t;
+    price + duration * spread
+}
+
+/// Dual-curve framework: LIBOR forwards projected off one curve, cash flows discounted on OIS.
+///
+/// Both curves are `(maturity, continuously compounded zero rate)` pairs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DualCurve {
+    /// Projection curve for floating rate fixings.
+    pub forward_curve: Vec<(f64, f64)>,
+    /// OIS curve used for discounting.
+    pub discount_curve: Vec<(f64, f64)>,
+}
+
+impl DualCurve {
+    /// Simple forward rate for the accrual period `[t1, t2]` from the projection curve.
+    pub fn forward_rate(&self, t1: f64, t2: f64) -> f64 {
+        let (maturities, rates) = split(&self.forward_curve);
+        let p1 = log_linear_discount(&maturities, &rates, t1);
+        let p2 = log_linear_discount(&maturities, &rates, t2);
+        (p1 / p2 - 1.0) / (t2 - t1)
+    }
+
+    /// Discount factor at `t` from the OIS curve.
+    pub fn discount_factor(&self, t: f64) -> f64 {
+        ois_discount_factor(&self.discount_curve, t)
+    }
+
+    /// Value per unit notional of a payer swap (pay fixed, receive floating).
+    ///
+    /// Both legs pay on `payment_times` (accruals start at 0); floating coupons are projected off
+    /// the forward curve and all cash flows discounted on the OIS curve.
+    pub fn payer_swap_value(&self, fixed_rate: f64, payment_times: &[f64]) -> f64 {
+        let mut start = 0.0;
+        let mut value = 0.0;
+        for &end in payment_times {
+            let accrual = end - start;
+            let forward = self.forward_rate(start, end);
+            value += (forward - fixed_rate) * accrual * self.discount_factor(end);
+            start = end;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn flat(rate: f64) -> Vec<(f64, f64)> {
+        vec![(0.25, rate), (1.0, rate), (5.0, rate)]
+    }
+
+    #[test]
+    fn test_no_adjustment_when_libor_equals_ois() {
+        assert_eq!(adjust_for_ois(1.2345, 0.03, 0.03, 1.0, 0.9), 1.2345);
+        let single = DualCurve {
+            forward_curve: flat(0.03),
+            discount_curve: flat(0.03),
+        };
+        let times = [0.25, 0.5, 0.75, 1.0];
+        // A single-curve par swap is worth zero.
+        let annuity: f64 = times
+            .iter()
+            .map(|&t| 0.25 * single.discount_factor(t))
+            .sum();
+        let par = (1.0 - single.discount_factor(1.0)) / annuity;
+        assert_relative_eq!(single.payer_swap_value(par, &times), 0.0, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_ten_bp_spread_moves_value_by_duration() {
+        let times = [0.25, 0.5, 0.75, 1.0];
+        let (libor, ois) = (0.03, 0.029);
+        let value = |discount: f64| {
+            DualCurve {
+                forward_curve: flat(libor),
+                discount_curve: flat(discount),
+            }
+            .payer_swap_value(0.05, &times)
+        };
+        let h = 1e-6;
+        let duration = -(value(libor + h) - value(libor - h)) / (2.0 * h);
+        let change = value(ois) - value(libor);
+        assert_relative_eq!(change, duration * 0.001, max_relative = 1e-2);
+
+        let simple = |r: f64| r.exp_m1();
+        let adjusted = adjust_for_ois(value(libor), simple(libor), simple(ois), 1.0, duration);
+        assert_relative_eq!(adjusted, value(ois), max_relative = 1e-2);
+    }
+}
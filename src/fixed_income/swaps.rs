@@ -0,0 +1,163 @@
+use crate::fixed_income::day_count::{Date, DayCount};
+use crate::fixed_income::yield_curve::interpolate_df;
+
+/// Present value of a basis point (annuity) of a swap's fixed leg per unit notional.
+///
+/// `PVBP = sum_i tau * df(t_i)`, the value of receiving one unit of rate on every payment date.
+/// Multiply by `1e-4` for the value of one basis point.
+///
+/// # Arguments
+///  - pay_times: fixed leg payment times in years
+///  - discount_factors: discount factors at `pay_times`
+///  - tau: accrual fraction of each period
+pub fn swap_pvbp(pay_times: &[f64], discount_factors: &[f64], tau: f64) -> f64 {
+    debug_assert_eq!(pay_times.len(), discount_factors.len());
+    discount_factors.iter().map(|df| tau * df).sum()
+}
+
+/// Par rate of a spot-starting swap, `(df(0) - df(T)) / PVBP` with `df(0) = 1`.
+///
+/// The floating leg of a spot-starting swap is worth `1 - df(T)` per unit notional, so this is
+/// the fixed rate at which both legs have equal value.
+///
+/// # Arguments
+///  - pay_times: fixed leg payment times in years, the last being maturity `T`
+///  - discount_factors: discount factors at `pay_times`
+///  - tau: accrual fraction of each period
+pub fn par_swap_rate(pay_times: &[f64], discount_factors: &[f64], tau: f64) -> f64 {
+    let df_end = discount_factors.last().copied().unwrap_or(1.0);
+    (1.0 - df_end) / swap_pvbp(pay_times, discount_factors, tau)
+}
+
+/// Present value of a swap's fixed leg with accruals from a day count convention.
+///
+/// The coupon on each period is `notional * fixed_rate * tau_i`, where `tau_i` is the year
+/// fraction of the period under `day_count`, paid at the end of the period. Payment times on
+/// the discount curve are measured Actual/365 Fixed from the first date.
+///
+/// # Arguments
+///  - notional: notional amount
+///  - fixed_rate: annual fixed rate
+///  - payment_dates: accrual start date followed by the payment date of every period
+///  - discount_factors: `(time, discount_factor)` pairs, log-linearly interpolated
+///  - day_count: day count convention of the fixed leg
+///
+/// # Returns
+/// Present value of the fixed coupons as of the first date
+pub fn fixed_leg_pv(
+    notional: f64,
+    fixed_rate: f64,
+    payment_dates: &[Date],
+    discount_factors: &[(f64, f64)],
+    day_count: DayCount,
+) -> f64 {
+    let Some(&start) = payment_dates.first() else {
+        return 0.0;
+    };
+    payment_dates
+        .windows(2)
+        .map(|w| {
+            let tau = day_count.year_fraction(w[0], w[1]);
+            let t = DayCount::Actual365Fixed.year_fraction(start, w[1]);
+            notional * fixed_rate * tau * interpolate_df(discount_factors, t)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn curve() -> (Vec<f64>, Vec<f64>) {
+        let times: Vec<f64> = (1..=10).map(|i| i as f64 * 0.5).collect();
+        let dfs = times
+            .iter()
+            .map(|t| (-(0.02 + 0.004 * t) * t).exp())
+            .collect();
+        (times, dfs)
+    }
+
+    #[test]
+    fn test_par_rate_gives_zero_npv() {
+        let (times, dfs) = curve();
+        let rate = par_swap_rate(&times, &dfs, 0.5);
+        // Floating leg off the same curve, with simple forwards over each period.
+        let mut prev = 1.0;
+        let floating: f64 = dfs
+            .iter()
+            .map(|&df| {
+                let forward = (prev / df - 1.0) / 0.5;
+                prev = df;
+                0.5 * forward * df
+            })
+            .sum();
+        let fixed: f64 = dfs.iter().map(|df| 0.5 * rate * df).sum();
+        assert_relative_eq!(fixed - floating, 0.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_pvbp_is_dv01_per_unit_notional() {
+        let (times, dfs) = curve();
+        let pvbp = swap_pvbp(&times, &dfs, 0.5);
+        let fixed_leg = |coupon: f64| dfs.iter().map(|df| 0.5 * coupon * df).sum::<f64>();
+        let dv01 = fixed_leg(0.0301) - fixed_leg(0.03);
+        assert_relative_eq!(dv01, pvbp * 1e-4, max_relative = 1e-10);
+    }
+
+    /// Annual dates from 15 January 2023 for `years` years, the first being the start.
+    fn annual_dates(years: i32) -> Vec<Date> {
+        (0..=years)
+            .map(|i| Date::from_ymd(2023 + i, 1, 15).unwrap())
+            .collect()
+    }
+
+    const CURVE: [(f64, f64); 4] = [(1.0, 0.97), (2.0, 0.935), (5.0, 0.84), (10.0, 0.70)];
+
+    #[test]
+    fn test_thirty_360_full_years() {
+        let dates = annual_dates(8);
+        for w in dates.windows(2) {
+            assert_eq!(DayCount::Thirty360.year_fraction(w[0], w[1]), 1.0);
+            // Actual/365 counts the leap day in 2024.
+            let act = DayCount::Actual365Fixed.year_fraction(w[0], w[1]);
+            let days = w[0].days_until(w[1]) as f64;
+            assert_eq!(act, days / 365.0);
+            assert_eq!(act == 1.0, days == 365.0);
+        }
+        let thirty = fixed_leg_pv(1e6, 0.04, &dates, &CURVE, DayCount::Thirty360);
+        let actual = fixed_leg_pv(1e6, 0.04, &dates, &CURVE, DayCount::Actual365Fixed);
+        // Two leap days over eight years accrue slightly more.
+        assert!(actual > thirty);
+        assert!((actual - thirty) / thirty < 1e-3);
+    }
+
+    #[test]
+    fn test_matches_pvbp_and_par_bond() {
+        let dates = annual_dates(5);
+        let start = dates[0];
+        let times: Vec<f64> = dates[1..]
+            .iter()
+            .map(|&d| DayCount::Actual365Fixed.year_fraction(start, d))
+            .collect();
+        let dfs: Vec<f64> = times.iter().map(|&t| interpolate_df(&CURVE, t)).collect();
+        let pv = fixed_leg_pv(100.0, 0.05, &dates, &CURVE, DayCount::Thirty360);
+        assert_relative_eq!(
+            pv,
+            100.0 * 0.05 * swap_pvbp(&times, &dfs, 1.0),
+            epsilon = 1e-12
+        );
+
+        // A bond paying the par rate as coupon, plus its principal, is worth par.
+        for day_count in [
+            DayCount::Thirty360,
+            DayCount::Actual365Fixed,
+            DayCount::ActualActual,
+        ] {
+            let annuity = fixed_leg_pv(1.0, 1.0, &dates, &CURVE, day_count);
+            let par = (1.0 - dfs[dfs.len() - 1]) / annuity;
+            let bond = fixed_leg_pv(100.0, par, &dates, &CURVE, day_count) + 100.0 * dfs[4];
+            assert_relative_eq!(bond, 100.0, epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::optimization::{secant, SecantError};
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Prices a European swaption with Black's model on the forward swap rate.
+///
+/// With the annuity (PVBP) as numeraire the forward swap rate is a martingale, so
+/// payer = `PVBP * (F N(d1) - K N(d2))` and receiver = `PVBP * (K N(-d2) - F N(-d1))`, where
+/// `d1 = (ln(F/K) + sigma^2 T / 2) / (sigma sqrt(T))` and `d2 = d1 - sigma sqrt(T)`.
+///
+/// # Arguments
+///  - swap_rate: forward par swap rate (F)
+///  - strike: fixed rate of the underlying swap (K)
+///  - pvbp: annuity of the underlying swap per unit notional
+///  - t_expiry: time to swaption expiry in years
+///  - sigma: lognormal (Black) volatility of the swap rate
+///  - is_payer: true for a payer swaption (right to pay fixed), false for a receiver
+///
+/// # Returns
+/// Swaption price per unit notional
+pub fn swaption_black(
+    swap_rate: f64,
+    strike: f64,
+    pvbp: f64,
+    t_expiry: f64,
+    sigma: f64,
+    is_payer: bool,
+) -> f64 {
+    if t_expiry <= 0.0 || sigma <= 0.0 {
+        let intrinsic = if is_payer {
+            swap_rate - strike
+        } else {
+            strike - swap_rate
+        };
+        return pvbp * intrinsic.max(0.0);
+    }
+    let sd = sigma * t_expiry.sqrt();
+    let d1 = ((swap_rate / strike).ln() + 0.5 * sd * sd) / sd;
+    let d2 = d1 - sd;
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    if is_payer {
+        pvbp * (swap_rate * stdn.cdf(d1) - strike * stdn.cdf(d2))
+    } else {
+        pvbp * (strike * stdn.cdf(-d2) - swap_rate * stdn.cdf(-d1))
+    }
+}
+
+/// Black implied volatility of a swaption price, solved with the secant method.
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]> with the lognormal swap rate volatility
+pub fn swaption_implied_vol_black(
+    price: f64,
+    swap_rate: f64,
+    strike: f64,
+    pvbp: f64,
+    t_expiry: f64,
+    is_payer: bool,
+) -> Result<f64, SecantError> {
+    let f = |sigma: f64| swaption_black(swap_rate, strike, pvbp, t_expiry, sigma, is_payer) - price;
+    secant(f, 0.1, 0.3, 1e-12, 1e-12, 100).map(|res| res.root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_payer_receiver_parity() {
+        // Payer minus receiver is a forward-starting payer swap worth PVBP * (F - K).
+        let (f, pvbp, t, sigma) = (0.035, 4.2, 2.0, 0.25);
+        for &k in &[0.02, 0.035, 0.05] {
+            let payer = swaption_black(f, k, pvbp, t, sigma, true);
+            let receiver = swaption_black(f, k, pvbp, t, sigma, false);
+            assert_relative_eq!(payer - receiver, pvbp * (f - k), epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_atm_price_scales_with_pvbp() {
+        let full = swaption_black(0.03, 0.03, 4.0, 1.0, 0.2, true);
+        let half = swaption_black(0.03, 0.03, 2.0, 1.0, 0.2, true);
+        assert_relative_eq!(half, 0.5 * full, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_implied_vol_round_trip() {
+        for &(k, is_payer) in &[(0.025, true), (0.03, true), (0.04, false)] {
+            let price = swaption_black(0.03, k, 4.0, 1.5, 0.22, is_payer);
+            let vol = swaption_implied_vol_black(price, 0.03, k, 4.0, 1.5, is_payer)
+                .expect("implied vol should converge");
+            assert_relative_eq!(vol, 0.22, epsilon = 1e-8);
+        }
+    }
+}
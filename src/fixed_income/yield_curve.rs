@@ -0,0 +1,308 @@
+use crate::optimization::{nelder_mead, CalibrationResult};
+
+/// Integrated rate `r(t) * t` on a flat-forward curve.
+///
+/// Linear in `t` between nodes, with the first zero rate held flat from 0 to the first node and
+/// the last forward rate extended beyond the last node.
+fn integrated_rate(maturities: &[f64], zero_rates: &[f64], t: f64) -> f64 {
+    let n = maturities.len();
+    let i = maturities.partition_point(|&m| m < t);
+    if i == 0 || n == 1 {
+        return zero_rates[0] * t;
+    }
+    let i = i.min(n - 1);
+    let (t0, t1) = (maturities[i - 1], maturities[i]);
+    let (y0, y1) = (zero_rates[i - 1] * t0, zero_rates[i] * t1);
+    let forward = (y1 - y0) / (t1 - t0);
+    y0 + forward * (t - t0)
+}
+
+/// Interpolates a continuously compounded zero rate assuming a flat instantaneous forward rate
+/// between adjacent grid points.
+///
+/// Between nodes `t_i < t < t_{i+1}` the forward rate is
+/// `f_i = (r_{i+1} * t_{i+1} - r_i * t_i) / (t_{i+1} - t_i)` and `r(t) * t = r_i * t_i + f_i * (t - t_i)`.
+/// The first zero rate is used before the first node and the last forward rate beyond the last.
+///
+/// # Arguments
+///  - maturities: increasing grid maturities in years
+///  - zero_rates: continuously compounded zero rates at the grid maturities
+///  - t: maturity to interpolate at
+///
+/// # Returns
+/// Zero rate at `t`
+pub fn flat_forward_interpolate(maturities: &[f64], zero_rates: &[f64], t: f64) -> f64 {
+    if t <= 0.0 {
+        return zero_rates[0];
+    }
+    integrated_rate(maturities, zero_rates, t) / t
+}
+
+/// Discount factor from log-linear interpolation of the grid discount factors.
+///
+/// Interpolating `ln(P(t))` linearly is the same as holding the forward rate flat between nodes,
+/// so this is `exp(-r(t) * t)` with `r(t)` from [`flat_forward_interpolate`].
+///
+/// # Returns
+/// Discount factor at `t`
+pub fn log_linear_discount(maturities: &[f64], zero_rates: &[f64], t: f64) -> f64 {
+    if t <= 0.0 {
+        return 1.0;
+    }
+    (-integrated_rate(maturities, zero_rates, t)).exp()
+}
+
+/// Discount factor at `t`, log-linearly interpolated from `(time, discount_factor)` pairs.
+///
+/// `ln(P)` is interpolated directly, so a node at `t = 0` is allowed. Before the first node
+/// `ln(P)` is linear from `P(0) = 1` and beyond the last node the last forward rate is extended.
+pub(crate) fn interpolate_df(discount_factors: &[(f64, f64)], t: f64) -> f64 {
+    if t <= 0.0 {
+        return 1.0;
+    }
+    let n = discount_factors.len();
+    let i = discount_factors.partition_point(|&(ti, _)| ti < t);
+    if i == 0 || n == 1 {
+        let (t0, df0) = discount_factors[0];
+        return if t0 > 0.0 { df0.powf(t / t0) } else { df0 };
+    }
+    let i = i.min(n - 1);
+    let ((t0, df0), (t1, df1)) = (discount_factors[i - 1], discount_factors[i]);
+    let (y0, y1) = (df0.ln(), df1.ln());
+    (y0 + (y1 - y0) * (t - t0) / (t1 - t0)).exp()
+}
+
+/// Nelson-Siegel zero rate at maturity `t`.
+///
+/// `y(t) = beta0 + beta1 * g(t / tau) + beta2 * (g(t / tau) - exp(-t / tau))` with
+/// `g(x) = (1 - exp(-x)) / x`. `beta0` is the long-end level, `beta0 + beta1` the short rate and
+/// `beta2` the size of the hump, located by `tau`.
+///
+/// # Arguments
+///  - params: `[beta0, beta1, beta2, tau]`
+///  - t: maturity in years
+pub fn nelson_siegel_yield(params: &[f64; 4], t: f64) -> f64 {
+    let [beta0, beta1, beta2, tau] = *params;
+    let x = t / tau;
+    let decay = (-x).exp();
+    // g(x) -> 1 as x -> 0.
+    let loading = if x.abs() < 1e-10 {
+        1.0 - 0.5 * x
+    } else {
+        (1.0 - decay) / x
+    };
+    beta0 + beta1 * loading + beta2 * (loading - decay)
+}
+
+/// Fits the Nelson-Siegel curve to observed zero rates by least squares with Nelder-Mead.
+///
+/// The search starts from `beta0` at the longest observed yield, `beta1` at the shortest minus
+/// the longest, `beta2 = 0` and `tau = 2`. It is restarted from its own result to escape a
+/// collapsed simplex. Non-positive `tau` is infeasible.
+///
+/// # Arguments
+///  - maturities: maturities in years
+///  - yields: continuously compounded zero rates at those maturities
+///
+/// # Returns
+/// [`Result`]<[`CalibrationResult`]<`[f64; 4]`>, `&'static str`> with
+/// `[beta0, beta1, beta2, tau]` and yield errors; iterations are summed over the restarts and
+/// convergence is that of the last one
+pub fn fit_nelson_siegel(
+    maturities: &[f64],
+    yields: &[f64],
+) -> Result<CalibrationResult<[f64; 4]>, &'static str> {
+    if maturities.len() != yields.len() || maturities.len() < 4 {
+        return Err("at least four maturities and yields of equal length are needed");
+    }
+    if maturities.iter().any(|&t| t <= 0.0) {
+        return Err("maturities must be positive");
+    }
+    let by_maturity = |a: &&(f64, f64), b: &&(f64, f64)| a.0.total_cmp(&b.0);
+    let points: Vec<(f64, f64)> = maturities
+        .iter()
+        .copied()
+        .zip(yields.iter().copied())
+        .collect();
+    let short_end = points.iter().min_by(by_maturity).unwrap().1;
+    let long_end = points.iter().max_by(by_maturity).unwrap().1;
+
+    let objective = |x: &[f64]| {
+        if x[3] <= 0.0 {
+            return f64::INFINITY;
+        }
+        let params = [x[0], x[1], x[2], x[3]];
+        points
+            .iter()
+            .map(|&(t, y)| (nelson_siegel_yield(&params, t) - y).powi(2))
+            .sum()
+    };
+    let mut x = vec![long_end, short_end - long_end, 0.0, 2.0];
+    let (mut n_iterations, mut converged) = (0, false);
+    for _ in 0..3 {
+        let search = nelder_mead(objective, &x, 0.1, 1e-10, 1e-18, 10_000);
+        n_iterations += search.iterations;
+        converged = search.converged;
+        x = search.x;
+    }
+    if !x.iter().all(|v| v.is_finite()) {
+        return Err("Nelson-Siegel fit did not produce finite parameters");
+    }
+    let params = [x[0], x[1], x[2], x[3]];
+    let residuals: Vec<f64> = points
+        .iter()
+        .map(|&(t, y)| nelson_siegel_yield(&params, t) - y)
+        .collect();
+    Ok(CalibrationResult::from_residuals(
+        params,
+        &residuals,
+        n_iterations,
+        converged,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const MATURITIES: [f64; 5] = [0.5, 1.0, 2.0, 5.0, 10.0];
+    const RATES: [f64; 5] = [0.03, 0.032, 0.035, 0.034, 0.038];
+
+    #[test]
+    fn test_methods_agree_at_nodes() {
+        for (&t, &r) in MATURITIES.iter().zip(RATES.iter()) {
+            assert_relative_eq!(
+                flat_forward_interpolate(&MATURITIES, &RATES, t),
+                r,
+                epsilon = 1e-14
+            );
+            assert_relative_eq!(
+                log_linear_discount(&MATURITIES, &RATES, t),
+                (-r * t).exp(),
+                epsilon = 1e-14
+            );
+        }
+    }
+
+    #[test]
+    fn test_forward_rates_piecewise_constant() {
+        let forward = |t: f64| {
+            let h = 1e-6;
+            let y = |t: f64| flat_forward_interpolate(&MATURITIES, &RATES, t) * t;
+            (y(t + h) - y(t - h)) / (2.0 * h)
+        };
+        for w in MATURITIES.windows(2) {
+            let expected = forward(0.5 * (w[0] + w[1]));
+            for j in 1..10 {
+                let t = w[0] + (w[1] - w[0]) * j as f64 / 10.0;
+                assert_relative_eq!(forward(t), expected, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interpolate_df_with_node_at_zero() {
+        let with_zero = [(0.0, 1.0), (1.0, 0.97), (2.0, 0.935), (5.0, 0.84)];
+        let without_zero = &with_zero[1..];
+        for i in 0..=70 {
+            let t = i as f64 * 0.1;
+            let df = interpolate_df(&with_zero, t);
+            assert!(df.is_finite() && df > 0.0, "t={} df={}", t, df);
+            assert_relative_eq!(df, interpolate_df(without_zero, t), max_relative = 1e-14);
+        }
+        for &(t, df) in &with_zero {
+            assert_relative_eq!(interpolate_df(&with_zero, t), df, max_relative = 1e-14);
+        }
+        // Log-linear between nodes: the geometric mean at the midpoint.
+        assert_relative_eq!(
+            interpolate_df(&with_zero, 1.5),
+            (0.97_f64 * 0.935).sqrt(),
+            max_relative = 1e-14
+        );
+    }
+
+    #[test]
+    fn test_discount_factors_positive() {
+        let inverted = [0.08, 0.05, 0.01, -0.01, -0.02];
+        for rates in [&RATES, &inverted] {
+            for i in 0..=150 {
+                let t = i as f64 * 0.1;
+                assert!(log_linear_discount(&MATURITIES, rates, t) > 0.0);
+                let r = flat_forward_interpolate(&MATURITIES, rates, t);
+                assert!((-r * t).exp() > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nelson_siegel_fit_matches_observed_yields() {
+        let truth = [0.045, -0.02, 0.015, 1.5];
+        let maturities = [0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 20.0, 30.0];
+        let yields: Vec<f64> = maturities
+            .iter()
+            .map(|&t| nelson_siegel_yield(&truth, t))
+            .collect();
+        let params = fit_nelson_siegel(&maturities, &yields).unwrap().params;
+        for (&t, &y) in maturities.iter().zip(&yields) {
+            assert!(
+                (nelson_siegel_yield(&params, t) - y).abs() < 5e-4,
+                "t={}",
+                t
+            );
+        }
+        // An inverted curve with 1bp of alternating noise, so not exactly Nelson-Siegel.
+        let inverted = [0.04, 0.01, -0.02, 2.5];
+        let yields: Vec<f64> = maturities
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| {
+                nelson_siegel_yield(&inverted, t) + if i % 2 == 0 { 1e-4 } else { -1e-4 }
+            })
+            .collect();
+        let params = fit_nelson_siegel(&maturities, &yields).unwrap().params;
+        for (&t, &y) in maturities.iter().zip(&yields) {
+            assert!(
+                (nelson_siegel_yield(&params, t) - y).abs() < 5e-4,
+                "t={}",
+                t
+            );
+        }
+        assert!(params[3] > 0.0);
+    }
+
+    #[test]
+    fn test_nelson_siegel_fit_quality() {
+        let maturities = [0.5, 1.0, 2.0, 3.0, 5.0, 10.0, 30.0];
+        let yields = [0.031, 0.033, 0.032, 0.036, 0.038, 0.041, 0.040];
+        let fit = fit_nelson_siegel(&maturities, &yields).unwrap();
+        let errors: Vec<f64> = maturities
+            .iter()
+            .zip(&yields)
+            .map(|(&t, &y)| nelson_siegel_yield(&fit.params, t) - y)
+            .collect();
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        assert_relative_eq!(fit.rmse_vol, rmse, max_relative = 1e-12);
+        assert!(fit.max_error_vol >= fit.rmse_vol && fit.max_error_vol < 2e-3);
+        assert!(fit.converged && fit.n_iterations > 0);
+    }
+
+    #[test]
+    fn test_nelson_siegel_flat_curve() {
+        let maturities = [0.5, 1.0, 2.0, 5.0, 10.0, 30.0];
+        let params = fit_nelson_siegel(&maturities, &[0.035; 6]).unwrap().params;
+        assert_relative_eq!(params[0], 0.035, epsilon = 1e-6);
+        assert!(
+            params[1].abs() < 1e-6 && params[2].abs() < 1e-6,
+            "{:?}",
+            params
+        );
+        assert!(params[3] > 0.0);
+    }
+
+    #[test]
+    fn test_nelson_siegel_rejects_bad_input() {
+        assert!(fit_nelson_siegel(&[1.0, 2.0, 3.0], &[0.01, 0.02, 0.03]).is_err());
+        assert!(fit_nelson_siegel(&[0.0, 1.0, 2.0, 3.0], &[0.01; 4]).is_err());
+    }
+}
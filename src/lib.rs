@@ -1,12 +1,25 @@
 use pyo3::prelude::*;
 
+pub mod credit;
+pub mod fixed_income;
+pub mod math;
 pub mod optimization;
 pub mod options;
+pub mod risk;
+pub mod simulation;
+pub mod utils;
+pub mod volatility;
 
-use options::volatility_py::implied_volatility_py;
+#[cfg(test)]
+mod test_utils;
+
+use options::volatility_py::{implied_dividend_yield_py, implied_volatility_py};
+use volatility::surface_py::vol_surface_from_chain_py;
 
 #[pymodule]
 fn rustyfin(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(implied_volatility_py, m)?)?;
+    m.add_function(wrap_pyfunction!(implied_dividend_yield_py, m)?)?;
+    m.add_function(wrap_pyfunction!(vol_surface_from_chain_py, m)?)?;
     Ok(())
-}
\ No newline at end of file
+}
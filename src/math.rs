@@ -0,0 +1,11 @@
+pub mod complex;
+pub mod distributions;
+pub mod grid;
+pub mod linalg;
+pub mod quadrature;
+
+pub use complex::{Complex, ComplexExt};
+pub use distributions::bivariate_normal_cdf;
+pub(crate) use grid::even_grid;
+pub use linalg::{cholesky_decompose, linear_regression, predict};
+pub use quadrature::{gauss_legendre_nodes_weights, integrate_gl};
@@ -0,0 +1,74 @@
+/// Complex number used for characteristic function pricing.
+///
+/// This is `num_complex::Complex64`, which provides `re`/`im` fields, the arithmetic operators,
+/// `exp` and `ln`; [`ComplexExt`] adds the remaining operations used in the crate.
+pub type Complex = num_complex::Complex64;
+
+/// Convenience methods on [`Complex`].
+pub trait ComplexExt: Sized {
+    /// Absolute value `sqrt(re^2 + im^2)`.
+    fn modulus(self) -> f64;
+
+    /// Division that reports a zero divisor instead of returning NaN or infinite parts.
+    fn checked_div(self, rhs: Self) -> Result<Self, &'static str>;
+}
+
+impl ComplexExt for Complex {
+    fn modulus(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    fn checked_div(self, rhs: Self) -> Result<Self, &'static str> {
+        if rhs.re == 0.0 && rhs.im == 0.0 {
+            return Err("division by zero");
+        }
+        Ok(self / rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_euler_identity() {
+        let z = Complex::new(0.0, PI).exp() + Complex::new(1.0, 0.0);
+        assert!(z.modulus() < 1e-15, "{}", z);
+    }
+
+    #[test]
+    fn test_exp_inverts_ln() {
+        for &(re, im) in &[
+            (1.0, 0.0),
+            (-2.0, 0.5),
+            (0.3, -4.0),
+            (-1.0, -1e-3),
+            (1e3, 2e2),
+        ] {
+            let z = Complex::new(re, im);
+            let back = z.ln().exp();
+            assert_relative_eq!(back.re, re, max_relative = 1e-14, epsilon = 1e-14);
+            assert_relative_eq!(back.im, im, max_relative = 1e-14, epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_and_modulus() {
+        let (a, b) = (Complex::new(1.0, 2.0), Complex::new(3.0, -1.0));
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        assert_eq!(a - b, Complex::new(-2.0, 3.0));
+        assert_eq!(-a, Complex::new(-1.0, -2.0));
+        let q = a.checked_div(b).unwrap();
+        assert_relative_eq!((q * b - a).modulus(), 0.0, epsilon = 1e-15);
+        assert_eq!(Complex::new(3.0, 4.0).modulus(), 5.0);
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        assert!(Complex::new(1.0, 1.0)
+            .checked_div(Complex::new(0.0, 0.0))
+            .is_err());
+    }
+}
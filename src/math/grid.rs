@@ -0,0 +1,28 @@
+/// `n` evenly spaced points from `lo` to `hi` inclusive.
+///
+/// A single point is `lo`, and `n = 0` gives an empty grid.
+pub(crate) fn even_grid(lo: f64, hi: f64, n: usize) -> impl Iterator<Item = f64> {
+    let step = if n > 1 {
+        (hi - lo) / (n - 1) as f64
+    } else {
+        0.0
+    };
+    (0..n).map(move |i| lo + step * i as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_even_grid_endpoints() {
+        let grid: Vec<f64> = even_grid(60.0, 150.0, 19).collect();
+        assert_eq!(grid.len(), 19);
+        assert_eq!(grid[0], 60.0);
+        assert_relative_eq!(grid[18], 150.0, epsilon = 1e-12);
+        assert_relative_eq!(grid[1] - grid[0], 5.0, epsilon = 1e-12);
+        assert_eq!(even_grid(1.0, 2.0, 1).collect::<Vec<_>>(), vec![1.0]);
+        assert_eq!(even_grid(1.0, 2.0, 0).count(), 0);
+    }
+}
@@ -0,0 +1,184 @@
+/// Cholesky decomposition of a symmetric positive definite matrix.
+///
+/// Returns the lower triangular factor `L` such that `L * L^T = matrix`. Only the lower triangle
+/// of `matrix` is read.
+///
+/// # Returns
+/// [`Result`]<`Vec<Vec<f64>>`, `&'static str`>, an error if the matrix is not square or not
+/// positive definite
+pub fn cholesky_decompose(matrix: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, &'static str> {
+    let n = matrix.len();
+    if matrix.iter().any(|row| row.len() != n) {
+        return Err("matrix must be square");
+    }
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let d = matrix[i][i] - sum;
+                if d <= 0.0 || d.is_nan() {
+                    return Err("matrix is not positive definite");
+                }
+                l[i][j] = d.sqrt();
+            } else {
+                l[i][j] = (matrix[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Ok(l)
+}
+
+/// Ordinary least-squares coefficients `beta` minimising `||X * beta - y||^2`.
+///
+/// Solved by Householder QR on the design matrix, which avoids squaring its condition number
+/// as the normal equations would. Include a column of ones in `x` to fit an intercept.
+///
+/// # Arguments
+///  - x: design matrix, one row per observation
+///  - y: observations
+///
+/// # Returns
+/// [`Result`]<`Vec<f64>`, `&'static str`>, an error if the shapes are inconsistent or the
+/// design matrix is rank deficient
+pub fn linear_regression(x: &[Vec<f64>], y: &[f64]) -> Result<Vec<f64>, &'static str> {
+    let m = x.len();
+    let n = x.first().map_or(0, |row| row.len());
+    if m != y.len() || n == 0 || x.iter().any(|row| row.len() != n) {
+        return Err("design matrix and observations have inconsistent shapes");
+    }
+    if m < n {
+        return Err("design matrix is rank deficient");
+    }
+    // Column-major copy of the design matrix, reduced in place to R.
+    let mut a: Vec<Vec<f64>> = (0..n)
+        .map(|j| x.iter().map(|row| row[j]).collect())
+        .collect();
+    let mut b = y.to_vec();
+    for j in 0..n {
+        let norm = a[j][j..].iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            return Err("design matrix is rank deficient");
+        }
+        let alpha = if a[j][j] > 0.0 { -norm } else { norm };
+        // Householder vector v = a_j - alpha * e_j, applied as I - 2 v v^T / (v^T v).
+        let mut v = a[j][j..].to_vec();
+        v[0] -= alpha;
+        let vtv: f64 = v.iter().map(|x| x * x).sum();
+        for col in a.iter_mut().skip(j).chain(std::iter::once(&mut b)) {
+            let tail = &mut col[j..];
+            let proj = 2.0 * v.iter().zip(tail.iter()).map(|(p, q)| p * q).sum::<f64>() / vtv;
+            for (t, vi) in tail.iter_mut().zip(&v) {
+                *t -= proj * vi;
+            }
+        }
+    }
+    let max_diag = (0..n).map(|j| a[j][j].abs()).fold(0.0, f64::max);
+    let tol = f64::EPSILON * m as f64 * max_diag;
+    if (0..n).any(|j| a[j][j].abs() <= tol) {
+        return Err("design matrix is rank deficient");
+    }
+    let mut beta = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|j| a[j][i] * beta[j]).sum();
+        beta[i] = (b[i] - sum) / a[i][i];
+    }
+    Ok(beta)
+}
+
+/// Fitted value `sum(coefficients[i] * x[i])` of a linear model.
+pub fn predict(coefficients: &[f64], x: &[f64]) -> f64 {
+    coefficients.iter().zip(x).map(|(c, v)| c * v).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn times_transpose(l: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let n = l.len();
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| (0..n).map(|k| l[i][k] * l[j][k]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reconstructs_matrix() {
+        let matrix = vec![
+            vec![4.0, 2.0, 0.6],
+            vec![2.0, 5.0, -1.0],
+            vec![0.6, -1.0, 3.0],
+        ];
+        let l = cholesky_decompose(&matrix).unwrap();
+        let back = times_transpose(&l);
+        for i in 0..3 {
+            assert_eq!(l[i][i + 1..].iter().filter(|&&x| x != 0.0).count(), 0);
+            for j in 0..3 {
+                assert_relative_eq!(back[i][j], matrix[i][j], epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_by_two_correlation() {
+        let rho: f64 = 0.7;
+        let l = cholesky_decompose(&[vec![1.0, rho], vec![rho, 1.0]]).unwrap();
+        assert_relative_eq!(l[0][0], 1.0);
+        assert_relative_eq!(l[1][0], rho);
+        assert_relative_eq!(l[1][1], (1.0 - rho * rho).sqrt());
+    }
+
+    #[test]
+    fn test_not_positive_definite() {
+        assert!(cholesky_decompose(&[vec![1.0, 2.0], vec![2.0, 1.0]]).is_err());
+        assert!(cholesky_decompose(&[vec![1.0, 0.0], vec![0.0, 0.0]]).is_err());
+    }
+
+    #[test]
+    fn test_identity() {
+        let identity: Vec<Vec<f64>> = (0..4)
+            .map(|i| (0..4).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect();
+        assert_eq!(cholesky_decompose(&identity).unwrap(), identity);
+    }
+
+    #[test]
+    fn test_regression_recovers_coefficients() {
+        let x: Vec<Vec<f64>> = (0..10)
+            .map(|i| vec![i as f64, ((i * i) % 7) as f64 - 2.5])
+            .collect();
+        let y: Vec<f64> = x.iter().map(|row| 2.0 * row[0] + 3.0 * row[1]).collect();
+        let beta = linear_regression(&x, &y).unwrap();
+        assert_relative_eq!(beta[0], 2.0, epsilon = 1e-12);
+        assert_relative_eq!(beta[1], 3.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_residuals_sum_to_zero_with_intercept() {
+        let x: Vec<Vec<f64>> = (0..25)
+            .map(|i| vec![1.0, (i as f64 * 0.37).sin()])
+            .collect();
+        let y: Vec<f64> = (0..25).map(|i| (i as f64 * 0.11).exp()).collect();
+        let beta = linear_regression(&x, &y).unwrap();
+        let residual_sum: f64 = x
+            .iter()
+            .zip(&y)
+            .map(|(row, yi)| yi - predict(&beta, row))
+            .sum();
+        assert!(residual_sum.abs() < 1e-10, "residual sum {}", residual_sum);
+    }
+
+    #[test]
+    fn test_rank_deficient_design() {
+        let collinear: Vec<Vec<f64>> = (0..5).map(|i| vec![i as f64, 2.0 * i as f64]).collect();
+        let y = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!(linear_regression(&collinear, &y).is_err());
+        let too_few = vec![vec![1.0, 2.0, 3.0]];
+        assert!(linear_regression(&too_few, &[1.0]).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+/// Gauss-Legendre nodes and weights on `[-1, 1]` via the Golub-Welsch algorithm.
+///
+/// The nodes are the eigenvalues of the symmetric tridiagonal Jacobi matrix of the Legendre
+/// recurrence, with off-diagonal entries `k / sqrt(4k^2 - 1)`, and each weight is twice the
+/// squared first component of the corresponding normalised eigenvector. The eigenproblem is
+/// solved with the implicit QL algorithm, tracking only the first row of the eigenvectors.
+///
+/// # Returns
+/// (nodes, weights), sorted by increasing node
+pub fn gauss_legendre_nodes_weights(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut d = vec![0.0_f64; n];
+    let mut e: Vec<f64> = (1..=n)
+        .map(|k| {
+            if k < n {
+                let k = k as f64;
+                k / (4.0 * k * k - 1.0).sqrt()
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let mut z = vec![0.0_f64; n];
+    if n > 0 {
+        z[0] = 1.0;
+    }
+
+    for l in 0..n {
+        for _ in 0..60 {
+            let mut m = l;
+            while m + 1 < n {
+                let dd = d[m].abs() + d[m + 1].abs();
+                if e[m].abs() <= f64::EPSILON * dd {
+                    break;
+                }
+                m += 1;
+            }
+            if m == l {
+                break;
+            }
+            let mut g = (d[l + 1] - d[l]) / (2.0 * e[l]);
+            let mut r = g.hypot(1.0);
+            g = d[m] - d[l] + e[l] / (g + r.copysign(g));
+            let (mut s, mut c, mut p) = (1.0, 1.0, 0.0);
+            let mut deflated = false;
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                e[i + 1] = r;
+                if r == 0.0 {
+                    d[i + 1] -= p;
+                    e[m] = 0.0;
+                    deflated = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + 2.0 * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+                let zi1 = z[i + 1];
+                z[i + 1] = s * z[i] + c * zi1;
+                z[i] = c * z[i] - s * zi1;
+            }
+            if deflated {
+                continue;
+            }
+            d[l] -= p;
+            e[l] = g;
+            e[m] = 0.0;
+        }
+    }
+
+    let mut pairs: Vec<(f64, f64)> = d
+        .into_iter()
+        .zip(z.into_iter().map(|v| 2.0 * v * v))
+        .collect();
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    pairs.into_iter().unzip()
+}
+
+/// Integrates `f` over `[a, b]` with `n`-point Gauss-Legendre quadrature.
+///
+/// Exact for polynomials of degree up to `2n - 1`.
+pub fn integrate_gl<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, n: usize) -> f64 {
+    let (nodes, weights) = gauss_legendre_nodes_weights(n);
+    let half = 0.5 * (b - a);
+    let mid = 0.5 * (a + b);
+    half * nodes
+        .iter()
+        .zip(&weights)
+        .map(|(x, w)| w * f(mid + half * x))
+        .sum::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_integrates_sine() {
+        assert_relative_eq!(integrate_gl(f64::sin, 0.0, PI, 20), 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_integrates_gaussian() {
+        let value = integrate_gl(|x| (-x * x).exp(), -10.0, 10.0, 80);
+        assert_relative_eq!(value, PI.sqrt(), epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_nodes_and_weights() {
+        for n in [1, 2, 5, 20, 64] {
+            let (nodes, weights) = gauss_legendre_nodes_weights(n);
+            assert!(nodes.iter().all(|x| (-1.0..=1.0).contains(x)));
+            assert!(nodes.windows(2).all(|w| w[0] < w[1]));
+            assert_relative_eq!(weights.iter().sum::<f64>(), 2.0, epsilon = 1e-13);
+        }
+        // Two-point rule: nodes +-1/sqrt(3), unit weights.
+        let (nodes, weights) = gauss_legendre_nodes_weights(2);
+        assert_relative_eq!(nodes[1], 1.0 / 3.0_f64.sqrt(), epsilon = 1e-15);
+        assert_relative_eq!(weights[0], 1.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_exact_for_polynomials() {
+        // 5 points integrate degree 9 exactly.
+        let value = integrate_gl(|x| x.powi(9) + 3.0 * x.powi(8), -1.0, 2.0, 5);
+        let exact = (2f64.powi(10) - 1.0) / 10.0 + (2f64.powi(9) + 1.0) / 3.0;
+        assert_relative_eq!(value, exact, max_relative = 1e-13);
+    }
+}
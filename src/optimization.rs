@@ -1,3 +1,9 @@
+pub mod calibration;
+pub mod differentiation;
+pub mod nelder_mead;
 pub mod root_find;
 
-pub use root_find::{secant, SecantError, SecantOk, ConvergenceType};
\ No newline at end of file
+pub use calibration::CalibrationResult;
+pub use differentiation::{auto_step, greek_fd_richardson, second_derivative_richardson};
+pub use nelder_mead::{nelder_mead, NelderMeadResult};
+pub use root_find::{brent, secant, ConvergenceType, SecantError, SecantOk};
@@ -0,0 +1,85 @@
+/// Fitted parameters of a calibration together with measures of fit quality.
+///
+/// Errors are in the units of the calibrated quotes: volatilities for smiles, yields for curves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationResult<P> {
+    /// Fitted parameters.
+    pub params: P,
+    /// Root mean square of the model-minus-market errors.
+    pub rmse_vol: f64,
+    /// Largest absolute model-minus-market error.
+    pub max_error_vol: f64,
+    /// Optimizer iterations spent on the returned fit.
+    pub n_iterations: usize,
+    /// Whether the optimizer met its tolerances before its iteration limit.
+    pub converged: bool,
+}
+
+impl<P> CalibrationResult<P> {
+    /// Builds a result from the model-minus-market errors at the fitted parameters.
+    ///
+    /// An empty set of errors gives zero RMSE and maximum error.
+    pub fn from_residuals(
+        params: P,
+        residuals: &[f64],
+        n_iterations: usize,
+        converged: bool,
+    ) -> Self {
+        let n = residuals.len().max(1) as f64;
+        let rmse_vol = (residuals.iter().map(|e| e * e).sum::<f64>() / n).sqrt();
+        let max_error_vol = residuals.iter().fold(0.0, |acc: f64, e| acc.max(e.abs()));
+        Self {
+            params,
+            rmse_vol,
+            max_error_vol,
+            n_iterations,
+            converged,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimization::nelder_mead;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_error_metrics() {
+        let result = CalibrationResult::from_residuals((), &[0.01, -0.02, 0.0, 0.005], 12, true);
+        assert_relative_eq!(
+            result.rmse_vol,
+            ((1e-4 + 4e-4 + 0.0 + 2.5e-5) / 4.0_f64).sqrt()
+        );
+        assert_eq!(result.max_error_vol, 0.02);
+        assert_eq!(result.n_iterations, 12);
+        let empty = CalibrationResult::from_residuals(1.0, &[], 0, true);
+        assert_eq!((empty.rmse_vol, empty.max_error_vol), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reports_iteration_limit() {
+        // Fit a flat level to three quotes, stopping the search after a few iterations.
+        let quotes = [0.2, 0.22, 0.25];
+        let objective = |x: &[f64]| quotes.iter().map(|q| (x[0] - q).powi(2)).sum::<f64>();
+        let residuals = |level: f64| quotes.iter().map(|q| level - q).collect::<Vec<f64>>();
+        let search = nelder_mead(objective, &[1.0], 0.5, 1e-12, 1e-16, 3);
+        let result = CalibrationResult::from_residuals(
+            search.x[0],
+            &residuals(search.x[0]),
+            search.iterations,
+            search.converged,
+        );
+        assert!(!result.converged);
+        assert_eq!(result.n_iterations, 3);
+        let search = nelder_mead(objective, &[1.0], 0.5, 1e-12, 1e-16, 1_000);
+        let result = CalibrationResult::from_residuals(
+            search.x[0],
+            &residuals(search.x[0]),
+            search.iterations,
+            search.converged,
+        );
+        assert!(result.converged);
+        assert_relative_eq!(result.params, 0.67 / 3.0, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,60 @@
+/// Default step for a fourth-order central difference at `x`, balancing truncation and round-off error.
+pub fn auto_step(x: f64) -> f64 {
+    f64::EPSILON.powf(0.2) * x.abs().max(1.0)
+}
+
+/// First derivative of `f` at `x` by Richardson-extrapolated central differences.
+///
+/// Combines the central differences with steps `h` and `2h`,
+/// `(8 * (f(x+h) - f(x-h)) - (f(x+2h) - f(x-2h))) / (12h)`, which cancels the `O(h^2)` error
+/// term of the plain central difference `(f(x+h) - f(x-h)) / (2h)` and is accurate to `O(h^4)`.
+pub fn greek_fd_richardson<F: Fn(f64) -> f64>(f: F, x: f64, h: f64) -> f64 {
+    (8.0 * (f(x + h) - f(x - h)) - (f(x + 2.0 * h) - f(x - 2.0 * h))) / (12.0 * h)
+}
+
+/// Second derivative of `f` at `x` by repeated Richardson extrapolation.
+///
+/// Builds a Richardson tableau from the central second differences
+/// `(f(x+h) - 2f(x) + f(x-h)) / h^2` at steps `h, h/2, h/4, h/8`. Each level removes the next even
+/// power of `h` from the error expansion, giving `O(h^8)` accuracy.
+pub fn second_derivative_richardson<F: Fn(f64) -> f64>(f: F, x: f64, h: f64) -> f64 {
+    const LEVELS: usize = 4;
+    let fx = f(x);
+    let central = |h: f64| (f(x + h) - 2.0 * fx + f(x - h)) / (h * h);
+
+    let mut tableau: Vec<f64> = (0..LEVELS)
+        .map(|i| central(h / 2f64.powi(i as i32)))
+        .collect();
+    for level in 1..LEVELS {
+        let factor = 4f64.powi(level as i32);
+        for i in (level..LEVELS).rev() {
+            tableau[i] = (factor * tableau[i] - tableau[i - 1]) / (factor - 1.0);
+        }
+    }
+    tableau[LEVELS - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_richardson_first_derivative() {
+        let x = 0.7_f64;
+        let plain = ((x + 0.1).sin() - (x - 0.1).sin()) / 0.2;
+        let richardson = greek_fd_richardson(f64::sin, x, 0.1);
+        assert!((richardson - x.cos()).abs() < (plain - x.cos()).abs() / 100.0);
+        assert_relative_eq!(
+            greek_fd_richardson(f64::sin, x, auto_step(x)),
+            x.cos(),
+            epsilon = 1e-11
+        );
+    }
+
+    #[test]
+    fn test_richardson_second_derivative_of_exp() {
+        let d2 = second_derivative_richardson(f64::exp, 0.0, 0.1);
+        assert_relative_eq!(d2, 1.0, epsilon = 1e-10);
+    }
+}
@@ -0,0 +1,157 @@
+/// Result of a Nelder-Mead minimisation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NelderMeadResult {
+    /// Best point found.
+    pub x: Vec<f64>,
+    /// Objective value at `x`.
+    pub fx: f64,
+    /// Number of iterations performed.
+    pub iterations: usize,
+    /// Whether the simplex collapsed below the tolerances before `max_iter` was reached.
+    pub converged: bool,
+}
+
+/// Nelder-Mead downhill simplex minimisation.
+///
+/// Derivative free, which makes it robust for calibration objectives that are noisy or only
+/// piecewise smooth. Constraints are best handled by reparametrising (e.g. optimising `ln(sigma)`)
+/// or by returning `f64::INFINITY` outside the feasible region.
+///
+/// # Arguments
+/// - `f`: objective function to minimise.
+/// - `x0`: starting point.
+/// - `step`: initial simplex edge length along each coordinate (relative to `|x0_i|` when non-zero).
+/// - `xtol`: absolute tolerance on the simplex diameter.
+/// - `ftol`: absolute tolerance on the spread of objective values across the simplex.
+/// - `max_iter`: maximum number of iterations.
+///
+/// # Returns
+/// [`NelderMeadResult`] with the best point found; check `converged`.
+pub fn nelder_mead(
+    f: impl Fn(&[f64]) -> f64,
+    x0: &[f64],
+    step: f64,
+    xtol: f64,
+    ftol: f64,
+    max_iter: usize,
+) -> NelderMeadResult {
+    const ALPHA: f64 = 1.0;
+    const GAMMA: f64 = 2.0;
+    const RHO: f64 = 0.5;
+    const SHRINK: f64 = 0.5;
+
+    let n = x0.len();
+    let eval = |x: &[f64]| {
+        let v = f(x);
+        if v.is_nan() {
+            f64::INFINITY
+        } else {
+            v
+        }
+    };
+
+    let mut simplex: Vec<Vec<f64>> = vec![x0.to_vec()];
+    for i in 0..n {
+        let mut x = x0.to_vec();
+        x[i] += if x[i] != 0.0 { step * x[i].abs() } else { step };
+        simplex.push(x);
+    }
+    let mut values: Vec<f64> = simplex.iter().map(|x| eval(x)).collect();
+
+    for iteration in 0..max_iter {
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+        simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+
+        let diameter = simplex[1..]
+            .iter()
+            .flat_map(|x| x.iter().zip(&simplex[0]).map(|(a, b)| (a - b).abs()))
+            .fold(0.0, f64::max);
+        if diameter <= xtol && (values[n] - values[0]).abs() <= ftol {
+            return NelderMeadResult {
+                x: simplex[0].clone(),
+                fx: values[0],
+                iterations: iteration,
+                converged: true,
+            };
+        }
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|x| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let towards = |coef: f64| -> Vec<f64> {
+            (0..n)
+                .map(|j| centroid[j] + coef * (simplex[n][j] - centroid[j]))
+                .collect()
+        };
+
+        let reflected = towards(-ALPHA);
+        let f_reflected = eval(&reflected);
+        if f_reflected < values[0] {
+            let expanded = towards(-GAMMA);
+            let f_expanded = eval(&expanded);
+            if f_expanded < f_reflected {
+                simplex[n] = expanded;
+                values[n] = f_expanded;
+            } else {
+                simplex[n] = reflected;
+                values[n] = f_reflected;
+            }
+        } else if f_reflected < values[n - 1] {
+            simplex[n] = reflected;
+            values[n] = f_reflected;
+        } else {
+            let contracted = if f_reflected < values[n] {
+                towards(-RHO)
+            } else {
+                towards(RHO)
+            };
+            let f_contracted = eval(&contracted);
+            if f_contracted < values[n].min(f_reflected) {
+                simplex[n] = contracted;
+                values[n] = f_contracted;
+            } else {
+                for i in 1..=n {
+                    simplex[i] = (0..n)
+                        .map(|j| simplex[0][j] + SHRINK * (simplex[i][j] - simplex[0][j]))
+                        .collect();
+                    values[i] = eval(&simplex[i]);
+                }
+            }
+        }
+    }
+
+    let best = (0..=n)
+        .min_by(|&a, &b| values[a].total_cmp(&values[b]))
+        .unwrap();
+    NelderMeadResult {
+        x: simplex[best].clone(),
+        fx: values[best],
+        iterations: max_iter,
+        converged: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_rosenbrock() {
+        let f = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let res = nelder_mead(f, &[-1.2, 1.0], 0.1, 1e-10, 1e-14, 5_000);
+        assert!(res.converged);
+        assert_relative_eq!(res.x[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.x[1], 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_max_iter_not_converged() {
+        let f = |x: &[f64]| x.iter().map(|v| v * v).sum::<f64>();
+        let res = nelder_mead(f, &[3.0, -2.0, 1.0], 0.5, 1e-12, 1e-12, 5);
+        assert!(!res.converged);
+        assert_eq!(res.iterations, 5);
+    }
+}
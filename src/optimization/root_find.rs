@@ -14,6 +14,8 @@ pub enum SecantError {
     MaxIterationsExceeded,
     /// The denominator (f(x1) - f(x0)) became too small indicating a possible repeated root or stagnation.
     DivisionByZero,
+    /// The target lies outside the range the function can attain, so no root exists.
+    OutOfBounds,
 }
 
 /// Result of a successful secant method execution.
@@ -25,6 +27,29 @@ pub struct SecantOk {
     pub iterations: usize,
     /// Which convergence criterion triggered termination.
     pub convergence_type: ConvergenceType,
+    /// Iteration budget the solver was given.
+    pub max_iter: usize,
+    /// Function value tolerance the solver was given (0 for [`brent`], which stops only on an exact zero).
+    pub ftol: f64,
+}
+
+impl SecantOk {
+    /// Whether the convergence can be trusted.
+    ///
+    /// Returns `false` when the solver stopped on x-tolerance at its very last iteration, which
+    /// suggests the steps were still shrinking slowly rather than settled, or when it stopped on
+    /// f-tolerance that was looser than `ftol_threshold`.
+    pub fn is_reliable(&self, ftol_threshold: f64) -> bool {
+        match self.convergence_type {
+            ConvergenceType::XTolerance => self.iterations + 1 < self.max_iter,
+            ConvergenceType::FTolerance => self.ftol <= ftol_threshold,
+        }
+    }
+
+    /// Absolute residual `|f(root)|`, evaluated post hoc.
+    pub fn residual(&self, f: impl Fn(f64) -> f64) -> f64 {
+        f(self.root).abs()
+    }
 }
 
 /// Secant root-finding method.
@@ -52,14 +77,14 @@ pub fn secant(
         let f0 = f(x0);
         let f1 = f(x1);
         if f1.abs() < ftol {
-            return Ok(SecantOk { root: x1, iterations: i, convergence_type: ConvergenceType::FTolerance });
+            return Ok(SecantOk { root: x1, iterations: i, convergence_type: ConvergenceType::FTolerance, max_iter, ftol });
         }
         else if (f1 - f0).abs() < ftol {
             return Err(SecantError::DivisionByZero);
         }
         let x2 = x1 - f1 * (x1 - x0) / (f1 - f0);
         if (x2 - x1).abs() < xtol{
-            return Ok(SecantOk { root: x2, iterations: i, convergence_type: ConvergenceType::XTolerance } );
+            return Ok(SecantOk { root: x2, iterations: i, convergence_type: ConvergenceType::XTolerance, max_iter, ftol } );
         }
         x0 = x1;
         x1 = x2;
@@ -67,6 +92,98 @@ pub fn secant(
     Err(SecantError::MaxIterationsExceeded)
 }
 
+/// Brent's root-finding method on a bracketing interval.
+///
+/// Combines inverse quadratic interpolation and secant steps with bisection, so it always
+/// converges once `f(a)` and `f(b)` have opposite signs, and every iterate stays in `[a, b]`.
+///
+/// # Arguments
+/// - `f`: function whose root is sought.
+/// - `a`, `b`: ends of the bracketing interval.
+/// - `xtol`: absolute tolerance on the width of the bracket.
+/// - `max_iter`: maximum number of iterations.
+///
+/// # Returns
+/// [`Result`]<[`SecantOk`], [`SecantError`]>`, with [`SecantError::OutOfBounds`] if `[a, b]`
+/// does not bracket a root.
+pub fn brent(
+    f: impl Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    xtol: f64,
+    max_iter: usize,
+) -> Result<SecantOk, SecantError> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Ok(SecantOk { root: a, iterations: 0, convergence_type: ConvergenceType::FTolerance, max_iter, ftol: 0.0 });
+    }
+    if fb == 0.0 {
+        return Ok(SecantOk { root: b, iterations: 0, convergence_type: ConvergenceType::FTolerance, max_iter, ftol: 0.0 });
+    }
+    if fa.signum() == fb.signum() {
+        return Err(SecantError::OutOfBounds);
+    }
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    for i in 0..max_iter {
+        // Keep the root between b and c, with b the best estimate.
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol = 2.0 * f64::EPSILON * b.abs() + 0.5 * xtol;
+        let m = 0.5 * (c - b);
+        if m.abs() <= tol {
+            return Ok(SecantOk { root: b, iterations: i, convergence_type: ConvergenceType::XTolerance, max_iter, ftol: 0.0 });
+        }
+        if fb == 0.0 {
+            return Ok(SecantOk { root: b, iterations: i, convergence_type: ConvergenceType::FTolerance, max_iter, ftol: 0.0 });
+        }
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)), (q - 1.0) * (r - 1.0) * (s - 1.0))
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+    }
+    Err(SecantError::MaxIterationsExceeded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +195,40 @@ mod tests {
         let res = secant(f, 1.0, 3.0, 1e-12, 1e-12, 50).expect("Expected convergence on trivial example.");
         assert!((res.root - 2.0).abs() < 1e-9, "root ≈ {}", res.root);
     }
+
+    #[test]
+    fn test_brent_bracketed_root() {
+        let f = |x: f64| x.powi(3) - 2.0 * x - 5.0;
+        let res = brent(f, 2.0, 3.0, 1e-14, 100).expect("Expected convergence on bracketed root.");
+        assert!((res.root - 2.0945514815423265).abs() < 1e-12, "root ≈ {}", res.root);
+        assert_eq!(brent(f, 3.0, 4.0, 1e-14, 100), Err(SecantError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_reliable_and_marginal_convergence() {
+        let f = |x: f64| x * x - 4.0;
+        let res = secant(f, 1.0, 3.0, 1e-12, 1e-12, 50).unwrap();
+        assert!(res.is_reliable(1e-10));
+        assert!(res.residual(f) < 1e-10);
+
+        // Slow linear convergence on a double root: the x-step only drops below
+        // tolerance once the budget runs out.
+        let g = |x: f64| (x - 1.0).powi(2);
+        let budget = (1..200)
+            .find(|&n| secant(g, 3.0, 2.5, 1e-6, 0.0, n).is_ok())
+            .unwrap();
+        let marginal = secant(g, 3.0, 2.5, 1e-6, 0.0, budget).unwrap();
+        assert_eq!(marginal.convergence_type, ConvergenceType::XTolerance);
+        assert_eq!(marginal.iterations, budget - 1);
+        assert!(!marginal.is_reliable(1e-10));
+        let generous = secant(g, 3.0, 2.5, 1e-6, 0.0, budget + 10).unwrap();
+        assert!(generous.is_reliable(1e-10));
+        assert_eq!(generous.root, marginal.root);
+
+        // A loose function tolerance is not trusted against a tighter threshold.
+        let loose = secant(f, 1.0, 3.0, 1e-12, 1e-2, 50).unwrap();
+        assert_eq!(loose.convergence_type, ConvergenceType::FTolerance);
+        assert!(!loose.is_reliable(1e-6));
+        assert!(loose.residual(f) < 1e-2);
+    }
 }
\ No newline at end of file
@@ -1,7 +1,68 @@
+pub mod american;
+pub mod barrier;
+pub mod binary_barrier;
+pub mod binomial;
 pub mod black_scholes;
+pub mod chain;
+pub mod exotic;
+pub mod fft;
+pub mod heston;
+pub mod jump_diffusion;
+pub mod lookback;
+pub mod lsm;
+pub mod lsm_basis;
+pub mod monte_carlo;
+pub mod multi_asset;
+pub mod nig;
+pub mod params;
+pub mod sabr;
+pub mod strategies;
+pub mod variance_gamma;
+pub mod variance_swap;
 pub mod volatility;
 pub mod volatility_py;
 
-pub use black_scholes::black_scholes;
-pub use volatility::implied_volatility;
-pub use volatility_py::implied_volatility_py;
\ No newline at end of file
+pub use american::{american_baw, american_put_boundary, american_put_kim};
+pub use barrier::{
+    barrier_option, binomial_barrier, double_barrier_knockin, double_barrier_knockout,
+    partial_barrier_option, BarrierType,
+};
+pub use binary_barrier::{no_touch, one_touch};
+pub use binomial::{
+    binomial_crr, binomial_discrete_dividend, binomial_european, binomial_lr,
+    implied_binomial_tree, implied_tree_option_price, implied_tree_state_prices,
+    tree_convergence_study,
+};
+pub use black_scholes::{
+    black_scholes, black_scholes_at_expiry, black_scholes_charm, black_scholes_color,
+    black_scholes_delta, black_scholes_dual_greeks, black_scholes_gamma, black_scholes_greeks,
+    black_scholes_merton, black_scholes_merton_greeks, black_scholes_speed, black_scholes_ts,
+    black_scholes_vanna, black_scholes_vega, black_scholes_vomma, black_scholes_zomma, bs_char_fn,
+    bs_char_fn_complex, finite_difference_greeks, forward_price, gamma_pnl_per_day,
+    implied_forward_from_options, theta_gamma_ratio, vol_smile_data, Greeks,
+};
+pub use chain::{open_interest_weighted_iv, volume_weighted_iv, OptionChain};
+pub use exotic::{calendar_spread_mc, cliquet_mc};
+pub use fft::carr_madan_fft;
+pub use heston::{heston_char_fn, heston_char_fn_complex, heston_to_sabr_approx};
+pub use jump_diffusion::{kou_char_fn, kou_price};
+pub use lookback::{lookback_floating, lookback_floating_partial};
+pub use lsm::american_lsm;
+pub use lsm_basis::{BasisFunction, HermiteBasis, LaguerreBasis, MonomialBasis, RadialBasis};
+pub use monte_carlo::{mc_delta_likelihood_ratio, mc_delta_pathwise};
+pub use multi_asset::{
+    call_on_max, call_on_max_rho_corr, exchange_option, exchange_option_rho_corr,
+    kirk_spread_option, kirk_spread_rho_corr,
+};
+pub use nig::{nig_char_fn, nig_price};
+pub use params::OptionParams;
+pub use sabr::{calibrate_sabr_slice, sabr_normal_vol, sabr_vol};
+pub use strategies::{static_replication_weights, strategy_breakeven_points};
+pub use variance_gamma::{variance_gamma_char_fn, variance_gamma_price};
+pub use variance_swap::{variance_swap_replication_pnl, variance_swap_replication_weights};
+pub use volatility::{
+    atm_call_approx, atm_vol_approx, bs_initial_vol_guess, call_price_bounds,
+    implied_dividend_yield, implied_repo_rate, implied_volatility, implied_volatility_regularized,
+    is_valid_call_price, is_valid_put_price, put_price_bounds, try_implied_volatility,
+};
+pub use volatility_py::{implied_dividend_yield_py, implied_volatility_py};
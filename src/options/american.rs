@@ -0,0 +1,395 @@
+use crate::optimization::brent;
+use crate::options::black_scholes::black_scholes_merton;
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Prices an American option with the quadratic approximation of Barone-Adesi and Whaley (1987).
+///
+/// The early exercise premium solves the Black-Scholes PDE with the time derivative dropped
+/// except through the factor `1 - exp(-r T)`, giving a power function of spot. With
+/// `n = 2 (r - q) / sigma^2` and `m = 2 r / sigma^2`, the exponents are
+/// `q1,2 = (-(n - 1) -/+ sqrt((n - 1)^2 + 4 m / (1 - exp(-r T)))) / 2`. The critical spot `S*`
+/// where exercise becomes optimal is found with Brent's method from the smooth pasting
+/// condition, and
+///
+/// - call: `c + A2 (S / S*)^q2` below `S*`, `S - K` above, with
+///   `A2 = S* / q2 * (1 - exp(-q T) N(d1(S*)))`
+/// - put: `p + A1 (S / S*)^q1` above `S*`, `K - S` below, with
+///   `A1 = -S* / q1 * (1 - exp(-q T) N(-d1(S*)))`
+///
+/// where `c` and `p` are the European prices of [`black_scholes_merton`]. A call on an asset
+/// without dividends (`q <= 0`) and a put when `r <= 0` are never exercised early and are
+/// priced as European.
+///
+/// The approximation is accurate for short and medium maturities; for long-dated options it
+/// overprices, e.g. by about 0.07 for a two-year put with `S = 44`, `K = 40` and `sigma = 0.4`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - q: continuously compounded dividend yield
+///  - sigma: volatility (annualized)
+///  - is_call: true for call option, false for put option
+pub fn american_baw(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, is_call: bool) -> f64 {
+    let intrinsic = if is_call { s - k } else { k - s }.max(0.0);
+    let european = |spot: f64| {
+        let (call, put) = black_scholes_merton(spot, k, t, r, q, sigma);
+        if is_call {
+            call
+        } else {
+            put
+        }
+    };
+    if t <= 0.0 || sigma <= 0.0 {
+        return european(s).max(intrinsic);
+    }
+    if (is_call && q <= 0.0) || (!is_call && r <= 0.0) {
+        return european(s);
+    }
+
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let var = sigma * sigma;
+    let carry = (-q * t).exp();
+    let d1 = |spot: f64| ((spot / k).ln() + (r - q + 0.5 * var) * t) / (sigma * t.sqrt());
+    let n = 2.0 * (r - q) / var;
+    // `m / (1 - exp(-r T))` with `m = 2 r / sigma^2`, which tends to `2 / (sigma^2 T)` as `r -> 0`.
+    let rt = r * t;
+    let discount_ratio = if rt.abs() < 1e-8 {
+        1.0 + 0.5 * rt
+    } else {
+        rt / -(-rt).exp_m1()
+    };
+    let m_scaled = 2.0 / (var * t) * discount_ratio;
+    let root = ((n - 1.0).powi(2) + 4.0 * m_scaled).sqrt();
+    let exponent = if is_call {
+        0.5 * (-(n - 1.0) + root)
+    } else {
+        0.5 * (-(n - 1.0) - root)
+    };
+    // Coefficient of the premium, `A / (S*)^exponent` times `(S*)^exponent`.
+    let premium = |spot: f64| {
+        if is_call {
+            spot / exponent * (1.0 - carry * stdn.cdf(d1(spot)))
+        } else {
+            -spot / exponent * (1.0 - carry * stdn.cdf(-d1(spot)))
+        }
+    };
+    // Smooth pasting: the exercise value equals the approximated continuation value at S*.
+    let pasting = |spot: f64| {
+        if is_call {
+            spot - k - european(spot) - premium(spot)
+        } else {
+            k - spot - european(spot) - premium(spot)
+        }
+    };
+    let (lo, hi) = if is_call {
+        let mut hi = 2.0 * k;
+        while pasting(hi) < 0.0 && hi < 1e6 * k {
+            hi *= 2.0;
+        }
+        (k, hi)
+    } else {
+        (1e-8 * k, k)
+    };
+    let critical = match brent(pasting, lo, hi, 1e-10 * k, 200) {
+        Ok(res) => res.root,
+        Err(_) => return european(s).max(intrinsic),
+    };
+    let exercised = if is_call {
+        s >= critical
+    } else {
+        s <= critical
+    };
+    if exercised {
+        intrinsic
+    } else {
+        european(s) + premium(critical) * (s / critical).powf(exponent)
+    }
+}
+
+/// Integrand of the early exercise premium of an American put, `s` years before the boundary
+/// point `b` is reached: `r K e^{-r s} N(-d2(x, b, s)) - q x e^{-q s} N(-d1(x, b, s))`.
+///
+/// At `s = 0` the normal probabilities are their limits: 1 below the boundary, 0 above and one
+/// half on it.
+#[allow(clippy::too_many_arguments)]
+fn put_premium_kernel(x: f64, b: f64, s: f64, k: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let (below_d1, below_d2) = if s <= 0.0 {
+        let limit = match x.partial_cmp(&b) {
+            Some(std::cmp::Ordering::Less) => 1.0,
+            Some(std::cmp::Ordering::Greater) => 0.0,
+            _ => 0.5,
+        };
+        (limit, limit)
+    } else {
+        let stdn = Normal::new(0.0, 1.0).unwrap();
+        let sd = sigma * s.sqrt();
+        let d1 = ((x / b).ln() + (r - q + 0.5 * sigma * sigma) * s) / sd;
+        (stdn.cdf(-d1), stdn.cdf(-(d1 - sd)))
+    };
+    r * k * (-r * s).exp() * below_d2 - q * x * (-q * s).exp() * below_d1
+}
+
+/// Early exercise premium of an American put at spot `x`, by the trapezoidal rule over the
+/// boundary `past` at times to expiry `0, dt, 2 dt, ...` followed by `current`, its value at the
+/// option's own time to expiry `past.len() * dt`.
+#[allow(clippy::too_many_arguments)]
+fn put_premium(
+    x: f64,
+    past: &[f64],
+    current: f64,
+    dt: f64,
+    k: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> f64 {
+    let steps = past.len();
+    let history: f64 = past
+        .iter()
+        .enumerate()
+        .map(|(j, &b)| {
+            let weight = if j == 0 { 0.5 } else { 1.0 };
+            weight * put_premium_kernel(x, b, (steps - j) as f64 * dt, k, r, q, sigma)
+        })
+        .sum();
+    (history + 0.5 * put_premium_kernel(x, current, 0.0, k, r, q, sigma)) * dt
+}
+
+/// Early exercise boundary of an American put from Kim's (1990) integral equation.
+///
+/// With `B(tau)` the critical spot `tau` years before expiry, exercising at the boundary must
+/// be worth as much as holding on:
+///
+/// `K - B(tau) = p(B(tau), tau) + int_0^tau [r K e^{-r (tau - u)} N(-d2) - q B(tau)
+/// e^{-q (tau - u)} N(-d1)] du`,
+///
+/// with `p` the European put of [`black_scholes_merton`] and `d1`, `d2` evaluated at spot
+/// `B(tau)`, strike `B(u)` and maturity `tau - u`. The equation is marched forward from the
+/// limit `B(0) = K min(1, r / q)`, solving for each new point with Brent's method and the
+/// integral by the trapezoidal rule. When `r <= 0` the put is never exercised early and the
+/// boundary is 0 throughout.
+///
+/// # Arguments
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - q: continuously compounded dividend yield
+///  - sigma: volatility (annualized)
+///  - n_time_steps: number of time steps
+///
+/// # Returns
+/// `(time_to_expiry, critical_spot)` pairs from expiry (`0`) out to `t`, or an error if the
+/// equation has no root below the previous boundary point
+pub fn american_put_boundary(
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    n_time_steps: usize,
+) -> Result<Vec<(f64, f64)>, &'static str> {
+    let n = n_time_steps.max(1);
+    let dt = t / n as f64;
+    if r <= 0.0 {
+        return Ok((0..=n).map(|i| (i as f64 * dt, 0.0)).collect());
+    }
+    let mut boundary = vec![if q > r { k * r / q } else { k }];
+    for i in 1..=n {
+        let tau = i as f64 * dt;
+        let equation = |b: f64| {
+            let holding = black_scholes_merton(b, k, tau, r, q, sigma).1
+                + put_premium(b, &boundary, b, dt, k, r, q, sigma);
+            k - b - holding
+        };
+        let b = brent(equation, 1e-8 * k, boundary[i - 1], 1e-10 * k, 200)
+            .map_err(|_| "failed to solve for the early exercise boundary")?;
+        boundary.push(b.root);
+    }
+    Ok(boundary
+        .into_iter()
+        .enumerate()
+        .map(|(i, b)| (i as f64 * dt, b))
+        .collect())
+}
+
+/// Prices an American put as the European put plus the early exercise premium integrated over
+/// the boundary of [`american_put_boundary`].
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - q: continuously compounded dividend yield
+///  - sigma: volatility (annualized)
+///  - n_time_steps: number of time steps of the boundary
+///
+/// # Returns
+/// Put price, or the error of [`american_put_boundary`]. As in [`american_baw`], the put is
+/// European when `r <= 0`.
+pub fn american_put_kim(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    n_time_steps: usize,
+) -> Result<f64, &'static str> {
+    if t <= 0.0 || sigma <= 0.0 || r <= 0.0 {
+        return Ok(american_baw(s, k, t, r, q, sigma, false));
+    }
+    let boundary: Vec<f64> = american_put_boundary(k, t, r, q, sigma, n_time_steps)?
+        .into_iter()
+        .map(|(_, b)| b)
+        .collect();
+    let (current, past) = boundary.split_last().unwrap();
+    if s <= *current {
+        return Ok(k - s);
+    }
+    let dt = t / past.len() as f64;
+    Ok(black_scholes_merton(s, k, t, r, q, sigma).1
+        + put_premium(s, past, *current, dt, k, r, q, sigma))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{binomial_crr, black_scholes};
+
+    #[test]
+    fn test_call_without_dividends_is_european() {
+        for &(s, k, t) in &[(90.0, 100.0, 0.5), (100.0, 100.0, 1.0), (130.0, 100.0, 3.0)] {
+            let (call, _) = black_scholes(s, k, t, 0.05, 0.25);
+            assert_eq!(american_baw(s, k, t, 0.05, 0.0, 0.25, true), call);
+        }
+    }
+
+    #[test]
+    fn test_put_early_exercise_premium() {
+        for &(s, k, t, sigma) in &[
+            (36.0, 40.0, 1.0, 0.2),
+            (40.0, 40.0, 0.5, 0.3),
+            (44.0, 40.0, 0.5, 0.4),
+            (100.0, 110.0, 0.25, 0.15),
+        ] {
+            let (_, european) = black_scholes(s, k, t, 0.06, sigma);
+            let baw = american_baw(s, k, t, 0.06, 0.0, sigma, false);
+            assert!(baw > european && baw >= k - s, "s={} baw={}", s, baw);
+            let tree = binomial_crr(s, k, t, 0.06, sigma, 500, false, true);
+            assert!(
+                (baw - tree).abs() < 0.05,
+                "s={} baw={} tree={}",
+                s,
+                baw,
+                tree
+            );
+        }
+    }
+
+    #[test]
+    fn test_deep_in_the_money_exercised() {
+        assert_eq!(american_baw(20.0, 40.0, 1.0, 0.06, 0.0, 0.2, false), 20.0);
+        assert_eq!(american_baw(200.0, 100.0, 1.0, 0.03, 0.2, 0.2, true), 100.0);
+    }
+
+    #[test]
+    fn test_boundary_limit_at_expiry() {
+        let boundary = american_put_boundary(100.0, 1.0, 0.05, 0.08, 0.3, 50).unwrap();
+        assert_eq!(boundary[0], (0.0, 62.5));
+        let boundary = american_put_boundary(100.0, 1.0, 0.05, 0.0, 0.3, 50).unwrap();
+        assert_eq!(boundary[0], (0.0, 100.0));
+        assert_eq!(boundary.len(), 51);
+        assert_eq!(boundary[50].0, 1.0);
+    }
+
+    #[test]
+    fn test_boundary_monotone() {
+        for &(r, q, sigma) in &[(0.06, 0.0, 0.2), (0.05, 0.03, 0.35), (0.03, 0.06, 0.25)] {
+            let boundary = american_put_boundary(40.0, 2.0, r, q, sigma, 100).unwrap();
+            // Further from expiry the holder waits for a lower spot before exercising.
+            for w in boundary.windows(2) {
+                assert!(w[1].1 < w[0].1, "r={} q={} {:?}", r, q, w);
+            }
+            assert!(boundary[100].1 > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_boundary_prices_match_tree() {
+        for &(s, k, t, sigma) in &[
+            (36.0, 40.0, 1.0, 0.2),
+            (40.0, 40.0, 0.5, 0.3),
+            (44.0, 40.0, 2.0, 0.4),
+            (100.0, 110.0, 0.25, 0.15),
+        ] {
+            let kim = american_put_kim(s, k, t, 0.06, 0.0, sigma, 200).unwrap();
+            let tree = binomial_crr(s, k, t, 0.06, sigma, 500, false, true);
+            assert!(
+                (kim - tree).abs() < 0.05,
+                "s={} kim={} tree={}",
+                s,
+                kim,
+                tree
+            );
+        }
+        assert_eq!(
+            american_put_kim(20.0, 40.0, 1.0, 0.06, 0.0, 0.2, 100),
+            Ok(20.0)
+        );
+    }
+
+    #[test]
+    fn test_kim_non_positive_rates_european() {
+        for &(r, q) in &[(0.0, 0.0), (-0.01, 0.0), (0.0, 0.02), (-0.02, 0.01)] {
+            for &s in &[70.0, 90.0, 110.0] {
+                let (_, european) = black_scholes_merton(s, 100.0, 1.0, r, q, 0.2);
+                let kim = american_put_kim(s, 100.0, 1.0, r, q, 0.2, 50).unwrap();
+                assert_eq!(kim, european, "r={} q={} s={}", r, q, s);
+            }
+            let boundary = american_put_boundary(100.0, 1.0, r, q, 0.2, 50).unwrap();
+            assert_eq!(boundary.len(), 51);
+            assert!(boundary.iter().all(|&(_, b)| b == 0.0));
+        }
+        // A small positive rate still gives a premium over the European put.
+        let (_, european) = black_scholes_merton(90.0, 100.0, 1.0, 0.001, 0.0, 0.2);
+        let kim = american_put_kim(90.0, 100.0, 1.0, 0.001, 0.0, 0.2, 100).unwrap();
+        assert!(kim >= european, "kim={} european={}", kim, european);
+    }
+
+    #[test]
+    fn test_dividend_call_premium() {
+        let mut prev = 0.0;
+        for &q in &[0.01, 0.04, 0.08] {
+            let (european, _) = black_scholes_merton(100.0, 100.0, 1.0, 0.05, q, 0.25);
+            let baw = american_baw(100.0, 100.0, 1.0, 0.05, q, 0.25, true);
+            // The premium grows with the dividends given up by not exercising.
+            assert!(baw - european > prev, "q={} premium={}", q, baw - european);
+            prev = baw - european;
+        }
+        assert_eq!(american_baw(100.0, 90.0, 0.0, 0.05, 0.04, 0.25, true), 10.0);
+    }
+
+    #[test]
+    fn test_dividend_call_at_zero_rate() {
+        for &s in &[80.0, 100.0, 120.0] {
+            let (european, _) = black_scholes_merton(s, 100.0, 1.0, 0.0, 0.04, 0.25);
+            let baw = american_baw(s, 100.0, 1.0, 0.0, 0.04, 0.25, true);
+            assert!(baw.is_finite() && baw >= european, "s={} baw={}", s, baw);
+            assert!(baw >= s - 100.0);
+            // Continuous in the rate through zero.
+            for &r in &[-1e-4, 1e-9, 1e-4] {
+                let nearby = american_baw(s, 100.0, 1.0, r, 0.04, 0.25, true);
+                assert!(
+                    (nearby - baw).abs() < 1e-2,
+                    "s={} r={} baw={}",
+                    s,
+                    r,
+                    nearby
+                );
+            }
+        }
+    }
+}
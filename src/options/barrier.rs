@@ -0,0 +1,899 @@
+use crate::math::bivariate_normal_cdf;
+use crate::options::black_scholes;
+use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+
+/// Direction and knock type of a single barrier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarrierType {
+    UpAndOut,
+    UpAndIn,
+    DownAndOut,
+    DownAndIn,
+}
+
+impl BarrierType {
+    fn is_up(self) -> bool {
+        matches!(self, BarrierType::UpAndOut | BarrierType::UpAndIn)
+    }
+
+    fn is_knock_out(self) -> bool {
+        matches!(self, BarrierType::UpAndOut | BarrierType::DownAndOut)
+    }
+}
+
+/// Integral `int_alpha^beta exp(k x) * phi(x - m) dx` where `phi` is the N(0, var) density.
+fn gaussian_exp_integral(k: f64, m: f64, var: f64, alpha: f64, beta: f64) -> f64 {
+    let sd = var.sqrt();
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let shift = m + k * var;
+    (k * m + 0.5 * k * k * var).exp()
+        * (stdn.cdf((beta - shift) / sd) - stdn.cdf((alpha - shift) / sd))
+}
+
+/// Undiscounted expectation `E[(c0 + c1 * exp(X)) * 1{alpha < X < beta}]` over paths of the
+/// log-return `X` that stay inside `(lower, upper)`, using the method of images.
+///
+/// `X` has drift `nu` and variance `var` at expiry; `lower < 0 < upper`.
+#[allow(clippy::too_many_arguments)]
+fn killed_expectation(
+    c0: f64,
+    c1: f64,
+    alpha: f64,
+    beta: f64,
+    lower: f64,
+    upper: f64,
+    nu_over_var: f64,
+    var: f64,
+) -> f64 {
+    let width = upper - lower;
+    // Girsanov factor exp(nu x / sigma^2 - nu^2 T / (2 sigma^2)) = exp(a x - a^2 var / 2).
+    let a = nu_over_var;
+    let girsanov = (-0.5 * a * a * var).exp();
+    // Image terms decay like exp(-2 n^2 width^2 / var); keep going well past one standard deviation.
+    let n_images = (3.0 * var.sqrt() / width).ceil() as i32 + 2;
+    let mut total = 0.0;
+    for n in -n_images..=n_images {
+        let direct = -2.0 * n as f64 * width;
+        let image = 2.0 * upper - 2.0 * n as f64 * width;
+        for &(k, c) in &[(a, c0), (a + 1.0, c1)] {
+            if c == 0.0 {
+                continue;
+            }
+            total += c
+                * (gaussian_exp_integral(k, direct, var, alpha, beta)
+                    - gaussian_exp_integral(k, image, var, alpha, beta));
+        }
+    }
+    girsanov * total
+}
+
+/// Double-barrier knock-out option with continuously monitored flat barriers.
+///
+/// The option expires worthless if spot touches either `h_low` or `h_high` before expiry,
+/// otherwise it pays the vanilla payoff. Priced with the Ikeda-Kunitomo image series.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - h_low: lower barrier, below spot
+///  - h_high: upper barrier, above spot
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// Price of the knock-out option
+#[allow(clippy::too_many_arguments)]
+pub fn double_barrier_knockout(
+    s: f64,
+    k: f64,
+    h_low: f64,
+    h_high: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    is_call: bool,
+) -> f64 {
+    if s <= h_low || s >= h_high {
+        return 0.0;
+    }
+    if t <= 0.0 || sigma <= 0.0 {
+        return if is_call {
+            (s - k).max(0.0)
+        } else {
+            (k - s).max(0.0)
+        };
+    }
+
+    let var = sigma * sigma * t;
+    let nu_over_var = (r - 0.5 * sigma * sigma) / (sigma * sigma);
+    let lower = (h_low / s).ln();
+    let upper = (h_high / s).ln();
+    let log_k = (k / s).ln();
+
+    let expectation = if is_call {
+        let alpha = log_k.max(lower);
+        if alpha >= upper {
+            return 0.0;
+        }
+        killed_expectation(-k, s, alpha, upper, lower, upper, nu_over_var, var)
+    } else {
+        let beta = log_k.min(upper);
+        if beta <= lower {
+            return 0.0;
+        }
+        killed_expectation(k, -s, lower, beta, lower, upper, nu_over_var, var)
+    };
+    (-r * t).exp() * expectation
+}
+
+/// Double-barrier knock-in option, which only pays the vanilla payoff if spot touches either
+/// barrier before expiry. Priced via in-out parity against [`double_barrier_knockout`].
+#[allow(clippy::too_many_arguments)]
+pub fn double_barrier_knockin(
+    s: f64,
+    k: f64,
+    h_low: f64,
+    h_high: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    is_call: bool,
+) -> f64 {
+    let (call, put) = black_scholes(s, k, t, r, sigma);
+    let vanilla = if is_call { call } else { put };
+    vanilla - double_barrier_knockout(s, k, h_low, h_high, t, r, sigma, is_call)
+}
+
+/// Undiscounted expectation `E[(c0 + c1 * exp(X)) * 1{alpha < X < beta}]` over paths of the
+/// log-return `X` that never cross the single level `barrier`, using one reflected image.
+fn single_killed_expectation(
+    c0: f64,
+    c1: f64,
+    alpha: f64,
+    beta: f64,
+    barrier: f64,
+    nu_over_var: f64,
+    var: f64,
+) -> f64 {
+    let a = nu_over_var;
+    let girsanov = (-0.5 * a * a * var).exp();
+    let mut total = 0.0;
+    for &(k, c) in &[(a, c0), (a + 1.0, c1)] {
+        if c == 0.0 {
+            continue;
+        }
+        total += c
+            * (gaussian_exp_integral(k, 0.0, var, alpha, beta)
+                - gaussian_exp_integral(k, 2.0 * barrier, var, alpha, beta));
+    }
+    girsanov * total
+}
+
+fn vanilla(s: f64, k: f64, t: f64, r: f64, sigma: f64, is_call: bool) -> f64 {
+    let (call, put) = black_scholes(s, k, t, r, sigma);
+    if is_call {
+        call
+    } else {
+        put
+    }
+}
+
+/// Single-barrier option with a continuously monitored flat barrier (Reiner-Rubinstein).
+///
+/// Knock-out options pay the vanilla payoff only if spot never touches `h` before expiry;
+/// knock-in options only if it does. Priced with the reflection principle, and knock-ins via
+/// in-out parity.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - h: barrier level, above spot for up barriers and below for down barriers
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - barrier_type: direction and knock type of the barrier
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// Price of the barrier option
+#[allow(clippy::too_many_arguments)]
+pub fn barrier_option(
+    s: f64,
+    k: f64,
+    h: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    barrier_type: BarrierType,
+    is_call: bool,
+) -> f64 {
+    let plain = vanilla(s, k, t, r, sigma, is_call);
+    let breached = if barrier_type.is_up() { s >= h } else { s <= h };
+    let knocked_out = if breached || t <= 0.0 || sigma <= 0.0 {
+        if breached {
+            0.0
+        } else {
+            plain
+        }
+    } else {
+        let var = sigma * sigma * t;
+        let nu_over_var = (r - 0.5 * sigma * sigma) / (sigma * sigma);
+        let barrier = (h / s).ln();
+        let log_k = (k / s).ln();
+        let (mut alpha, mut beta, c0, c1) = if is_call {
+            (log_k, f64::INFINITY, -k, s)
+        } else {
+            (f64::NEG_INFINITY, log_k, k, -s)
+        };
+        if barrier_type.is_up() {
+            beta = beta.min(barrier);
+        } else {
+            alpha = alpha.max(barrier);
+        }
+        if alpha >= beta {
+            0.0
+        } else {
+            (-r * t).exp()
+                * single_killed_expectation(c0, c1, alpha, beta, barrier, nu_over_var, var)
+        }
+    };
+    if barrier_type.is_knock_out() {
+        knocked_out
+    } else {
+        plain - knocked_out
+    }
+}
+
+/// Single-barrier option on a binomial tree whose nodes fall exactly on the barrier.
+///
+/// A CRR tree effectively moves the barrier to the next layer of nodes beyond it, so its price
+/// oscillates with `n`. Here the log-spacing is `dx = |ln(H / S)| / m`, with `m` the number of
+/// layers between spot and barrier that a CRR tree of `n` steps would have, and the number of
+/// steps is re-chosen as the one matching `sigma^2 dt` to `dx^2`, at most `n`. A barrier within
+/// one CRR layer of spot is placed on the first layer of an `n`-step CRR tree and the knock-out
+/// value scaled by the barrier's fraction of that layer, as it vanishes at the barrier. The up
+/// probability matches the risk-neutral drift. Knock-ins follow by in-out parity on the same tree.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - h: barrier level, above spot for up barriers and below for down barriers
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - n: maximum number of time steps
+///  - barrier_type: direction and knock type of the barrier
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// Price of the barrier option
+#[allow(clippy::too_many_arguments)]
+pub fn binomial_barrier(
+    s: f64,
+    k: f64,
+    h: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    n: usize,
+    barrier_type: BarrierType,
+    is_call: bool,
+) -> f64 {
+    let breached = if barrier_type.is_up() { s >= h } else { s <= h };
+    if breached || t <= 0.0 || sigma <= 0.0 {
+        return barrier_option(s, k, h, t, r, sigma, barrier_type, is_call);
+    }
+    let distance = (h / s).ln().abs();
+    let crr_dx = sigma * (t / n.max(1) as f64).sqrt();
+    // A barrier closer than one CRR layer cannot sit on a node without more than n steps, so
+    // it is put on the first layer and the knock-out value interpolated back in the barrier.
+    let near = distance < crr_dx;
+    let layers = ((distance / crr_dx).floor() as i64).max(1);
+    let dx = if near {
+        crr_dx
+    } else {
+        distance / layers as f64
+    };
+    let steps = ((sigma * sigma * t / (dx * dx)).round() as usize).clamp(1, n.max(1));
+    let dt = t / steps as f64;
+    let growth = (r * dt).exp();
+    let p = (growth - (-dx).exp()) / (dx.exp() - (-dx).exp());
+    let disc = 1.0 / growth;
+    // Node i of step m sits at level j = 2i - m, i.e. at S exp(j dx).
+    let alive = |j: i64| {
+        if barrier_type.is_up() {
+            j < layers
+        } else {
+            j > -layers
+        }
+    };
+    let payoff = |j: i64| {
+        let st = s * (j as f64 * dx).exp();
+        if is_call {
+            (st - k).max(0.0)
+        } else {
+            (k - st).max(0.0)
+        }
+    };
+    let mut plain: Vec<f64> = (0..=steps as i64)
+        .map(|i| payoff(2 * i - steps as i64))
+        .collect();
+    let mut knocked_out: Vec<f64> = (0..=steps as i64)
+        .map(|i| {
+            let j = 2 * i - steps as i64;
+            if alive(j) {
+                payoff(j)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    for step in (0..steps).rev() {
+        for i in 0..=step {
+            let j = 2 * i as i64 - step as i64;
+            plain[i] = disc * (p * plain[i + 1] + (1.0 - p) * plain[i]);
+            knocked_out[i] = if alive(j) {
+                disc * (p * knocked_out[i + 1] + (1.0 - p) * knocked_out[i])
+            } else {
+                0.0
+            };
+        }
+    }
+    // A barrier at spot knocks out immediately, so between spot and the first layer the
+    // knock-out value is interpolated linearly from zero.
+    let knocked_out = if near {
+        knocked_out[0] * distance / dx
+    } else {
+        knocked_out[0]
+    };
+    if barrier_type.is_knock_out() {
+        knocked_out
+    } else {
+        plain[0] - knocked_out
+    }
+}
+
+/// Discounted `E[S_T * 1{S_T > k, alive}]` and `E[1{S_T > k, alive}]` when the barrier `h` is
+/// monitored over `[0, t1]` only (Heynen-Kat partial-start). `k = 0` drops the strike condition.
+#[allow(clippy::too_many_arguments)]
+fn partial_start_terms(
+    s: f64,
+    k: f64,
+    h: f64,
+    t1: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    is_up: bool,
+) -> (f64, f64) {
+    let eta = if is_up { -1.0 } else { 1.0 };
+    let sd_t = sigma * t.sqrt();
+    let sd_t1 = sigma * t1.sqrt();
+    let rho = (t1 / t).sqrt();
+    let mu = (r - 0.5 * sigma * sigma) / (sigma * sigma);
+    let log_hs = (h / s).ln();
+
+    let (d1, f1) = if k > 0.0 {
+        let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / sd_t;
+        (d1, d1 + 2.0 * log_hs / sd_t)
+    } else {
+        (f64::INFINITY, f64::INFINITY)
+    };
+    let (d2, f2) = (d1 - sd_t, f1 - sd_t);
+    let e1 = (-log_hs + (r + 0.5 * sigma * sigma) * t1) / sd_t1;
+    let e2 = e1 - sd_t1;
+    let e3 = e1 + 2.0 * log_hs / sd_t1;
+    let e4 = e3 - sd_t1;
+
+    let ratio = h / s;
+    let asset = s
+        * (bivariate_normal_cdf(d1, eta * e1, eta * rho)
+            - ratio.powf(2.0 * (mu + 1.0)) * bivariate_normal_cdf(f1, eta * e3, eta * rho));
+    let cash = (-r * t).exp()
+        * (bivariate_normal_cdf(d2, eta * e2, eta * rho)
+            - ratio.powf(2.0 * mu) * bivariate_normal_cdf(f2, eta * e4, eta * rho));
+    (asset, cash)
+}
+
+/// Knock-out option whose barrier is monitored over `[0, t1]` only, with `t1 <= t`.
+#[allow(clippy::too_many_arguments)]
+fn partial_start_knockout(
+    s: f64,
+    k: f64,
+    h: f64,
+    t1: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    is_up: bool,
+    is_call: bool,
+) -> f64 {
+    if if is_up { s >= h } else { s <= h } {
+        return 0.0;
+    }
+    if t1 <= 0.0 {
+        return vanilla(s, k, t, r, sigma, is_call);
+    }
+    let (asset_k, cash_k) = partial_start_terms(s, k, h, t1, t, r, sigma, is_up);
+    if is_call {
+        asset_k - k * cash_k
+    } else {
+        let (asset_0, cash_0) = partial_start_terms(s, 0.0, h, t1, t, r, sigma, is_up);
+        k * (cash_0 - cash_k) - (asset_0 - asset_k)
+    }
+}
+
+/// Partial-time single-barrier option: the barrier is only monitored during `[t_start, t_end]`.
+///
+/// A window starting at 0 uses the Heynen-Kat (1994) closed form in terms of the bivariate
+/// normal distribution. A later window is priced by integrating that closed form, started at
+/// `t_start`, over the lognormal distribution of `S(t_start)`. If `t_start == t_end` the barrier
+/// is observed once, at that instant. Knock-ins are priced via in-out parity. Without
+/// volatility the spot follows `s * exp(r u)` and the option is knocked out only if that path
+/// touches the barrier inside the window.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - h: barrier level
+///  - t_start: start of the monitoring window in years
+///  - t_end: end of the monitoring window in years, at most `t`
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - barrier_type: direction and knock type of the barrier
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// Price of the partial barrier option
+#[allow(clippy::too_many_arguments)]
+pub fn partial_barrier_option(
+    s: f64,
+    k: f64,
+    h: f64,
+    t_start: f64,
+    t_end: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    barrier_type: BarrierType,
+    is_call: bool,
+) -> f64 {
+    let plain = vanilla(s, k, t, r, sigma, is_call);
+    let is_up = barrier_type.is_up();
+    let t_end = t_end.min(t);
+    let t_start = t_start.clamp(0.0, t_end);
+    let knocked_out = if sigma <= 0.0 || t <= 0.0 {
+        let (first, last) = (s * (r * t_start).exp(), s * (r * t_end).exp());
+        let touched = if is_up {
+            first.max(last) >= h
+        } else {
+            first.min(last) <= h
+        };
+        if touched {
+            0.0
+        } else {
+            plain
+        }
+    } else if t_start <= 0.0 {
+        partial_start_knockout(s, k, h, t_end, t, r, sigma, is_up, is_call)
+    } else {
+        // Integrate over z, with S(t_start) = s * exp(drift + vol * z), on the surviving side.
+        let drift = (r - 0.5 * sigma * sigma) * t_start;
+        let vol = sigma * t_start.sqrt();
+        let z_h = ((h / s).ln() - drift) / vol;
+        let (lo, hi) = if is_up {
+            (-10.0, z_h.min(10.0))
+        } else {
+            (z_h.max(-10.0), 10.0)
+        };
+        if lo >= hi {
+            0.0
+        } else {
+            let stdn = Normal::new(0.0, 1.0).unwrap();
+            let integrand = |z: f64| {
+                let s1 = s * (drift + vol * z).exp();
+                stdn.pdf(z)
+                    * partial_start_knockout(
+                        s1,
+                        k,
+                        h,
+                        t_end - t_start,
+                        t - t_start,
+                        r,
+                        sigma,
+                        is_up,
+                        is_call,
+                    )
+            };
+            // Composite 3-point Gauss-Legendre, which never evaluates the barrier endpoint itself.
+            let n = 200;
+            let dz = (hi - lo) / n as f64;
+            let nodes = [
+                (-(0.6_f64).sqrt(), 5.0 / 9.0),
+                (0.0, 8.0 / 9.0),
+                ((0.6_f64).sqrt(), 5.0 / 9.0),
+            ];
+            let sum: f64 = (0..n)
+                .map(|i| {
+                    let mid = lo + (i as f64 + 0.5) * dz;
+                    nodes
+                        .iter()
+                        .map(|&(x, w)| w * integrand(mid + 0.5 * dz * x))
+                        .sum::<f64>()
+                })
+                .sum();
+            (-r * t_start).exp() * sum * 0.5 * dz
+        }
+    };
+    if barrier_type.is_knock_out() {
+        knocked_out
+    } else {
+        plain - knocked_out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_price_increases_with_barrier_width() {
+        let mut prev_call = 0.0;
+        let mut prev_put = 0.0;
+        for &w in &[10.0, 20.0, 30.0, 50.0] {
+            let call =
+                double_barrier_knockout(100.0, 100.0, 100.0 - w, 100.0 + w, 1.0, 0.05, 0.2, true);
+            let put =
+                double_barrier_knockout(100.0, 100.0, 100.0 - w, 100.0 + w, 1.0, 0.05, 0.2, false);
+            assert!(
+                call > prev_call && put > prev_put,
+                "w={} call={} put={}",
+                w,
+                call,
+                put
+            );
+            prev_call = call;
+            prev_put = put;
+        }
+    }
+
+    #[test]
+    fn test_wide_barriers_converge_to_vanilla() {
+        let (call, put) = black_scholes(100.0, 100.0, 1.0, 0.05, 0.2);
+        let ko_call = double_barrier_knockout(100.0, 100.0, 1.0, 10_000.0, 1.0, 0.05, 0.2, true);
+        let ko_put = double_barrier_knockout(100.0, 100.0, 1.0, 10_000.0, 1.0, 0.05, 0.2, false);
+        assert_relative_eq!(ko_call, call, epsilon = 1e-8);
+        assert_relative_eq!(ko_put, put, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_narrow_barriers_collapse_to_zero() {
+        let price = double_barrier_knockout(100.0, 99.0, 99.5, 100.5, 1.0, 0.05, 0.2, true);
+        assert!(price.abs() < 1e-10, "price={}", price);
+    }
+
+    #[test]
+    fn test_strike_spread_converges_to_digital() {
+        // A tight knock-out call spread, normalised by its width, pays one unit whenever spot
+        // ends above the strike without touching a barrier: a double-barrier digital.
+        let (s, t, r, sigma) = (100.0, 0.75, 0.04, 0.3);
+        let spread = |k: f64, h_low: f64, h_high: f64, dk: f64| {
+            (double_barrier_knockout(s, k - dk, h_low, h_high, t, r, sigma, true)
+                - double_barrier_knockout(s, k + dk, h_low, h_high, t, r, sigma, true))
+                / (2.0 * dk)
+        };
+        let var = sigma * sigma * t;
+        let nu_over_var = (r - 0.5 * sigma * sigma) / (sigma * sigma);
+        let (lower, upper) = ((80.0_f64 / s).ln(), (130.0_f64 / s).ln());
+        let digital = (-r * t).exp()
+            * killed_expectation(
+                1.0,
+                0.0,
+                (105.0_f64 / s).ln(),
+                upper,
+                lower,
+                upper,
+                nu_over_var,
+                var,
+            );
+        assert!(digital > 0.0);
+        let mut prev_err = f64::INFINITY;
+        for &dk in &[2.0, 0.5, 0.1, 0.01] {
+            let err = (spread(105.0, 80.0, 130.0, dk) - digital).abs();
+            assert!(err < prev_err, "dk={} err={}", dk, err);
+            prev_err = err;
+        }
+        assert!(prev_err < 1e-6, "err={}", prev_err);
+
+        // With the barriers out of reach it is the vanilla cash-or-nothing call.
+        let d2 = ((s / 105.0_f64).ln() + (r - 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+        let vanilla_digital = (-r * t).exp() * Normal::new(0.0, 1.0).unwrap().cdf(d2);
+        assert_relative_eq!(
+            spread(105.0, 1.0, 10_000.0, 0.01),
+            vanilla_digital,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_in_out_parity() {
+        for &is_call in &[true, false] {
+            let (call, put) = black_scholes(100.0, 95.0, 0.5, 0.03, 0.25);
+            let vanilla = if is_call { call } else { put };
+            let ko = double_barrier_knockout(100.0, 95.0, 80.0, 125.0, 0.5, 0.03, 0.25, is_call);
+            let ki = double_barrier_knockin(100.0, 95.0, 80.0, 125.0, 0.5, 0.03, 0.25, is_call);
+            assert!(ko > 0.0 && ki > 0.0);
+            assert_relative_eq!(ko + ki, vanilla, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_reference_value() {
+        // Haug (2007), double barrier table: S=100, K=100, L=50, U=150, T=0.25, r=b=0.1, sigma=0.25.
+        let price = double_barrier_knockout(100.0, 100.0, 50.0, 150.0, 0.25, 0.1, 0.25, true);
+        assert_relative_eq!(price, 6.1644, epsilon = 1e-4);
+    }
+
+    const ALL_TYPES: [BarrierType; 4] = [
+        BarrierType::UpAndOut,
+        BarrierType::UpAndIn,
+        BarrierType::DownAndOut,
+        BarrierType::DownAndIn,
+    ];
+
+    /// Up-and-out call on a plain CRR tree, knocking out at nodes at or above the barrier.
+    fn crr_up_and_out_call(s: f64, k: f64, h: f64, t: f64, r: f64, sigma: f64, n: usize) -> f64 {
+        let dt = t / n as f64;
+        let u = (sigma * dt.sqrt()).exp();
+        let p = ((r * dt).exp() - 1.0 / u) / (u - 1.0 / u);
+        let disc = (-r * dt).exp();
+        let node = |i: usize, m: usize| s * u.powi(2 * i as i32 - m as i32);
+        let mut v: Vec<f64> = (0..=n)
+            .map(|i| {
+                let st = node(i, n);
+                if st >= h {
+                    0.0
+                } else {
+                    (st - k).max(0.0)
+                }
+            })
+            .collect();
+        for m in (0..n).rev() {
+            for i in 0..=m {
+                v[i] = if node(i, m) >= h {
+                    0.0
+                } else {
+                    disc * (p * v[i + 1] + (1.0 - p) * v[i])
+                };
+            }
+        }
+        v[0]
+    }
+
+    #[test]
+    fn test_binomial_barrier_converges_to_analytic() {
+        for &barrier_type in &ALL_TYPES {
+            let h = if barrier_type.is_up() { 125.0 } else { 90.0 };
+            for &(k, is_call) in &[(95.0, true), (105.0, false)] {
+                let exact = barrier_option(100.0, k, h, 1.0, 0.05, 0.25, barrier_type, is_call);
+                let tree =
+                    binomial_barrier(100.0, k, h, 1.0, 0.05, 0.25, 1000, barrier_type, is_call);
+                assert!(
+                    (tree - exact).abs() < 0.02,
+                    "{:?} k={} tree={} exact={}",
+                    barrier_type,
+                    k,
+                    tree,
+                    exact
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_binomial_barrier_near_spot() {
+        // Barriers closer to spot than one CRR layer (about 0.8% here).
+        for &barrier_type in &ALL_TYPES {
+            let levels = if barrier_type.is_up() {
+                [100.01, 100.2, 100.5, 101.0]
+            } else {
+                [99.99, 99.8, 99.5, 99.0]
+            };
+            for &h in &levels {
+                for &(k, is_call) in &[(100.0, true), (100.0, false)] {
+                    let exact = barrier_option(100.0, k, h, 1.0, 0.05, 0.25, barrier_type, is_call);
+                    let tree =
+                        binomial_barrier(100.0, k, h, 1.0, 0.05, 0.25, 1000, barrier_type, is_call);
+                    assert!(
+                        (tree - exact).abs() < 0.02,
+                        "{:?} h={} call={} tree={} exact={}",
+                        barrier_type,
+                        h,
+                        is_call,
+                        tree,
+                        exact
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binomial_barrier_in_out_parity() {
+        for &(h, out, knock_in) in &[
+            (120.0, BarrierType::UpAndOut, BarrierType::UpAndIn),
+            (85.0, BarrierType::DownAndOut, BarrierType::DownAndIn),
+        ] {
+            for &is_call in &[true, false] {
+                let ko = binomial_barrier(100.0, 100.0, h, 0.5, 0.03, 0.3, 300, out, is_call);
+                let ki = binomial_barrier(100.0, 100.0, h, 0.5, 0.03, 0.3, 300, knock_in, is_call);
+                // Infinitely distant barriers leave the vanilla on the same tree.
+                let far = if h > 100.0 { 1e12 } else { 1e-12 };
+                let vanilla =
+                    binomial_barrier(100.0, 100.0, far, 0.5, 0.03, 0.3, 300, out, is_call);
+                let (call, put) = black_scholes(100.0, 100.0, 0.5, 0.03, 0.3);
+                let bs = if is_call { call } else { put };
+                assert!(ko > 0.0 && ki > 0.0);
+                assert_relative_eq!(ko + ki, bs, max_relative = 0.01);
+                assert!(vanilla > ko);
+            }
+        }
+    }
+
+    #[test]
+    fn test_binomial_barrier_beats_crr() {
+        let (s, k, h, t, r, sigma) = (100.0, 100.0, 120.0, 1.0, 0.05, 0.2);
+        let exact = barrier_option(s, k, h, t, r, sigma, BarrierType::UpAndOut, true);
+        let (mut aligned, mut naive) = (0.0, 0.0);
+        for n in (100..=300).step_by(10) {
+            let tree = binomial_barrier(s, k, h, t, r, sigma, n, BarrierType::UpAndOut, true);
+            aligned += (tree - exact).abs();
+            naive += (crr_up_and_out_call(s, k, h, t, r, sigma, n) - exact).abs();
+        }
+        assert!(aligned < 0.2 * naive, "aligned={} naive={}", aligned, naive);
+    }
+
+    #[test]
+    fn test_single_barrier_matches_wide_double_barrier() {
+        for &(k, is_call) in &[(95.0, true), (105.0, true), (95.0, false), (105.0, false)] {
+            let down = barrier_option(
+                100.0,
+                k,
+                85.0,
+                1.0,
+                0.05,
+                0.25,
+                BarrierType::DownAndOut,
+                is_call,
+            );
+            let double = double_barrier_knockout(100.0, k, 85.0, 1e6, 1.0, 0.05, 0.25, is_call);
+            assert_relative_eq!(down, double, epsilon = 1e-10);
+            let up = barrier_option(
+                100.0,
+                k,
+                120.0,
+                1.0,
+                0.05,
+                0.25,
+                BarrierType::UpAndOut,
+                is_call,
+            );
+            let double = double_barrier_knockout(100.0, k, 1e-6, 120.0, 1.0, 0.05, 0.25, is_call);
+            assert_relative_eq!(up, double, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_full_window_matches_barrier_option() {
+        for &barrier_type in &ALL_TYPES {
+            let h = if barrier_type.is_up() { 115.0 } else { 88.0 };
+            for &(k, is_call) in &[(90.0, true), (110.0, true), (90.0, false), (120.0, false)] {
+                let full = barrier_option(100.0, k, h, 0.75, 0.04, 0.3, barrier_type, is_call);
+                let partial = partial_barrier_option(
+                    100.0,
+                    k,
+                    h,
+                    0.0,
+                    0.75,
+                    0.75,
+                    0.04,
+                    0.3,
+                    barrier_type,
+                    is_call,
+                );
+                assert_relative_eq!(partial, full, epsilon = 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_instantaneous_barrier_is_single_observation() {
+        // Monitoring only at tau, an up-and-out call pays when S(tau) < H and S(T) > K:
+        // S * M(d1, -a1; -rho) - K * exp(-r T) * M(d2, -a2; -rho).
+        let (s, k, h, tau, t, r, sigma): (f64, f64, f64, f64, f64, f64, f64) =
+            (100.0, 100.0, 120.0, 0.5, 1.0, 0.03, 0.25);
+        let a1 = ((s / h).ln() + (r + 0.5 * sigma * sigma) * tau) / (sigma * tau.sqrt());
+        let a2 = a1 - sigma * tau.sqrt();
+        let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+        let d2 = d1 - sigma * t.sqrt();
+        let rho = (tau / t).sqrt();
+        let expected = s * bivariate_normal_cdf(d1, -a1, -rho)
+            - k * (-r * t).exp() * bivariate_normal_cdf(d2, -a2, -rho);
+        let price =
+            partial_barrier_option(s, k, h, tau, tau, t, r, sigma, BarrierType::UpAndOut, true);
+        assert_relative_eq!(price, expected, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_partial_zero_vol_follows_forward_path() {
+        // The spot drifts from 100 to about 105.1 over a year at r = 5%.
+        let price = |h: f64, t_start: f64, barrier_type: BarrierType| {
+            partial_barrier_option(
+                100.0,
+                95.0,
+                h,
+                t_start,
+                1.0,
+                1.0,
+                0.05,
+                0.0,
+                barrier_type,
+                true,
+            )
+        };
+        let (call, _) = black_scholes(100.0, 95.0, 1.0, 0.05, 0.0);
+        assert!(call > 0.0);
+        assert_eq!(price(110.0, 0.0, BarrierType::UpAndOut), call);
+        assert_eq!(price(104.0, 0.5, BarrierType::UpAndOut), 0.0);
+        assert_eq!(price(104.0, 0.5, BarrierType::UpAndIn), call);
+        assert_eq!(price(101.0, 0.0, BarrierType::UpAndOut), 0.0);
+        assert_eq!(price(90.0, 0.3, BarrierType::DownAndOut), call);
+        for barrier_type in ALL_TYPES {
+            for &h in &[90.0, 102.0, 110.0] {
+                assert!(price(h, 0.25, barrier_type).is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn test_longer_window_knocks_out_more() {
+        let price = |t_start: f64, t_end: f64| {
+            partial_barrier_option(
+                100.0,
+                100.0,
+                90.0,
+                t_start,
+                t_end,
+                1.0,
+                0.03,
+                0.25,
+                BarrierType::DownAndOut,
+                false,
+            )
+        };
+        let full = barrier_option(
+            100.0,
+            100.0,
+            90.0,
+            1.0,
+            0.03,
+            0.25,
+            BarrierType::DownAndOut,
+            false,
+        );
+        let (_, put) = black_scholes(100.0, 100.0, 1.0, 0.03, 0.25);
+        let (inner, outer) = (price(0.4, 0.6), price(0.2, 0.8));
+        assert!(
+            full < outer && outer < inner && inner < put,
+            "{} {} {} {}",
+            full,
+            outer,
+            inner,
+            put
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Discounted first-passage functional `E[exp(-rate * tau) * 1{tau <= T}]` of log-spot to a
+/// barrier at log-distance `h` from spot.
+///
+/// `nu` is the drift of log-spot, `rate` the discount rate applied at the hitting time. With
+/// `rate = 0` this is the probability of touching the barrier before `t`.
+fn first_passage(h: f64, nu: f64, sigma: f64, t: f64, rate: f64) -> f64 {
+    // Reflect down barriers onto up barriers.
+    let (h, nu) = if h < 0.0 { (-h, -nu) } else { (h, nu) };
+    let sigma2 = sigma * sigma;
+    let gamma = (nu * nu + 2.0 * rate * sigma2).sqrt();
+    let sigma_sqrt_t = sigma * t.sqrt();
+
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    (h * (nu - gamma) / sigma2).exp() * stdn.cdf((-h + gamma * t) / sigma_sqrt_t)
+        + (h * (nu + gamma) / sigma2).exp() * stdn.cdf((-h - gamma * t) / sigma_sqrt_t)
+}
+
+/// One-touch option paying one unit of cash if spot touches the barrier `h` before expiry.
+///
+/// The barrier is an up barrier when `h > s` and a down barrier when `h < s`. Spot is
+/// monitored continuously under Black-Scholes dynamics without dividends.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - h: barrier level (H)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - at_expiry: true to pay at expiry, false to pay immediately when the barrier is touched
+///
+/// # Returns
+/// Price of the one-touch option
+pub fn one_touch(s: f64, h: f64, t: f64, r: f64, sigma: f64, at_expiry: bool) -> f64 {
+    let df = (-r * t).exp();
+    if s == h {
+        return if at_expiry { df } else { 1.0 };
+    }
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+
+    let nu = r - 0.5 * sigma * sigma;
+    let log_h = (h / s).ln();
+    if at_expiry {
+        df * first_passage(log_h, nu, sigma, t, 0.0)
+    } else {
+        first_passage(log_h, nu, sigma, t, r)
+    }
+}
+
+/// No-touch option paying one unit of cash at expiry if spot never touches the barrier `h`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - h: barrier level (H), above spot for an up barrier and below spot for a down barrier
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///
+/// # Returns
+/// Price of the no-touch option
+pub fn no_touch(s: f64, h: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    (-r * t).exp() - one_touch(s, h, t, r, sigma, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_one_touch_plus_no_touch_is_discount_factor() {
+        let (s, t, r, sigma): (f64, f64, f64, f64) = (100.0, 1.0, 0.05, 0.2);
+        for &h in &[80.0, 95.0, 105.0, 130.0] {
+            let total = one_touch(s, h, t, r, sigma, true) + no_touch(s, h, t, r, sigma);
+            assert_relative_eq!(total, (-r * t).exp(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_one_touch_near_barrier() {
+        let (s, t, r, sigma): (f64, f64, f64, f64) = (100.0, 1.0, 0.05, 0.2);
+        let df = (-r * t).exp();
+        assert_relative_eq!(one_touch(s, 100.001, t, r, sigma, true), df, epsilon = 1e-3);
+        assert_relative_eq!(one_touch(s, 99.999, t, r, sigma, true), df, epsilon = 1e-3);
+        assert_relative_eq!(
+            one_touch(s, 100.001, t, r, sigma, false),
+            1.0,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_one_touch_far_barrier() {
+        let (s, t, r, sigma): (f64, f64, f64, f64) = (100.0, 1.0, 0.05, 0.2);
+        assert!(one_touch(s, 1000.0, t, r, sigma, true) < 1e-10);
+        assert!(one_touch(s, 10.0, t, r, sigma, false) < 1e-10);
+    }
+
+    #[test]
+    fn test_pay_at_hit_worth_more_than_pay_at_expiry() {
+        let (s, t, r, sigma): (f64, f64, f64, f64) = (100.0, 1.0, 0.05, 0.2);
+        for &h in &[85.0, 115.0] {
+            assert!(one_touch(s, h, t, r, sigma, false) > one_touch(s, h, t, r, sigma, true));
+        }
+    }
+}
@@ -0,0 +1,763 @@
+use crate::math::linear_regression;
+use crate::options::chain::OptionChain;
+
+/// Prices a European or American option on a Cox-Ross-Rubinstein binomial tree.
+///
+/// Up and down factors are `u = exp(sigma * sqrt(dt))` and `d = 1 / u` with risk-neutral
+/// probability `p = (exp(r * dt) - d) / (u - d)`. American options are exercised at a node
+/// whenever intrinsic value exceeds continuation value.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - n: number of time steps
+///  - is_call: true for call option, false for put option
+///  - is_american: true to allow early exercise
+///
+/// # Returns
+/// Option price
+#[allow(clippy::too_many_arguments)]
+pub fn binomial_crr(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    n: usize,
+    is_call: bool,
+    is_american: bool,
+) -> f64 {
+    binomial_discrete_dividend(s, k, t, r, sigma, n, &[], is_call, is_american)
+}
+
+/// Prices a European option on a Cox-Ross-Rubinstein binomial tree.
+///
+/// See [`binomial_crr`].
+pub fn binomial_european(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    n: usize,
+    is_call: bool,
+) -> f64 {
+    binomial_crr(s, k, t, r, sigma, n, is_call, false)
+}
+
+/// Peizer-Pratt (method 2) inversion: the binomial probability approximating `N(z)` on an
+/// `n`-step tree.
+fn peizer_pratt(z: f64, n: usize) -> f64 {
+    let n = n as f64;
+    let x = z / (n + 1.0 / 3.0 + 0.1 / (n + 1.0));
+    0.5 + 0.5 * z.signum() * (1.0 - (-x * x * (n + 1.0 / 6.0)).exp()).sqrt()
+}
+
+/// Prices a European option on a Leisen-Reimer (1996) binomial tree.
+///
+/// Node probabilities are the Peizer-Pratt inversions `p = h(d2)` and `p' = h(d1)` of the
+/// Black-Scholes `d1` and `d2`, with `u = exp(r dt) p' / p` and
+/// `d = (exp(r dt) - p u) / (1 - p)`. This centres the tree on the strike and gives smooth
+/// `O(1/n^2)` convergence, against the oscillating `O(1/n)` of CRR. The method needs an odd
+/// number of steps, so an even `n` is rounded up.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - n: number of time steps
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// Option price
+pub fn binomial_lr(s: f64, k: f64, t: f64, r: f64, sigma: f64, n: usize, is_call: bool) -> f64 {
+    let payoff = |spot: f64| {
+        if is_call {
+            (spot - k).max(0.0)
+        } else {
+            (k - spot).max(0.0)
+        }
+    };
+    if t <= 0.0 || sigma <= 0.0 || n == 0 {
+        return payoff(s);
+    }
+    let n = n | 1;
+    let dt = t / n as f64;
+    let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let p = peizer_pratt(d2, n);
+    let p_star = peizer_pratt(d1, n);
+    let growth = (r * dt).exp();
+    let u = growth * p_star / p;
+    let d = (growth - p * u) / (1.0 - p);
+    let disc = 1.0 / growth;
+
+    let mut values: Vec<f64> = (0..=n)
+        .map(|j| payoff(s * u.powi(j as i32) * d.powi((n - j) as i32)))
+        .collect();
+    for step in (0..n).rev() {
+        for j in 0..=step {
+            values[j] = disc * (p * values[j + 1] + (1.0 - p) * values[j]);
+        }
+    }
+    values[0]
+}
+
+/// Prices a European option on Boyle's trinomial tree, with log-spacing `sigma * sqrt(2 dt)` and
+/// branch probabilities matched to two CRR half-steps.
+fn trinomial_european(s: f64, k: f64, t: f64, r: f64, sigma: f64, n: usize, is_call: bool) -> f64 {
+    let payoff = |spot: f64| {
+        if is_call {
+            (spot - k).max(0.0)
+        } else {
+            (k - spot).max(0.0)
+        }
+    };
+    if t <= 0.0 || sigma <= 0.0 || n == 0 {
+        return payoff(s);
+    }
+    let dt = t / n as f64;
+    let dx = sigma * (2.0 * dt).sqrt();
+    let half_growth = (0.5 * r * dt).exp();
+    let half_up = (0.5 * dx).exp();
+    let half_down = 1.0 / half_up;
+    let p_up = ((half_growth - half_down) / (half_up - half_down)).powi(2);
+    let p_down = ((half_up - half_growth) / (half_up - half_down)).powi(2);
+    let p_mid = 1.0 - p_up - p_down;
+    let disc = (-r * dt).exp();
+
+    let mut values: Vec<f64> = (0..=2 * n)
+        .map(|j| payoff(s * ((j as f64 - n as f64) * dx).exp()))
+        .collect();
+    for step in (0..n).rev() {
+        for j in 0..=2 * step {
+            values[j] = disc * (p_up * values[j + 2] + p_mid * values[j + 1] + p_down * values[j]);
+        }
+    }
+    values[0]
+}
+
+/// Compares the convergence of CRR, Leisen-Reimer and trinomial trees for a European option.
+///
+/// The absolute error of each method is its distance from the Black-Scholes price.
+///
+/// # Arguments
+///  - s: spot price of the underlying asset (S)
+///  - k: strike (K)
+///  - t: time to expiration in years
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility of the underlying asset
+///  - n_values: numbers of time steps to evaluate
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// `(n, crr_price, lr_price, trinomial_price)` for every `n` in `n_values`
+pub fn tree_convergence_study(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    n_values: &[usize],
+    is_call: bool,
+) -> Vec<(usize, f64, f64, f64)> {
+    n_values
+        .iter()
+        .map(|&n| {
+            (
+                n,
+                binomial_european(s, k, t, r, sigma, n, is_call),
+                binomial_lr(s, k, t, r, sigma, n, is_call),
+                trinomial_european(s, k, t, r, sigma, n, is_call),
+            )
+        })
+        .collect()
+}
+
+/// Terminal probabilities closest in least squares to `prior` that reprice the forward and
+/// `options` (payoff vectors with target undiscounted values), with non-negativity enforced by
+/// pinning negative probabilities to zero and re-solving. Fails when the constraints are
+/// degenerate on the free nodes, e.g. two quotes with the same payoff on the grid.
+fn fit_terminal_probabilities(
+    prior: &[f64],
+    spots: &[f64],
+    forward: f64,
+    options: &[(Vec<f64>, f64)],
+) -> Result<Vec<f64>, &'static str> {
+    let n = prior.len();
+    let mut rows: Vec<(Vec<f64>, f64)> = vec![(vec![1.0; n], 1.0), (spots.to_vec(), forward)];
+    rows.extend(options.iter().cloned());
+    let mut free = vec![true; n];
+    loop {
+        // Minimise |P - prior|^2 over the free nodes subject to A P = b: P = prior + A^T lambda.
+        let a: Vec<Vec<f64>> = rows
+            .iter()
+            .map(|(row, _)| {
+                row.iter()
+                    .zip(&free)
+                    .map(|(&v, &f)| if f { v } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+        let residual: Vec<f64> = rows
+            .iter()
+            .zip(&a)
+            .map(|((_, target), row)| {
+                target - row.iter().zip(prior).map(|(v, p)| v * p).sum::<f64>()
+            })
+            .collect();
+        let gram: Vec<Vec<f64>> = a
+            .iter()
+            .map(|ri| {
+                a.iter()
+                    .map(|rj| ri.iter().zip(rj).map(|(x, y)| x * y).sum())
+                    .collect()
+            })
+            .collect();
+        let lambda = linear_regression(&gram, &residual)
+            .map_err(|_| "the quotes cannot be repriced on the tree's terminal nodes")?;
+        let probs: Vec<f64> = (0..n)
+            .map(|j| {
+                if free[j] {
+                    prior[j]
+                        + a.iter()
+                            .zip(&lambda)
+                            .map(|(row, l)| row[j] * l)
+                            .sum::<f64>()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let negative: Vec<usize> = (0..n).filter(|&j| probs[j] < 0.0).collect();
+        if negative.is_empty() {
+            return Ok(probs);
+        }
+        for j in negative {
+            free[j] = false;
+        }
+    }
+}
+
+/// Calibrates an implied binomial tree to an option chain with Rubinstein's (1994) method.
+///
+/// The terminal nodes are those of an `n`-step CRR tree at the at-the-money implied
+/// volatility of the chain. Their risk-neutral probabilities are chosen as close as possible,
+/// in least squares, to the CRR binomial probabilities, subject to repricing the forward and
+/// the out-of-the-money mid price at every strike, and to being non-negative. Working backwards,
+/// every path to a terminal node is taken to be equally likely. Each node's probability is then
+/// the sum of its two successors', and its stock price is their discounted risk-neutral
+/// expectation.
+///
+/// # Arguments
+///  - chain: calibration quotes, all for the same expiry
+///  - n: number of time steps
+///
+/// # Returns
+/// Stock prices `tree[i][j]` at step `i` after `j` up moves (see [`implied_tree_option_price`]
+/// and [`implied_tree_state_prices`]), or an error if no terminal distribution on the grid
+/// reprices the chain, e.g. when quotes lie beyond the extreme nodes
+pub fn implied_binomial_tree(chain: &OptionChain, n: usize) -> Result<Vec<Vec<f64>>, &'static str> {
+    let (s, r, t) = (chain.spot, chain.rate, chain.expiry);
+    let dt = t / n as f64;
+    let growth = (r * dt).exp();
+    let forward = s * (r * t).exp();
+
+    let vols: Vec<(f64, f64)> = chain
+        .strikes
+        .iter()
+        .zip(chain.implied_vols())
+        .filter_map(|(&k, iv)| iv.ok().map(|v| (k, v)))
+        .collect();
+    let atm_vol = vols
+        .iter()
+        .min_by(|a, b| (a.0 - forward).abs().total_cmp(&(b.0 - forward).abs()))
+        .map_or(0.2, |&(_, v)| v);
+    let u = (atm_vol * dt.sqrt()).exp();
+    let d = 1.0 / u;
+    let p = (growth - d) / (u - d);
+
+    let spots: Vec<f64> = (0..=n)
+        .map(|j| s * u.powi(j as i32) * d.powi((n - j) as i32))
+        .collect();
+    // Binomial coefficients as floats to avoid overflow for large n.
+    let mut choose = vec![1.0; n + 1];
+    for j in 1..=n {
+        choose[j] = choose[j - 1] * (n + 1 - j) as f64 / j as f64;
+    }
+    let prior: Vec<f64> = (0..=n)
+        .map(|j| choose[j] * p.powi(j as i32) * (1.0 - p).powi((n - j) as i32))
+        .collect();
+
+    let (call_mids, put_mids) = chain.mid_prices();
+    let accrual = (r * t).exp();
+    let options: Vec<(Vec<f64>, f64)> = chain
+        .strikes
+        .iter()
+        .enumerate()
+        .map(|(i, &k)| {
+            if k >= forward {
+                (
+                    spots.iter().map(|&x| (x - k).max(0.0)).collect(),
+                    call_mids[i] * accrual,
+                )
+            } else {
+                (
+                    spots.iter().map(|&x| (k - x).max(0.0)).collect(),
+                    put_mids[i] * accrual,
+                )
+            }
+        })
+        .collect();
+    let terminal = fit_terminal_probabilities(&prior, &spots, forward, &options)?;
+
+    // Probability of each individual path through a node, and the node stock prices.
+    let mut path_prob: Vec<f64> = terminal.iter().zip(&choose).map(|(q, c)| q / c).collect();
+    let mut tree = vec![spots];
+    for step in (0..n).rev() {
+        let next = &tree[0];
+        let mut level = Vec::with_capacity(step + 1);
+        let mut probs = Vec::with_capacity(step + 1);
+        for j in 0..=step {
+            let node_prob = path_prob[j] + path_prob[j + 1];
+            let q = if node_prob > 0.0 {
+                path_prob[j + 1] / node_prob
+            } else {
+                0.5
+            };
+            level.push((q * next[j + 1] + (1.0 - q) * next[j]) / growth);
+            probs.push(node_prob);
+        }
+        path_prob = probs;
+        tree.insert(0, level);
+    }
+    Ok(tree)
+}
+
+/// Risk-neutral probability of moving up from node `(i, j)` of a stock price tree.
+fn tree_up_probability(tree: &[Vec<f64>], i: usize, j: usize, growth: f64) -> f64 {
+    let (up, down) = (tree[i + 1][j + 1], tree[i + 1][j]);
+    if up > down {
+        (tree[i][j] * growth - down) / (up - down)
+    } else {
+        0.5
+    }
+}
+
+/// Arrow-Debreu state prices of every node of a stock price tree such as
+/// [`implied_binomial_tree`].
+///
+/// # Arguments
+///  - tree: stock prices `tree[i][j]`
+///  - t: time to the last step in years
+///  - r: continuously compounded risk-free rate
+pub fn implied_tree_state_prices(tree: &[Vec<f64>], t: f64, r: f64) -> Vec<Vec<f64>> {
+    let n = tree.len() - 1;
+    let disc = (-r * t / n as f64).exp();
+    let mut prices = vec![vec![1.0]];
+    for i in 0..n {
+        let mut next = vec![0.0; i + 2];
+        for j in 0..=i {
+            let q = tree_up_probability(tree, i, j, 1.0 / disc);
+            next[j + 1] += prices[i][j] * q * disc;
+            next[j] += prices[i][j] * (1.0 - q) * disc;
+        }
+        prices.push(next);
+    }
+    prices
+}
+
+/// Prices a European or American option by backward induction on a stock price tree such as
+/// [`implied_binomial_tree`].
+///
+/// # Arguments
+///  - tree: stock prices `tree[i][j]`
+///  - k: strike (K)
+///  - t: time to the last step in years
+///  - r: continuously compounded risk-free rate
+///  - is_call: true for call option, false for put option
+///  - is_american: true to allow early exercise
+pub fn implied_tree_option_price(
+    tree: &[Vec<f64>],
+    k: f64,
+    t: f64,
+    r: f64,
+    is_call: bool,
+    is_american: bool,
+) -> f64 {
+    let payoff = |spot: f64| {
+        if is_call {
+            (spot - k).max(0.0)
+        } else {
+            (k - spot).max(0.0)
+        }
+    };
+    let n = tree.len() - 1;
+    let growth = (r * t / n as f64).exp();
+    let mut values: Vec<f64> = tree[n].iter().map(|&x| payoff(x)).collect();
+    for i in (0..n).rev() {
+        for j in 0..=i {
+            let q = tree_up_probability(tree, i, j, growth);
+            let cont = (q * values[j + 1] + (1.0 - q) * values[j]) / growth;
+            values[j] = if is_american {
+                cont.max(payoff(tree[i][j]))
+            } else {
+                cont
+            };
+        }
+    }
+    values[0]
+}
+
+/// Stock price at time `time` given the risky part `s_star` of the Schroder (1988) tree, by
+/// adding back the present value of dividends still to go ex.
+fn with_escrowed_dividends(
+    s_star: f64,
+    time: f64,
+    dividends: &[(f64, f64)],
+    r: f64,
+    t: f64,
+) -> f64 {
+    s_star
+        + dividends
+            .iter()
+            .filter(|&&(frac, _)| frac * t > time)
+            .map(|&(frac, amount)| amount * (-r * (frac * t - time)).exp())
+            .sum::<f64>()
+}
+
+/// Prices a European or American option with known cash dividends on a CRR binomial tree.
+///
+/// Follows Schroder (1988): the tree is built for the risky part of the stock,
+/// `S* = S - PV(dividends)`, which recombines, and at every node the stock price is recovered
+/// as `S*` plus the present value of the dividends that have not yet gone ex. The stock price
+/// therefore drops by the dividend amount on each ex-date. Payoffs and early exercise use the
+/// recovered stock price.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility of the risky part of the stock (annualized)
+///  - n: number of time steps
+///  - dividends: `(ex_date_fraction, dividend_amount)` pairs, the ex-date being
+///    `ex_date_fraction * t`
+///  - is_call: true for call option, false for put option
+///  - is_american: true to allow early exercise
+///
+/// # Returns
+/// Option price
+#[allow(clippy::too_many_arguments)]
+pub fn binomial_discrete_dividend(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    n: usize,
+    dividends: &[(f64, f64)],
+    is_call: bool,
+    is_american: bool,
+) -> f64 {
+    let payoff = |spot: f64| {
+        if is_call {
+            (spot - k).max(0.0)
+        } else {
+            (k - spot).max(0.0)
+        }
+    };
+    let s_star = s - with_escrowed_dividends(0.0, 0.0, dividends, r, t);
+    if t <= 0.0 || sigma <= 0.0 || n == 0 {
+        return payoff(s);
+    }
+
+    let dt = t / n as f64;
+    let u = (sigma * dt.sqrt()).exp();
+    let d = 1.0 / u;
+    let disc = (-r * dt).exp();
+    let p = ((r * dt).exp() - d) / (u - d);
+    let spot = |step: usize, j: usize| {
+        let risky = s_star * u.powi(j as i32) * d.powi((step - j) as i32);
+        with_escrowed_dividends(risky, step as f64 * dt, dividends, r, t)
+    };
+
+    let mut values: Vec<f64> = (0..=n).map(|j| payoff(spot(n, j))).collect();
+    for step in (0..n).rev() {
+        for j in 0..=step {
+            let cont = disc * (p * values[j + 1] + (1.0 - p) * values[j]);
+            values[j] = if is_american {
+                cont.max(payoff(spot(step, j)))
+            } else {
+                cont
+            };
+        }
+    }
+    values[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_european_converges_to_black_scholes() {
+        let (call, put) = black_scholes(100.0, 100.0, 1.0, 0.05, 0.2);
+        assert_relative_eq!(
+            binomial_crr(100.0, 100.0, 1.0, 0.05, 0.2, 1000, true, false),
+            call,
+            epsilon = 5e-3
+        );
+        assert_relative_eq!(
+            binomial_crr(100.0, 100.0, 1.0, 0.05, 0.2, 1000, false, false),
+            put,
+            epsilon = 5e-3
+        );
+    }
+
+    #[test]
+    fn test_american_put_early_exercise_premium() {
+        let european = binomial_crr(36.0, 40.0, 1.0, 0.06, 0.2, 500, false, false);
+        let american = binomial_crr(36.0, 40.0, 1.0, 0.06, 0.2, 500, false, true);
+        assert!(american > european);
+        // Longstaff-Schwartz (2001), Table 1 finite difference value.
+        assert_relative_eq!(american, 4.478, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_no_dividends_matches_european() {
+        for &is_call in &[true, false] {
+            let plain = binomial_european(100.0, 95.0, 1.0, 0.04, 0.25, 300, is_call);
+            let div =
+                binomial_discrete_dividend(100.0, 95.0, 1.0, 0.04, 0.25, 300, &[], is_call, false);
+            assert_relative_eq!(div, plain, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_stock_drops_by_dividend_on_ex_date() {
+        let dividends = [(0.5, 2.0), (0.75, 1.5)];
+        let (r, t, s_star) = (0.05, 1.0, 90.0);
+        for &(frac, amount) in &dividends {
+            let ex: f64 = frac * t;
+            let before = with_escrowed_dividends(s_star, ex - 1e-9, &dividends, r, t);
+            let after = with_escrowed_dividends(s_star, ex, &dividends, r, t);
+            assert_relative_eq!(before - after, amount, epsilon = 1e-8);
+        }
+        assert_eq!(with_escrowed_dividends(s_star, t, &dividends, r, t), s_star);
+    }
+
+    #[test]
+    fn test_dividends_reduce_american_call() {
+        let plain = binomial_crr(100.0, 100.0, 1.0, 0.05, 0.2, 500, true, true);
+        let div = binomial_discrete_dividend(
+            100.0,
+            100.0,
+            1.0,
+            0.05,
+            0.2,
+            500,
+            &[(0.5, 3.0)],
+            true,
+            true,
+        );
+        let div_european = binomial_discrete_dividend(
+            100.0,
+            100.0,
+            1.0,
+            0.05,
+            0.2,
+            500,
+            &[(0.5, 3.0)],
+            true,
+            false,
+        );
+        assert!(div < plain, "div={} plain={}", div, plain);
+        // Early exercise just before the ex-date now has value.
+        assert!(
+            div > div_european,
+            "american={} european={}",
+            div,
+            div_european
+        );
+    }
+
+    #[test]
+    fn test_lr_matches_black_scholes_at_100_steps() {
+        for &(k, is_call) in &[(90.0, true), (100.0, true), (110.0, false), (100.0, false)] {
+            let (call, put) = black_scholes(100.0, k, 1.0, 0.05, 0.2);
+            let expected = if is_call { call } else { put };
+            let lr = binomial_lr(100.0, k, 1.0, 0.05, 0.2, 100, is_call);
+            assert!(
+                (lr - expected).abs() < 1e-3,
+                "k={} lr={} bs={}",
+                k,
+                lr,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_lr_converges_faster_than_crr() {
+        let (call, _) = black_scholes(100.0, 105.0, 0.75, 0.03, 0.3);
+        let mut lr_wins = 0;
+        let steps: Vec<usize> = (10..=500).step_by(10).collect();
+        for &n in &steps {
+            let crr_error =
+                (binomial_european(100.0, 105.0, 0.75, 0.03, 0.3, n, true) - call).abs();
+            let lr_error = (binomial_lr(100.0, 105.0, 0.75, 0.03, 0.3, n, true) - call).abs();
+            if lr_error < crr_error {
+                lr_wins += 1;
+            }
+        }
+        assert_eq!(lr_wins, steps.len());
+        // Second-order: ten times the steps cuts the error about a hundredfold.
+        let error = |n| (binomial_lr(100.0, 105.0, 0.75, 0.03, 0.3, n, true) - call).abs();
+        assert!(
+            error(501) < error(51) / 50.0,
+            "{} {}",
+            error(51),
+            error(501)
+        );
+    }
+
+    fn skewed_chain() -> OptionChain {
+        let (spot, rate, expiry) = (100.0, 0.03, 0.5);
+        let strikes: Vec<f64> = (0..9).map(|i| 80.0 + 5.0 * i as f64).collect();
+        // Equity-style skew: higher vol at low strikes.
+        let prices: Vec<(f64, f64)> = strikes
+            .iter()
+            .map(|&k| black_scholes(spot, k, expiry, rate, 0.22 - 0.3 * (k / spot).ln()))
+            .collect();
+        OptionChain {
+            expiry,
+            spot,
+            rate,
+            call_bids: prices.iter().map(|p| p.0 - 0.02).collect(),
+            call_asks: prices.iter().map(|p| p.0 + 0.02).collect(),
+            put_bids: prices.iter().map(|p| p.1 - 0.02).collect(),
+            put_asks: prices.iter().map(|p| p.1 + 0.02).collect(),
+            strikes,
+        }
+    }
+
+    #[test]
+    fn test_implied_tree_reprices_chain() {
+        let chain = skewed_chain();
+        let tree = implied_binomial_tree(&chain, 60).unwrap();
+        let (calls, puts) = chain.mid_prices();
+        for (i, &k) in chain.strikes.iter().enumerate() {
+            let call = implied_tree_option_price(&tree, k, chain.expiry, chain.rate, true, false);
+            let put = implied_tree_option_price(&tree, k, chain.expiry, chain.rate, false, false);
+            assert!(
+                (call - calls[i]).abs() < 0.01,
+                "k={} tree={} market={}",
+                k,
+                call,
+                calls[i]
+            );
+            assert!(
+                (put - puts[i]).abs() < 0.01,
+                "k={} tree={} market={}",
+                k,
+                put,
+                puts[i]
+            );
+        }
+        assert_relative_eq!(tree[0][0], chain.spot, max_relative = 1e-10);
+    }
+
+    #[test]
+    fn test_implied_tree_rejects_unreachable_quotes() {
+        // The top node of the 60-step tree is near 330, so both far strikes pay nothing on the
+        // grid and cannot be fitted.
+        let mut chain = skewed_chain();
+        for k in [500.0, 600.0] {
+            let (call, put) = black_scholes(chain.spot, k, chain.expiry, chain.rate, 0.3);
+            chain.strikes.push(k);
+            chain.call_bids.push(call);
+            chain.call_asks.push(call + 0.01);
+            chain.put_bids.push(put);
+            chain.put_asks.push(put + 0.01);
+        }
+        assert!(implied_binomial_tree(&chain, 60).is_err());
+        // Too few terminal nodes for the number of quotes.
+        assert!(implied_binomial_tree(&skewed_chain(), 4).is_err());
+    }
+
+    #[test]
+    fn test_implied_tree_state_prices_positive() {
+        let chain = skewed_chain();
+        let tree = implied_binomial_tree(&chain, 60).unwrap();
+        let state_prices = implied_tree_state_prices(&tree, chain.expiry, chain.rate);
+        for (i, level) in state_prices.iter().enumerate() {
+            assert!(level.iter().all(|&p| p >= 0.0));
+            let df = (-chain.rate * chain.expiry * i as f64 / 60.0).exp();
+            assert_relative_eq!(level.iter().sum::<f64>(), df, max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_implied_tree_american_prices_consistent() {
+        let chain = skewed_chain();
+        let tree = implied_binomial_tree(&chain, 60).unwrap();
+        let (t, r) = (chain.expiry, chain.rate);
+        for &k in &[85.0, 100.0, 115.0] {
+            let euro_call = implied_tree_option_price(&tree, k, t, r, true, false);
+            let amer_call = implied_tree_option_price(&tree, k, t, r, true, true);
+            assert_relative_eq!(amer_call, euro_call, epsilon = 1e-10);
+            let euro_put = implied_tree_option_price(&tree, k, t, r, false, false);
+            let amer_put = implied_tree_option_price(&tree, k, t, r, false, true);
+            assert!(amer_put >= euro_put && amer_put >= k - chain.spot);
+        }
+    }
+
+    #[test]
+    fn test_trinomial_matches_black_scholes() {
+        let (call, put) = black_scholes(100.0, 95.0, 1.0, 0.05, 0.25);
+        assert_relative_eq!(
+            trinomial_european(100.0, 95.0, 1.0, 0.05, 0.25, 500, true),
+            call,
+            epsilon = 5e-3
+        );
+        assert_relative_eq!(
+            trinomial_european(100.0, 95.0, 1.0, 0.05, 0.25, 500, false),
+            put,
+            epsilon = 5e-3
+        );
+    }
+
+    #[test]
+    fn test_tree_convergence_study_ranks_methods() {
+        let n_values = [25, 50, 100, 200, 400];
+        for &(k, is_call) in &[(90.0, true), (100.0, true), (110.0, false)] {
+            let (call, put) = black_scholes(100.0, k, 1.0, 0.05, 0.3);
+            let reference = if is_call { call } else { put };
+            let study = tree_convergence_study(100.0, k, 1.0, 0.05, 0.3, &n_values, is_call);
+            assert_eq!(study.len(), n_values.len());
+            let mut crr_total = 0.0;
+            let mut tri_total = 0.0;
+            for &(n, crr, lr, tri) in &study {
+                let crr_err = (crr - reference).abs();
+                assert!(
+                    (lr - reference).abs() < crr_err,
+                    "n={} lr={} crr={}",
+                    n,
+                    lr,
+                    crr
+                );
+                crr_total += crr_err;
+                tri_total += (tri - reference).abs();
+            }
+            assert!(tri_total < crr_total, "tri={} crr={}", tri_total, crr_total);
+        }
+    }
+}
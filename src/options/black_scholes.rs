@@ -1,4 +1,7 @@
-use statrs::distribution::{Normal, ContinuousCDF};
+use crate::fixed_income::flat_forward_interpolate;
+use crate::math::{even_grid, Complex};
+use crate::optimization::{auto_step, greek_fd_richardson, second_derivative_richardson};
+use statrs::distribution::{Continuous, ContinuousCDF, Normal};
 
 fn d1_f(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
     let sqrt_t = t.sqrt();
@@ -11,6 +14,36 @@ fn d2_f(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
     d1_f(s, k, t, r, sigma) - sigma * t.sqrt()
 }
 
+/// Limiting prices and deltas of European options at expiry (`t = 0`).
+///
+/// The prices are the payoffs. Call delta is the Heaviside step `H(S - K)`, taking the midpoint
+/// value 0.5 at the money, and put delta is `H(S - K) - 1`. The other Greek functions follow the
+/// same conventions whenever `t <= 0` or `sigma <= 0`: gamma is a Dirac delta at the strike,
+/// reported as [`f64::INFINITY`] at the money and 0 elsewhere; vega, theta, rho and all higher
+/// order Greeks are 0. None of them return NaN.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///
+/// # Returns
+/// (call_price, put_price, call_delta, put_delta)
+pub fn black_scholes_at_expiry(s: f64, k: f64) -> (f64, f64, f64, f64) {
+    let call_delta = if s > k {
+        1.0
+    } else if s < k {
+        0.0
+    } else {
+        0.5
+    };
+    (
+        (s - k).max(0.0),
+        (k - s).max(0.0),
+        call_delta,
+        call_delta - 1.0,
+    )
+}
+
 /// Compute European call and put prices.
 /// Formula (risk-neutral):
 ///   d1 = [ln(S/K) + (r + 0.5*sigma^2) * T] / (sigma * sqrt(T))
@@ -31,8 +64,7 @@ fn d2_f(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
 pub fn black_scholes(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> (f64, f64) {
     // Basic edge handling: if T=0 or sigma=0 treat as intrinsic value.
     if t <= 0.0 || sigma <= 0.0 {
-        let call = (s - k).max(0.0);
-        let put = (k - s).max(0.0);
+        let (call, put, _, _) = black_scholes_at_expiry(s, k);
         return (call, put);
     }
 
@@ -51,9 +83,519 @@ pub fn black_scholes(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> (f64, f64) {
     (call, put)
 }
 
+/// Compute European call and put prices in the Merton model with continuous dividend yield `q`.
+/// Formula (risk-neutral):
+///   d1 = [ln(S/K) + (r - q + 0.5*sigma^2) * T] / (sigma * sqrt(T))
+///   d2 = d1 - sigma*sqrt(T)
+///   Call = S * exp(-q*T) * N(d1) - K * exp(-r*T) * N(d2)
+///   Put  = K * exp(-r*T) * N(-d2) - S * exp(-q*T) * N(-d1)
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - q: continuously compounded dividend yield
+///  - sigma: volatility (annualized)
+///
+/// # Returns
+/// (call_price, put_price)
+pub fn black_scholes_merton(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> (f64, f64) {
+    // The dividend-paying stock behaves like a non-paying one with spot S * exp(-q*T).
+    black_scholes(s * (-q * t).exp(), k, t, r, sigma)
+}
+
+/// Black-Scholes-Merton prices with term structures of interest rates and dividend yields.
+///
+/// With deterministic rates only the integrals of the forward rates over `[0, T]` matter, so
+/// each curve is replaced by its zero rate at `T`, the average forward rate over the option's
+/// life. Zero rates are interpolated flat-forward (see [`flat_forward_interpolate`]).
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - rate_curve: `(maturity, continuously compounded zero rate)` pairs, increasing in maturity
+///  - div_curve: `(maturity, continuously compounded dividend yield)` pairs, increasing in maturity
+///  - sigma: volatility (annualized)
+///
+/// # Returns
+/// (call_price, put_price)
+pub fn black_scholes_ts(
+    s: f64,
+    k: f64,
+    t: f64,
+    rate_curve: &[(f64, f64)],
+    div_curve: &[(f64, f64)],
+    sigma: f64,
+) -> (f64, f64) {
+    let average = |curve: &[(f64, f64)]| {
+        let (maturities, rates): (Vec<f64>, Vec<f64>) = curve.iter().copied().unzip();
+        flat_forward_interpolate(&maturities, &rates, t)
+    };
+    black_scholes_merton(s, k, t, average(rate_curve), average(div_curve), sigma)
+}
+
+/// Forward price of an asset with continuous dividend yield, `F = S * exp((r - q) * T)`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - r: continuously compounded risk-free rate
+///  - q: continuously compounded dividend yield
+///  - t: time to delivery in years (T)
+pub fn forward_price(s: f64, r: f64, q: f64, t: f64) -> f64 {
+    s * ((r - q) * t).exp()
+}
+
+/// Forward price implied by a call and a put of the same strike and expiry.
+///
+/// Put-call parity `C - P = exp(-r*T) * (F - K)` gives `F = K + (C - P) * exp(r*T)`, which
+/// needs no dividend or volatility assumption.
+///
+/// # Arguments
+///  - call: call price
+///  - put: put price
+///  - k: common strike (K)
+///  - r: continuously compounded risk-free rate
+///  - t: common time to expiry in years (T)
+pub fn implied_forward_from_options(call: f64, put: f64, k: f64, r: f64, t: f64) -> f64 {
+    k + (call - put) * (r * t).exp()
+}
+
+/// Delta: sensitivity of the option price to spot.
+///
+/// Call delta is `N(d1)` and put delta is `N(d1) - 1`. When `t <= 0` or `sigma <= 0` the delta
+/// of the intrinsic value is returned, see [`black_scholes_at_expiry`].
+///
+/// # Returns
+/// (call_delta, put_delta)
+pub fn black_scholes_delta(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> (f64, f64) {
+    if t <= 0.0 || sigma <= 0.0 {
+        let (_, _, call_delta, put_delta) = black_scholes_at_expiry(s, k);
+        return (call_delta, put_delta);
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let nd1 = stdn.cdf(d1);
+    (nd1, nd1 - 1.0)
+}
+
+/// Gamma: sensitivity of delta to spot, `phi(d1) / (S * sigma * sqrt(T))`.
+///
+/// Identical for calls and puts. When `t <= 0` or `sigma <= 0` gamma is a Dirac delta at the
+/// strike: [`f64::INFINITY`] if `s == k` and 0 otherwise.
+pub fn black_scholes_gamma(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return if s == k { f64::INFINITY } else { 0.0 };
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    stdn.pdf(d1) / (s * sigma * t.sqrt())
+}
+
+/// Vega: sensitivity of the option price to volatility, `S * phi(d1) * sqrt(T)`.
+///
+/// Identical for calls and puts. Returns 0 when `t <= 0` or `sigma <= 0`.
+pub fn black_scholes_vega(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    s * stdn.pdf(d1) * t.sqrt()
+}
+
+/// Vomma (volga): sensitivity of vega to volatility, `d^2V/d sigma^2 = vega * d1 * d2 / sigma`.
+///
+/// Identical for calls and puts. Returns 0 when `t <= 0` or `sigma <= 0`.
+pub fn black_scholes_vomma(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    let d2 = d2_f(s, k, t, r, sigma);
+    black_scholes_vega(s, k, t, r, sigma) * d1 * d2 / sigma
+}
+
+/// Vanna: sensitivity of delta to volatility, `d^2V/(dS d sigma) = -phi(d1) * d2 / sigma`.
+///
+/// Identical for calls and puts. Returns 0 when `t <= 0` or `sigma <= 0`.
+pub fn black_scholes_vanna(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    let d2 = d2_f(s, k, t, r, sigma);
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    -stdn.pdf(d1) * d2 / sigma
+}
+
+/// Charm (delta decay): rate of change of delta with the passage of time, `d^2V/(dS dt)`.
+///
+/// Computed in the Merton model with dividend yield `q`. Expressed per year of calendar time,
+/// i.e. the negative of the derivative with respect to time to maturity. Returns 0 when
+/// `t <= 0` or `sigma <= 0`.
+pub fn black_scholes_charm(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, is_call: bool) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+    let d1 = d1_f(s, k, t, r - q, sigma);
+    let d2 = d1 - sigma * t.sqrt();
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let dq = (-q * t).exp();
+    let common = dq * stdn.pdf(d1) * (2.0 * (r - q) * t - d2 * sigma * t.sqrt())
+        / (2.0 * t * sigma * t.sqrt());
+    if is_call {
+        q * dq * stdn.cdf(d1) - common
+    } else {
+        -q * dq * stdn.cdf(-d1) - common
+    }
+}
+
+/// Speed: third derivative of the option price with respect to spot,
+/// `d^3V/dS^3 = -gamma / S * (d1 / (sigma * sqrt(T)) + 1)`.
+///
+/// Identical for calls and puts. Returns 0 when `t <= 0` or `sigma <= 0`.
+pub fn black_scholes_speed(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    -black_scholes_gamma(s, k, t, r, sigma) / s * (d1 / (sigma * t.sqrt()) + 1.0)
+}
+
+/// Color: sensitivity of gamma to time, `d^3V/(dS^2 dT)`.
+///
+/// Computed in the Merton model with dividend yield `q`. Unlike [`black_scholes_charm`] this
+/// follows the usual textbook convention of differentiating with respect to time to maturity,
+/// so near-the-money color is negative: gamma grows as expiry approaches. Identical for calls
+/// and puts. Returns 0 when `t <= 0` or `sigma <= 0`.
+pub fn black_scholes_color(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+    let sigma_sqrt_t = sigma * t.sqrt();
+    let d1 = d1_f(s, k, t, r - q, sigma);
+    let d2 = d1 - sigma_sqrt_t;
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    -(-q * t).exp() * stdn.pdf(d1) / (2.0 * s * t * sigma_sqrt_t)
+        * (2.0 * q * t + 1.0 + (2.0 * (r - q) * t - d2 * sigma_sqrt_t) / sigma_sqrt_t * d1)
+}
+
+/// Zomma: sensitivity of gamma to volatility, `d^3V/(dS^2 d sigma) = gamma * (d1 * d2 - 1) / sigma`.
+///
+/// Identical for calls and puts. Returns 0 when `t <= 0` or `sigma <= 0`.
+pub fn black_scholes_zomma(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    if t <= 0.0 || sigma <= 0.0 {
+        return 0.0;
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    let d2 = d2_f(s, k, t, r, sigma);
+    black_scholes_gamma(s, k, t, r, sigma) * (d1 * d2 - 1.0) / sigma
+}
+
+/// Dual Greeks: sensitivities of the option price to the strike.
+///
+/// Dual delta is `-exp(-r*T) * N(d2)` for calls and `exp(-r*T) * N(-d2)` for puts. Dual gamma,
+/// `d^2C/dK^2 = exp(-r*T) * phi(d2) / (K * sigma * sqrt(T))`, is the same for calls and puts and
+/// is the discounted risk-neutral density of the terminal spot evaluated at `K`.
+///
+/// # Returns
+/// (dual_delta_call, dual_delta_put, dual_gamma, risk_neutral_density)
+pub fn black_scholes_dual_greeks(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+) -> (f64, f64, f64, f64) {
+    if t <= 0.0 || sigma <= 0.0 {
+        let (_, _, call_delta, _) = black_scholes_at_expiry(s, k);
+        let dual_gamma = if s == k { f64::INFINITY } else { 0.0 };
+        return (-call_delta, 1.0 - call_delta, dual_gamma, dual_gamma);
+    }
+    let d2 = d2_f(s, k, t, r, sigma);
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let df = (-r * t).exp();
+    let density = stdn.pdf(d2) / (k * sigma * t.sqrt());
+    (
+        -df * stdn.cdf(d2),
+        df * stdn.cdf(-d2),
+        df * density,
+        density,
+    )
+}
+
+/// Characteristic function `u -> E[exp(i u ln S_T)]` of the Black-Scholes log-price, for complex
+/// arguments.
+///
+/// `phi(u) = exp(i u (ln S + (r - sigma^2 / 2) T) - sigma^2 T u^2 / 2)`. Fourier pricers such as
+/// [`carr_madan_fft`](crate::options::carr_madan_fft) evaluate it off the real axis.
+pub fn bs_char_fn_complex(s: f64, r: f64, sigma: f64, t: f64) -> impl Fn(Complex) -> Complex {
+    let mean = s.ln() + (r - 0.5 * sigma * sigma) * t;
+    let var = sigma * sigma * t;
+    move |u| (Complex::i() * u * mean - 0.5 * var * u * u).exp()
+}
+
+/// Characteristic function of the Black-Scholes log-price on the real line.
+///
+/// # Returns
+/// Closure mapping `u` to `(Re phi(u), Im phi(u))`, see [`bs_char_fn_complex`]
+pub fn bs_char_fn(s: f64, r: f64, sigma: f64, t: f64) -> impl Fn(f64) -> (f64, f64) {
+    let phi = bs_char_fn_complex(s, r, sigma, t);
+    move |u| {
+        let z = phi(Complex::new(u, 0.0));
+        (z.re, z.im)
+    }
+}
+
+/// First-order sensitivities, plus gamma, of a single European option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    /// Sensitivity to spot, `dV/dS`.
+    pub delta: f64,
+    /// Sensitivity of delta to spot, `d2V/dS2`.
+    pub gamma: f64,
+    /// Sensitivity to volatility, `dV/dsigma`.
+    pub vega: f64,
+    /// Sensitivity to the passage of time, `-dV/dT`, per year.
+    pub theta: f64,
+    /// Sensitivity to the risk-free rate, `dV/dr`.
+    pub rho: f64,
+    /// Sensitivity to the continuous dividend yield, `dV/dq`.
+    pub rho_dividend: f64,
+}
+
+/// Delta, gamma, vega, theta and rho of a European option under Black-Scholes.
+///
+/// Theta is quoted per year of calendar time (negative for a long vanilla in most cases). When
+/// `t <= 0` or `sigma <= 0` the limits of [`black_scholes_at_expiry`] apply: only delta and, at
+/// the money, gamma are non-zero.
+pub fn black_scholes_greeks(s: f64, k: f64, t: f64, r: f64, sigma: f64, is_call: bool) -> Greeks {
+    let (call_delta, put_delta) = black_scholes_delta(s, k, t, r, sigma);
+    let delta = if is_call { call_delta } else { put_delta };
+    if t <= 0.0 || sigma <= 0.0 {
+        return Greeks {
+            delta,
+            gamma: black_scholes_gamma(s, k, t, r, sigma),
+            vega: 0.0,
+            theta: 0.0,
+            rho: 0.0,
+            rho_dividend: 0.0,
+        };
+    }
+    let d1 = d1_f(s, k, t, r, sigma);
+    let d2 = d2_f(s, k, t, r, sigma);
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let df = (-r * t).exp();
+    let decay = -s * stdn.pdf(d1) * sigma / (2.0 * t.sqrt());
+    let (theta, rho) = if is_call {
+        (decay - r * k * df * stdn.cdf(d2), k * t * df * stdn.cdf(d2))
+    } else {
+        (
+            decay + r * k * df * stdn.cdf(-d2),
+            -k * t * df * stdn.cdf(-d2),
+        )
+    };
+    Greeks {
+        delta,
+        gamma: black_scholes_gamma(s, k, t, r, sigma),
+        vega: black_scholes_vega(s, k, t, r, sigma),
+        theta,
+        rho,
+        rho_dividend: -t * s * delta,
+    }
+}
+
+/// Greeks of a European option in the Merton model with continuous dividend yield `q`.
+///
+/// Evaluated as [`black_scholes_greeks`] on the forward-adjusted spot `S * exp(-q*T)`, with
+/// delta and gamma rescaled to the actual spot and theta picking up the carry term
+/// `q * S * exp(-q*T) * N(d1)` (calls) or `-q * S * exp(-q*T) * N(-d1)` (puts). The dividend
+/// rho is `-T * S * exp(-q*T) * N(d1)` for a call and `T * S * exp(-q*T) * N(-d1)` for a put.
+/// With `q = 0` the result equals [`black_scholes_greeks`].
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - q: continuously compounded dividend yield
+///  - sigma: volatility (annualized)
+///  - is_call: true for a call, false for a put
+pub fn black_scholes_merton_greeks(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    is_call: bool,
+) -> Greeks {
+    let carry = (-q * t).exp();
+    let forward_spot = s * carry;
+    let g = black_scholes_greeks(forward_spot, k, t, r, sigma, is_call);
+    Greeks {
+        delta: carry * g.delta,
+        gamma: carry * carry * g.gamma,
+        vega: g.vega,
+        theta: g.theta + q * forward_spot * g.delta,
+        rho: g.rho,
+        rho_dividend: g.rho_dividend,
+    }
+}
+
+/// Delta, gamma and vega computed by Richardson-extrapolated finite differences of [`black_scholes`].
+///
+/// Step sizes are chosen automatically from the magnitude of spot and volatility. Useful as a
+/// cross-check of the analytical Greeks and as a template for models without closed forms.
+/// When `t <= 0` or `sigma <= 0` there is no volatility to bump, and the degenerate Greeks of
+/// [`black_scholes_greeks`] are returned.
+///
+/// # Returns
+/// (delta, gamma, vega)
+pub fn finite_difference_greeks(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    is_call: bool,
+) -> (f64, f64, f64) {
+    if t <= 0.0 || sigma <= 0.0 {
+        let g = black_scholes_greeks(s, k, t, r, sigma, is_call);
+        return (g.delta, g.gamma, g.vega);
+    }
+    let price = |s: f64, sigma: f64| {
+        let (call, put) = black_scholes(s, k, t, r, sigma);
+        if is_call {
+            call
+        } else {
+            put
+        }
+    };
+    let delta = greek_fd_richardson(|x| price(x, sigma), s, auto_step(s));
+    let gamma = second_derivative_richardson(|x| price(x, sigma), s, 0.01 * s);
+    // Keep the volatility bumps away from the sigma <= 0 intrinsic branch.
+    let vega = greek_fd_richardson(|x| price(s, x), sigma, auto_step(sigma).min(0.25 * sigma));
+    (delta, gamma, vega)
+}
+
+/// Carry efficiency: theta of a delta-hedged, financed option over its expected gamma P&L.
+///
+/// The hedged position `V - delta * S` is financed at `r`, so the time decay it actually pays is
+/// `theta - r * (V - delta * S)`. The Black-Scholes PDE states that this exactly offsets the
+/// expected gamma P&L `0.5 * gamma * S^2 * sigma^2` per year, so the ratio is `-1` whatever the
+/// parameters; a market option deviating from it is rich or cheap relative to its realised
+/// volatility breakeven. Calls and puts give the same ratio.
+///
+/// The normalisation is per unit of time, not over the whole life of the option, so there is
+/// no factor `T` in the denominator.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///
+/// # Returns
+/// Financed theta over expected gamma P&L, or NaN when there is no gamma P&L to compare with
+/// (`t <= 0`, `sigma <= 0`, or a gamma that underflows to zero far from the strike)
+pub fn theta_gamma_ratio(s: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    let greeks = black_scholes_greeks(s, k, t, r, sigma, true);
+    let gamma_pnl = 0.5 * greeks.gamma * s * s * sigma * sigma;
+    if t <= 0.0 || gamma_pnl <= 0.0 {
+        return f64::NAN;
+    }
+    let (call, _) = black_scholes(s, k, t, r, sigma);
+    let financed_theta = greeks.theta - r * (call - greeks.delta * s);
+    financed_theta / gamma_pnl
+}
+
+/// Expected daily P&L of a delta-hedged long option from gamma, `0.5 * gamma * S^2 * sigma^2 / 252`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - sigma: volatility (annualized) realised by the underlying
+///  - gamma: gamma of the position
+pub fn gamma_pnl_per_day(s: f64, sigma: f64, gamma: f64) -> f64 {
+    0.5 * gamma * s * s * sigma * sigma / 252.0
+}
+
+/// Implied volatility smile of Black-Scholes prices, for plotting or quick inspection.
+///
+/// Each strike is priced with [`black_scholes`] and its volatility recovered with
+/// [`implied_volatility_regularized`](crate::options::implied_volatility_regularized) from the
+/// out-of-the-money option, searching `[0.0001, 10]`, so the smile is flat at `sigma` up to the
+/// accuracy of the inversion. Strikes whose price underflows to zero, far in the wings or at
+/// short maturities, or whose volatility lies outside the search interval are left out.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k_range: lowest and highest strike
+///  - n_strikes: number of evenly spaced strikes
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///
+/// # Returns
+/// `(K / S, implied_vol)` pairs in increasing order of strike, one for each strike whose
+/// volatility could be recovered, so there may be fewer than `n_strikes`
+pub fn vol_smile_data(
+    s: f64,
+    k_range: (f64, f64),
+    n_strikes: usize,
+    t: f64,
+    r: f64,
+    sigma: f64,
+) -> Vec<(f64, f64)> {
+    let forward = s * (r * t).exp();
+    even_grid(k_range.0, k_range.1, n_strikes)
+        .filter_map(|k| {
+            let (call, put) = black_scholes(s, k, t, r, sigma);
+            let is_call = k >= forward;
+            let price = if is_call { call } else { put };
+            if price <= 0.0 {
+                return None;
+            }
+            crate::options::implied_volatility_regularized(price, s, k, t, r, is_call, 1e-4, 10.0)
+                .ok()
+                .map(|iv| (k / s, iv))
+        })
+        .collect()
+}
+
+/// Panics unless a call/put pair satisfies the model-free no-arbitrage bounds.
+///
+/// Checks that both prices are non-negative, that the call is worth at least
+/// `max(S - K * exp(-r*T), 0)` and that put-call parity `C - P = S - K * exp(-r*T)` holds. Each
+/// check allows `1e-10` of rounding error. Meant for tests of pricers and calibrations.
+#[cfg(test)]
+pub(crate) fn assert_no_arbitrage_bounds(call: f64, put: f64, s: f64, k: f64, t: f64, r: f64) {
+    const TOL: f64 = 1e-10;
+    let forward_intrinsic = s - k * (-r * t).exp();
+    assert!(call >= -TOL, "negative call price {}", call);
+    assert!(put >= -TOL, "negative put price {}", put);
+    assert!(
+        call >= forward_intrinsic.max(0.0) - TOL,
+        "call {} below lower bound {}",
+        call,
+        forward_intrinsic.max(0.0)
+    );
+    assert!(
+        (call - put - forward_intrinsic).abs() <= TOL,
+        "put-call parity violated: C - P = {}, S - K exp(-rT) = {}",
+        call - put,
+        forward_intrinsic
+    );
+}
+
 #[cfg(test)]
 mod tests {
-    use super::black_scholes;
+    use super::*;
+    use crate::fixed_income::log_linear_discount;
+    use approx::assert_relative_eq;
 
     #[test]
     fn test_basic_pricing() {
@@ -70,4 +612,594 @@ mod tests {
         assert_eq!(call, 20.0);
         assert_eq!(put, 0.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_vomma_matches_finite_difference() {
+        let (s, k, t, r, sigma) = (100.0, 110.0, 0.75, 0.03, 0.25);
+        let eps = 1e-5;
+        let fd = (black_scholes_vega(s, k, t, r, sigma + eps)
+            - black_scholes_vega(s, k, t, r, sigma - eps))
+            / (2.0 * eps);
+        assert_relative_eq!(black_scholes_vomma(s, k, t, r, sigma), fd, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_vomma_positive_otm() {
+        // Far from the money d1 and d2 share the same sign.
+        for &k in &[60.0, 150.0] {
+            assert!(black_scholes_vomma(100.0, k, 1.0, 0.05, 0.2) > 0.0, "k={}", k);
+        }
+    }
+
+    #[test]
+    fn test_vomma_zero_when_d1_zero() {
+        let (s, t, r, sigma): (f64, f64, f64, f64) = (100.0, 1.0, 0.05, 0.2);
+        let k = s * ((r + 0.5 * sigma * sigma) * t).exp();
+        assert_relative_eq!(black_scholes_vomma(s, k, t, r, sigma), 0.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_vanna_matches_finite_difference() {
+        let (s, k, t, r, sigma) = (100.0, 95.0, 0.5, 0.04, 0.3);
+        let eps = 1e-5;
+        let fd = (black_scholes_delta(s, k, t, r, sigma + eps).0
+            - black_scholes_delta(s, k, t, r, sigma - eps).0)
+            / (2.0 * eps);
+        assert_relative_eq!(black_scholes_vanna(s, k, t, r, sigma), fd, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_vanna_vega_formula_agrees() {
+        let (s, k, t, r, sigma): (f64, f64, f64, f64, f64) = (100.0, 120.0, 2.0, 0.01, 0.15);
+        let d1 = ((s / k).ln() + (r + 0.5 * sigma * sigma) * t) / (sigma * t.sqrt());
+        let via_vega = black_scholes_vega(s, k, t, r, sigma) * (1.0 - d1 / (sigma * t.sqrt())) / s;
+        assert_relative_eq!(black_scholes_vanna(s, k, t, r, sigma), via_vega, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_vanna_deep_itm_vanishes() {
+        assert!(black_scholes_vanna(200.0, 50.0, 0.5, 0.05, 0.2).abs() < 1e-10);
+    }
+
+    fn merton_delta(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64, is_call: bool) -> f64 {
+        let (call_delta, put_delta) = black_scholes_delta(s, k, t, r - q, sigma);
+        (-q * t).exp() * if is_call { call_delta } else { put_delta }
+    }
+
+    #[test]
+    fn test_black_scholes_within_arbitrage_bounds() {
+        for &s in &[20.0, 80.0, 100.0, 125.0, 400.0] {
+            for &(t, r, sigma) in &[
+                (0.0, 0.05, 0.2),
+                (0.1, 0.0, 0.6),
+                (2.0, 0.05, 0.2),
+                (10.0, 0.1, 0.05),
+            ] {
+                let (call, put) = black_scholes(s, 100.0, t, r, sigma);
+                assert_no_arbitrage_bounds(call, put, s, 100.0, t, r);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "negative put price")]
+    fn test_arbitrage_bounds_reject_negative_price() {
+        assert_no_arbitrage_bounds(1.0, -0.5, 100.0, 101.0, 0.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "below lower bound")]
+    fn test_arbitrage_bounds_reject_call_below_intrinsic() {
+        let (call, put) = black_scholes(150.0, 100.0, 1.0, 0.05, 0.2);
+        assert_no_arbitrage_bounds(call - 10.0, put, 150.0, 100.0, 1.0, 0.05);
+    }
+
+    #[test]
+    #[should_panic(expected = "put-call parity violated")]
+    fn test_arbitrage_bounds_reject_parity_break() {
+        let (call, put) = black_scholes(100.0, 100.0, 1.0, 0.05, 0.2);
+        assert_no_arbitrage_bounds(call, put + 1e-6, 100.0, 100.0, 1.0, 0.05);
+    }
+
+    #[test]
+    fn test_implied_forward_matches_forward_price() {
+        let (s, t, r, q, sigma) = (100.0, 1.25, 0.04, 0.015, 0.3);
+        let forward = forward_price(s, r, q, t);
+        for &k in &[60.0, 100.0, 150.0] {
+            let (call, put) = black_scholes_merton(s, k, t, r, q, sigma);
+            assert_relative_eq!(
+                implied_forward_from_options(call, put, k, r, t),
+                forward,
+                max_relative = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_forward_price_properties() {
+        assert_relative_eq!(forward_price(100.0, 0.03, 0.03, 5.0), 100.0);
+        assert_eq!(forward_price(100.0, 0.05, 0.02, 0.0), 100.0);
+        for &(s, r, q, t) in &[
+            (1e-3, 0.1, 0.9, 30.0),
+            (50.0, 0.0, 0.2, 2.0),
+            (5e3, 0.2, 0.0, 1.0),
+        ] {
+            assert!(forward_price(s, r, q, t) > 0.0);
+        }
+        // Carry above the dividend yield pushes the forward above spot.
+        assert!(forward_price(100.0, 0.05, 0.01, 1.0) > 100.0);
+        assert!(forward_price(100.0, 0.01, 0.05, 1.0) < 100.0);
+    }
+
+    #[test]
+    fn test_term_structure_flat_curves() {
+        let rates = [(0.5, 0.04), (2.0, 0.04), (10.0, 0.04)];
+        let divs = [(1.0, 0.015)];
+        for &(s, k, t) in &[(100.0, 90.0, 0.25), (100.0, 100.0, 1.0), (80.0, 120.0, 7.0)] {
+            let (call, put) = black_scholes_ts(s, k, t, &rates, &divs, 0.25);
+            let (bsm_call, bsm_put) = black_scholes_merton(s, k, t, 0.04, 0.015, 0.25);
+            assert_relative_eq!(call, bsm_call, max_relative = 1e-12);
+            assert_relative_eq!(put, bsm_put, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_term_structure_upward_curve() {
+        let rates = [(0.25, 0.01), (1.0, 0.02), (5.0, 0.04), (10.0, 0.05)];
+        let divs = [(1.0, 0.0)];
+        for &t in &[1.0, 3.0, 8.0] {
+            let (call, put) = black_scholes_ts(100.0, 100.0, t, &rates, &divs, 0.2);
+            let (short_call, short_put) = black_scholes(100.0, 100.0, t, 0.01, 0.2);
+            assert!(
+                call > short_call,
+                "t={} call={} short={}",
+                t,
+                call,
+                short_call
+            );
+            assert!(put < short_put);
+        }
+    }
+
+    #[test]
+    fn test_term_structure_discount_factor() {
+        // Put-call parity C - P = S * D_q(T) - K * D_r(T) recovers the curve discount factors.
+        let rates = [(0.5, 0.02), (2.0, 0.035), (5.0, 0.03)];
+        let divs = [(1.0, 0.01), (3.0, 0.02)];
+        let (maturities, zeros): (Vec<f64>, Vec<f64>) = rates.iter().copied().unzip();
+        let (div_maturities, yields): (Vec<f64>, Vec<f64>) = divs.iter().copied().unzip();
+        for &t in &[0.3, 1.5, 4.0, 6.0] {
+            let df = log_linear_discount(&maturities, &zeros, t);
+            let div_df = log_linear_discount(&div_maturities, &yields, t);
+            let (call_low, put_low) = black_scholes_ts(100.0, 90.0, t, &rates, &divs, 0.3);
+            let (call_high, put_high) = black_scholes_ts(100.0, 110.0, t, &rates, &divs, 0.3);
+            // The slope of C - P in strike is -D_r(T).
+            let slope = ((call_high - put_high) - (call_low - put_low)) / 20.0;
+            assert_relative_eq!(-slope, df, max_relative = 1e-10);
+            assert_relative_eq!(
+                call_low - put_low,
+                100.0 * div_df - 90.0 * df,
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn test_theta_gamma_ratio_is_minus_one() {
+        for &s in &[60.0, 100.0, 150.0] {
+            for &(t, r, sigma) in &[(0.1, 0.0, 0.4), (1.0, 0.05, 0.2), (5.0, 0.1, 0.15)] {
+                assert_relative_eq!(
+                    theta_gamma_ratio(s, 100.0, t, r, sigma),
+                    -1.0,
+                    epsilon = 1e-10
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_theta_gamma_ratio_undefined_without_gamma() {
+        assert!(theta_gamma_ratio(100.0, 100.0, 0.0, 0.05, 0.2).is_nan());
+        assert!(theta_gamma_ratio(110.0, 100.0, 1.0, 0.05, 0.0).is_nan());
+        assert!(theta_gamma_ratio(1e4, 1.0, 0.01, 0.05, 0.1).is_nan());
+    }
+
+    #[test]
+    fn test_theta_pays_for_gamma() {
+        // Without rates theta alone is the rent paid for gamma: the breakeven condition.
+        let (s, t, sigma) = (100.0, 0.5, 0.25);
+        for is_call in [true, false] {
+            let g = black_scholes_greeks(s, 95.0, t, 0.0, sigma, is_call);
+            assert_relative_eq!(
+                gamma_pnl_per_day(s, sigma, g.gamma) + g.theta / 252.0,
+                0.0,
+                epsilon = 1e-14
+            );
+        }
+        // Realising more volatility than implied makes the long gamma position profitable.
+        let g = black_scholes_greeks(s, 95.0, t, 0.0, sigma, true);
+        assert!(gamma_pnl_per_day(s, 0.3, g.gamma) + g.theta / 252.0 > 0.0);
+    }
+
+    #[test]
+    fn test_vol_smile_data_flat() {
+        let data = vol_smile_data(100.0, (60.0, 150.0), 19, 0.5, 0.03, 0.25);
+        assert_eq!(data.len(), 19);
+        assert_relative_eq!(data[0].0, 0.6);
+        assert_relative_eq!(data[18].0, 1.5);
+        for pair in data.windows(2) {
+            assert!(pair[1].0 > pair[0].0);
+        }
+        for &(_, iv) in &data {
+            assert!(iv > 0.0);
+            assert_relative_eq!(iv, 0.25, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_vol_smile_data_drops_worthless_wings() {
+        // A week out, strikes far from the spot have no price left to invert.
+        let data = vol_smile_data(100.0, (20.0, 400.0), 39, 1.0 / 52.0, 0.03, 0.2);
+        assert!(!data.is_empty() && data.len() < 39);
+        assert!(data.iter().all(|&(m, _)| m > 0.2 && m < 4.0));
+        for &(_, iv) in &data {
+            assert_relative_eq!(iv, 0.2, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_merton_greeks_dividend_rho_sign() {
+        for &k in &[70.0, 100.0, 140.0] {
+            for &q in &[0.0, 0.02, 0.08] {
+                let call = black_scholes_merton_greeks(100.0, k, 1.5, 0.04, q, 0.25, true);
+                let put = black_scholes_merton_greeks(100.0, k, 1.5, 0.04, q, 0.25, false);
+                assert!(call.rho_dividend < 0.0, "k={} q={}", k, q);
+                assert!(put.rho_dividend > 0.0, "k={} q={}", k, q);
+                // Parity: C - P = S exp(-qT) - K exp(-rT).
+                let parity = -1.5 * 100.0 * (-q * 1.5_f64).exp();
+                assert_relative_eq!(
+                    call.rho_dividend - put.rho_dividend,
+                    parity,
+                    epsilon = 1e-10
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_merton_greeks_match_finite_differences() {
+        let (s, k, t, r, q, sigma) = (100.0, 95.0, 0.75, 0.03, 0.05, 0.3);
+        let eps = 1e-5;
+        for is_call in [true, false] {
+            let price = |s: f64, t: f64, r: f64, q: f64, sigma: f64| {
+                let (call, put) = black_scholes_merton(s, k, t, r, q, sigma);
+                if is_call {
+                    call
+                } else {
+                    put
+                }
+            };
+            let g = black_scholes_merton_greeks(s, k, t, r, q, sigma, is_call);
+            let fd = |f: &dyn Fn(f64) -> f64, x: f64| (f(x + eps) - f(x - eps)) / (2.0 * eps);
+            let delta = fd(&|x| price(x, t, r, q, sigma), s);
+            let gamma = fd(
+                &|x| black_scholes_merton_greeks(x, k, t, r, q, sigma, is_call).delta,
+                s,
+            );
+            let vega = fd(&|x| price(s, t, r, q, x), sigma);
+            let theta = -fd(&|x| price(s, x, r, q, sigma), t);
+            let rho = fd(&|x| price(s, t, x, q, sigma), r);
+            let rho_dividend = fd(&|x| price(s, t, r, x, sigma), q);
+            assert_relative_eq!(g.delta, delta, epsilon = 1e-6);
+            assert_relative_eq!(g.gamma, gamma, epsilon = 1e-6);
+            assert_relative_eq!(g.vega, vega, epsilon = 1e-5);
+            assert_relative_eq!(g.theta, theta, epsilon = 1e-5);
+            assert_relative_eq!(g.rho, rho, epsilon = 1e-5);
+            assert_relative_eq!(g.rho_dividend, rho_dividend, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_merton_greeks_without_dividend() {
+        for is_call in [true, false] {
+            let merton = black_scholes_merton_greeks(100.0, 110.0, 1.0, 0.05, 0.0, 0.2, is_call);
+            let bs = black_scholes_greeks(100.0, 110.0, 1.0, 0.05, 0.2, is_call);
+            assert_eq!(merton, bs);
+            // The rate rho keeps the sign of the standard formula.
+            assert_eq!(merton.rho > 0.0, is_call);
+            assert_relative_eq!(merton.rho_dividend.abs(), 100.0 * bs.delta.abs());
+        }
+    }
+
+    #[test]
+    fn test_merton_reduces_to_black_scholes() {
+        let merton = black_scholes_merton(100.0, 100.0, 1.0, 0.05, 0.0, 0.2);
+        let bs = black_scholes(100.0, 100.0, 1.0, 0.05, 0.2);
+        assert_relative_eq!(merton.0, bs.0, epsilon = 1e-12);
+        assert_relative_eq!(merton.1, bs.1, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_charm_matches_finite_difference() {
+        let (s, k, t, r, q, sigma) = (100.0, 105.0, 0.8, 0.04, 0.02, 0.25);
+        let eps = 1e-5;
+        for &is_call in &[true, false] {
+            let fd = (merton_delta(s, k, t - eps, r, q, sigma, is_call)
+                - merton_delta(s, k, t + eps, r, q, sigma, is_call))
+                / (2.0 * eps);
+            assert_relative_eq!(black_scholes_charm(s, k, t, r, q, sigma, is_call), fd, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_charm_negative_for_otm_call() {
+        for &k in &[110.0, 130.0] {
+            assert!(black_scholes_charm(100.0, k, 0.5, 0.01, 0.0, 0.2, true) < 0.0, "k={}", k);
+        }
+    }
+
+    #[test]
+    fn test_charm_zero_at_expiry() {
+        assert_eq!(black_scholes_charm(100.0, 100.0, 0.0, 0.05, 0.02, 0.2, true), 0.0);
+        assert_eq!(black_scholes_charm(100.0, 100.0, 0.0, 0.05, 0.02, 0.2, false), 0.0);
+    }
+
+    #[test]
+    fn test_speed_matches_finite_difference() {
+        let (s, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.2);
+        let eps = 1e-4;
+        let fd = (black_scholes_gamma(s + eps, k, t, r, sigma)
+            - black_scholes_gamma(s - eps, k, t, r, sigma))
+            / (2.0 * eps);
+        assert_relative_eq!(black_scholes_speed(s, k, t, r, sigma), fd, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_speed_negative_around_the_money() {
+        for &k in &[70.0, 80.0, 90.0, 100.0, 110.0] {
+            assert!(black_scholes_speed(100.0, k, 1.0, 0.05, 0.2) < 0.0, "k={}", k);
+        }
+    }
+
+    fn merton_gamma(s: f64, k: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+        (-q * t).exp() * black_scholes_gamma(s, k, t, r - q, sigma)
+    }
+
+    #[test]
+    fn test_color_matches_finite_difference() {
+        let (s, k, t, r, q, sigma) = (100.0, 95.0, 0.5, 0.03, 0.01, 0.2);
+        let eps = 1e-5;
+        let fd = (merton_gamma(s, k, t + eps, r, q, sigma)
+            - merton_gamma(s, k, t - eps, r, q, sigma))
+            / (2.0 * eps);
+        assert_relative_eq!(
+            black_scholes_color(s, k, t, r, q, sigma),
+            fd,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_color_negative_near_expiry_atm() {
+        for &t in &[0.01, 0.05, 0.1] {
+            assert!(
+                black_scholes_color(100.0, 100.0, t, 0.03, 0.01, 0.2) < 0.0,
+                "t={}",
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn test_color_finite() {
+        for &k in &[50.0, 80.0, 100.0, 120.0, 200.0] {
+            for &t in &[0.01, 0.5, 5.0] {
+                assert!(black_scholes_color(100.0, k, t, 0.05, 0.02, 0.3).is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn test_zomma_matches_finite_difference() {
+        let (s, k, t, r, sigma) = (100.0, 90.0, 0.75, 0.02, 0.3);
+        let eps = 1e-5;
+        let fd = (black_scholes_gamma(s, k, t, r, sigma + eps)
+            - black_scholes_gamma(s, k, t, r, sigma - eps))
+            / (2.0 * eps);
+        assert_relative_eq!(black_scholes_zomma(s, k, t, r, sigma), fd, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_zomma_sign_follows_d1_d2() {
+        let (s, t, r, sigma) = (100.0, 1.0, 0.0, 0.2);
+        for &k in &[50.0, 70.0, 100.0, 130.0, 200.0] {
+            let d1 = d1_f(s, k, t, r, sigma);
+            let d2 = d2_f(s, k, t, r, sigma);
+            let zomma = black_scholes_zomma(s, k, t, r, sigma);
+            if d1 * d2 > 1.0 {
+                assert!(zomma > 0.0, "k={} zomma={}", k, zomma);
+            } else {
+                assert!(zomma < 0.0, "k={} zomma={}", k, zomma);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zomma_predicts_gamma_after_vol_shock() {
+        // A trader long ATM gamma sees that gamma falls when implied vol rises; zomma gives the
+        // first-order size of that change.
+        let (s, k, t, r, sigma) = (100.0, 100.0, 0.5, 0.01, 0.2);
+        let d_sigma = 0.01;
+        let zomma = black_scholes_zomma(s, k, t, r, sigma);
+        assert!(zomma < 0.0);
+        let predicted = black_scholes_gamma(s, k, t, r, sigma) + zomma * d_sigma;
+        let actual = black_scholes_gamma(s, k, t, r, sigma + d_sigma);
+        assert_relative_eq!(predicted, actual, max_relative = 1e-2);
+    }
+
+    #[test]
+    fn test_dual_greeks_match_strike_finite_differences() {
+        let (s, k, t, r, sigma): (f64, f64, f64, f64, f64) = (100.0, 105.0, 1.0, 0.05, 0.2);
+        let eps = 1e-3;
+        let call = |k: f64| black_scholes(s, k, t, r, sigma).0;
+        let put = |k: f64| black_scholes(s, k, t, r, sigma).1;
+        let (dd_call, dd_put, dual_gamma, density) = black_scholes_dual_greeks(s, k, t, r, sigma);
+
+        assert_relative_eq!(
+            dd_call,
+            (call(k + eps) - call(k - eps)) / (2.0 * eps),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            dd_put,
+            (put(k + eps) - put(k - eps)) / (2.0 * eps),
+            epsilon = 1e-6
+        );
+        let d2c_dk2 = (call(k + eps) - 2.0 * call(k) + call(k - eps)) / (eps * eps);
+        assert_relative_eq!(dual_gamma, d2c_dk2, epsilon = 1e-6);
+        assert_relative_eq!(density, d2c_dk2 / (-r * t).exp(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_finite_difference_greeks_match_analytical() {
+        let (s, k, t, r, sigma) = (100.0, 95.0, 0.5, 0.03, 0.25);
+        let (call_delta, put_delta) = black_scholes_delta(s, k, t, r, sigma);
+        for &(is_call, analytic_delta) in &[(true, call_delta), (false, put_delta)] {
+            let (delta, gamma, vega) = finite_difference_greeks(s, k, t, r, sigma, is_call);
+            assert_relative_eq!(delta, analytic_delta, epsilon = 1e-9);
+            assert_relative_eq!(
+                gamma,
+                black_scholes_gamma(s, k, t, r, sigma),
+                epsilon = 1e-8
+            );
+            assert_relative_eq!(vega, black_scholes_vega(s, k, t, r, sigma), epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_finite_difference_greeks_degenerate() {
+        for &(t, sigma) in &[(0.0, 0.2), (0.5, 0.0), (0.0, 0.0)] {
+            for &s in &[80.0, 100.0, 120.0] {
+                for &is_call in &[true, false] {
+                    let (delta, gamma, vega) =
+                        finite_difference_greeks(s, 90.0, t, 0.03, sigma, is_call);
+                    let g = black_scholes_greeks(s, 90.0, t, 0.03, sigma, is_call);
+                    assert!(delta.is_finite() && gamma.is_finite() && vega.is_finite());
+                    assert_eq!((delta, gamma, vega), (g.delta, g.gamma, g.vega));
+                }
+            }
+        }
+        assert_eq!(
+            finite_difference_greeks(100.0, 90.0, 1.0, 0.03, 0.0, true).2,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_greeks_theta_rho_match_finite_difference() {
+        let (s, k, t, r, sigma) = (100.0, 95.0, 0.5, 0.04, 0.3);
+        let h = 1e-5;
+        for &is_call in &[true, false] {
+            let price = |t: f64, r: f64| {
+                let (call, put) = black_scholes(s, k, t, r, sigma);
+                if is_call {
+                    call
+                } else {
+                    put
+                }
+            };
+            let g = black_scholes_greeks(s, k, t, r, sigma, is_call);
+            let theta_fd = -(price(t + h, r) - price(t - h, r)) / (2.0 * h);
+            let rho_fd = (price(t, r + h) - price(t, r - h)) / (2.0 * h);
+            assert_relative_eq!(g.theta, theta_fd, epsilon = 1e-5);
+            assert_relative_eq!(g.rho, rho_fd, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_char_fn_normalised_and_bounded() {
+        let phi = bs_char_fn(100.0, 0.05, 0.2, 1.0);
+        assert_eq!(phi(0.0), (1.0, 0.0));
+        for i in 1..200 {
+            let (re, im) = phi(i as f64 * 0.37 - 30.0);
+            assert!(re.hypot(im) <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_gil_pelaez_recovers_lognormal_cdf() {
+        use crate::math::integrate_gl;
+        let (s, r, sigma, t): (f64, f64, f64, f64) = (100.0, 0.05, 0.2, 1.0);
+        let phi = bs_char_fn(s, r, sigma, t);
+        let mean = s.ln() + (r - 0.5 * sigma * sigma) * t;
+        let stdn = Normal::new(0.0, 1.0).unwrap();
+        for &k in &[70.0_f64, 95.0, 100.0, 110.0, 150.0] {
+            let x = k.ln();
+            // P(ln S_T <= x) = 1/2 - (1/pi) int_0^inf Im[exp(-iux) phi(u)] / u du
+            let integrand = |u: f64| {
+                let (re, im) = phi(u);
+                (im * (u * x).cos() - re * (u * x).sin()) / u
+            };
+            let cdf = 0.5 - integrate_gl(integrand, 0.0, 60.0, 200) / std::f64::consts::PI;
+            let expected = stdn.cdf((x - mean) / (sigma * t.sqrt()));
+            assert_relative_eq!(cdf, expected, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_at_expiry_limits() {
+        assert_eq!(black_scholes_at_expiry(110.0, 100.0), (10.0, 0.0, 1.0, 0.0));
+        assert_eq!(black_scholes_at_expiry(90.0, 100.0), (0.0, 10.0, 0.0, -1.0));
+        assert_eq!(black_scholes_at_expiry(100.0, 100.0), (0.0, 0.0, 0.5, -0.5));
+        // Deltas just before expiry approach the step function away from the strike.
+        let (call_delta, put_delta) = black_scholes_delta(101.0, 100.0, 1e-8, 0.05, 0.2);
+        assert_relative_eq!(call_delta, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(put_delta, 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_greeks_at_expiry_never_nan() {
+        for &s in &[90.0, 100.0, 110.0] {
+            for &(t, sigma) in &[(0.0, 0.2), (-1.0, 0.2), (1.0, 0.0), (0.0, 0.0)] {
+                let (k, r, q) = (100.0, 0.05, 0.02);
+                for is_call in [true, false] {
+                    let g = black_scholes_greeks(s, k, t, r, sigma, is_call);
+                    for v in [g.delta, g.gamma, g.vega, g.theta, g.rho] {
+                        assert!(!v.is_nan(), "s={} t={} sigma={} {:?}", s, t, sigma, g);
+                    }
+                    assert!(!black_scholes_charm(s, k, t, r, q, sigma, is_call).is_nan());
+                }
+                let (call, put) = black_scholes(s, k, t, r, sigma);
+                let (dual_call, dual_put, dual_gamma, density) =
+                    black_scholes_dual_greeks(s, k, t, r, sigma);
+                let higher = [
+                    black_scholes_vomma(s, k, t, r, sigma),
+                    black_scholes_vanna(s, k, t, r, sigma),
+                    black_scholes_speed(s, k, t, r, sigma),
+                    black_scholes_color(s, k, t, r, q, sigma),
+                    black_scholes_zomma(s, k, t, r, sigma),
+                ];
+                for v in [call, put, dual_call, dual_put, dual_gamma, density] {
+                    assert!(!v.is_nan());
+                }
+                assert!(higher.iter().all(|&v| v == 0.0));
+                assert_eq!(black_scholes_vega(s, k, t, r, sigma), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gamma_at_expiry_is_dirac() {
+        assert_eq!(
+            black_scholes_gamma(100.0, 100.0, 0.0, 0.05, 0.2),
+            f64::INFINITY
+        );
+        assert_eq!(black_scholes_gamma(100.5, 100.0, 0.0, 0.05, 0.2), 0.0);
+        assert_eq!(
+            black_scholes_greeks(100.0, 100.0, 0.0, 0.05, 0.2, true).gamma,
+            f64::INFINITY
+        );
+        // Gamma grows without bound at the money as expiry approaches.
+        assert!(black_scholes_gamma(100.0, 100.0, 1e-10, 0.0, 0.2) > 1e3);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::optimization::SecantError;
+use crate::options::black_scholes::{black_scholes_greeks, Greeks};
+use crate::options::volatility::try_implied_volatility;
+
+/// Bid/ask quotes for calls and puts across strikes at a single expiry.
+///
+/// All quote vectors are indexed like `strikes`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionChain {
+    /// Time to expiry in years.
+    pub expiry: f64,
+    /// Spot price of the underlying.
+    pub spot: f64,
+    /// Continuously compounded risk-free rate.
+    pub rate: f64,
+    pub strikes: Vec<f64>,
+    pub call_bids: Vec<f64>,
+    pub call_asks: Vec<f64>,
+    pub put_bids: Vec<f64>,
+    pub put_asks: Vec<f64>,
+}
+
+impl OptionChain {
+    /// Mid prices `(bid + ask) / 2` for every strike.
+    ///
+    /// # Returns
+    /// (call_mids, put_mids)
+    pub fn mid_prices(&self) -> (Vec<f64>, Vec<f64>) {
+        let mid = |bids: &[f64], asks: &[f64]| -> Vec<f64> {
+            bids.iter().zip(asks).map(|(b, a)| 0.5 * (b + a)).collect()
+        };
+        (
+            mid(&self.call_bids, &self.call_asks),
+            mid(&self.put_bids, &self.put_asks),
+        )
+    }
+
+    /// Black-Scholes implied volatility of the mid price at each strike.
+    ///
+    /// Uses the out-of-the-money option, i.e. the put below the forward `S * exp(r * T)` and the
+    /// call at or above it, since its price carries the most time value.
+    pub fn implied_vols(&self) -> Vec<Result<f64, SecantError>> {
+        let (call_mids, put_mids) = self.mid_prices();
+        let forward = self.spot * (self.rate * self.expiry).exp();
+        self.strikes
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| {
+                let is_call = k >= forward;
+                let price = if is_call { call_mids[i] } else { put_mids[i] };
+                try_implied_volatility(price, self.spot, k, self.expiry, self.rate, is_call)
+            })
+            .collect()
+    }
+
+    /// Call or put Greeks at each strike for a flat volatility `sigma`.
+    ///
+    /// # Arguments
+    ///  - sigma: volatility (annualized)
+    ///  - is_call: true for call Greeks, false for put Greeks
+    pub fn greeks(&self, sigma: f64, is_call: bool) -> Vec<Greeks> {
+        self.strikes
+            .iter()
+            .map(|&k| black_scholes_greeks(self.spot, k, self.expiry, self.rate, sigma, is_call))
+            .collect()
+    }
+}
+
+/// Weighted average of the chain's implied volatilities, skipping strikes of zero weight.
+fn weighted_iv(chain: &OptionChain, weights: &[f64]) -> Result<f64, SecantError> {
+    debug_assert_eq!(chain.strikes.len(), weights.len());
+    let (mut sum, mut total) = (0.0, 0.0);
+    for (iv, &w) in chain.implied_vols().into_iter().zip(weights) {
+        if w > 0.0 {
+            sum += w * iv?;
+            total += w;
+        }
+    }
+    if total > 0.0 {
+        Ok(sum / total)
+    } else {
+        Err(SecantError::OutOfBounds)
+    }
+}
+
+/// Volume-weighted average implied volatility of a chain.
+///
+/// Each strike's implied volatility is that of [`OptionChain::implied_vols`]. Strikes with zero
+/// volume are excluded, so an unsolvable quote there does not cause an error.
+///
+/// # Arguments
+///  - chain: option chain
+///  - volumes: traded volume at each strike
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]>, the first inversion error of a traded strike, or
+/// [`SecantError::OutOfBounds`] if nothing traded
+pub fn volume_weighted_iv(chain: &OptionChain, volumes: &[f64]) -> Result<f64, SecantError> {
+    weighted_iv(chain, volumes)
+}
+
+/// Open-interest-weighted average implied volatility of a chain.
+///
+/// Like [`volume_weighted_iv`], with strikes of zero open interest excluded.
+///
+/// # Arguments
+///  - chain: option chain
+///  - open_interests: open interest at each strike
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]>, the first inversion error of a strike with open
+/// interest, or [`SecantError::OutOfBounds`] if there is none
+pub fn open_interest_weighted_iv(
+    chain: &OptionChain,
+    open_interests: &[f64],
+) -> Result<f64, SecantError> {
+    weighted_iv(chain, open_interests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+    use approx::assert_relative_eq;
+
+    fn chain(sigma: f64, half_spread: f64) -> OptionChain {
+        let (spot, rate, expiry) = (100.0, 0.03, 0.5);
+        let strikes = vec![80.0, 90.0, 100.0, 110.0, 120.0];
+        let prices: Vec<(f64, f64)> = strikes
+            .iter()
+            .map(|&k| black_scholes(spot, k, expiry, rate, sigma))
+            .collect();
+        OptionChain {
+            expiry,
+            spot,
+            rate,
+            call_bids: prices.iter().map(|p| p.0 - half_spread).collect(),
+            call_asks: prices.iter().map(|p| p.0 + half_spread).collect(),
+            put_bids: prices.iter().map(|p| p.1 - half_spread).collect(),
+            put_asks: prices.iter().map(|p| p.1 + half_spread).collect(),
+            strikes,
+        }
+    }
+
+    #[test]
+    fn test_mid_prices_satisfy_parity() {
+        let c = chain(0.25, 0.05);
+        let (calls, puts) = c.mid_prices();
+        for (i, &k) in c.strikes.iter().enumerate() {
+            assert!(c.call_bids[i] <= calls[i] && calls[i] <= c.call_asks[i]);
+            assert!(c.put_bids[i] <= puts[i] && puts[i] <= c.put_asks[i]);
+            let parity = c.spot - k * (-c.rate * c.expiry).exp();
+            assert_relative_eq!(calls[i] - puts[i], parity, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_implied_vols_round_trip() {
+        let c = chain(0.25, 0.01);
+        for iv in c.implied_vols() {
+            assert_relative_eq!(
+                iv.expect("implied vol should converge"),
+                0.25,
+                epsilon = 1e-4
+            );
+        }
+    }
+
+    #[test]
+    fn test_greeks_per_strike() {
+        let c = chain(0.25, 0.0);
+        let calls = c.greeks(0.25, true);
+        let puts = c.greeks(0.25, false);
+        assert_eq!(calls.len(), c.strikes.len());
+        // Delta decreases with strike.
+        assert!(calls.windows(2).all(|w| w[0].delta > w[1].delta));
+        assert!(puts.windows(2).all(|w| w[0].delta > w[1].delta));
+        // Put-call parity: the put is one share short of the call, with the same convexity.
+        for (call, put) in calls.iter().zip(&puts) {
+            assert!(put.delta < 0.0);
+            assert_relative_eq!(call.delta - put.delta, 1.0, epsilon = 1e-12);
+            assert_relative_eq!(call.gamma, put.gamma, epsilon = 1e-12);
+            assert_relative_eq!(call.vega, put.vega, epsilon = 1e-12);
+        }
+    }
+
+    /// Chain with a skew, quoted without spread.
+    fn skewed_chain() -> OptionChain {
+        let mut c = chain(0.25, 0.0);
+        let forward = c.spot * (c.rate * c.expiry).exp();
+        for (i, &k) in c.strikes.clone().iter().enumerate() {
+            let vol = 0.25 - 0.3 * (k / forward).ln();
+            let (call, put) = black_scholes(c.spot, k, c.expiry, c.rate, vol);
+            c.call_bids[i] = call;
+            c.call_asks[i] = call;
+            c.put_bids[i] = put;
+            c.put_asks[i] = put;
+        }
+        c
+    }
+
+    #[test]
+    fn test_weighted_iv_between_extremes() {
+        let c = skewed_chain();
+        let ivs: Vec<f64> = c.implied_vols().into_iter().map(|v| v.unwrap()).collect();
+        let (lo, hi) = ivs
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+        let volume = volume_weighted_iv(&c, &[10.0, 250.0, 900.0, 400.0, 5.0]).unwrap();
+        let oi = open_interest_weighted_iv(&c, &[3000.0, 1500.0, 800.0, 2500.0, 100.0]).unwrap();
+        for v in [volume, oi] {
+            assert!(lo <= v && v <= hi);
+        }
+        assert!(volume != oi);
+    }
+
+    #[test]
+    fn test_equal_volumes_give_mean() {
+        let c = skewed_chain();
+        let ivs: Vec<f64> = c.implied_vols().into_iter().map(|v| v.unwrap()).collect();
+        let mean = ivs.iter().sum::<f64>() / ivs.len() as f64;
+        assert_relative_eq!(
+            volume_weighted_iv(&c, &[7.0; 5]).unwrap(),
+            mean,
+            epsilon = 1e-14
+        );
+    }
+
+    #[test]
+    fn test_zero_volume_excluded() {
+        let mut c = skewed_chain();
+        let ivs: Vec<f64> = c.implied_vols().into_iter().map(|v| v.unwrap()).collect();
+        // An unsolvable quote does not matter when nothing traded there.
+        c.call_bids[4] = 2.0 * c.spot;
+        c.call_asks[4] = 2.0 * c.spot;
+        let v = volume_weighted_iv(&c, &[0.0, 1.0, 0.0, 3.0, 0.0]).unwrap();
+        assert_relative_eq!(v, 0.25 * ivs[1] + 0.75 * ivs[3], epsilon = 1e-14);
+        assert_eq!(
+            volume_weighted_iv(&c, &[0.0, 1.0, 0.0, 3.0, 1.0]),
+            Err(SecantError::OutOfBounds)
+        );
+        assert_eq!(
+            volume_weighted_iv(&c, &[0.0; 5]),
+            Err(SecantError::OutOfBounds)
+        );
+    }
+}
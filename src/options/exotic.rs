@@ -0,0 +1,216 @@
+use crate::options::monte_carlo::mean_std_err;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// Prices a cliquet (ratchet) option by Monte Carlo under GBM.
+///
+/// Over each reset period the return `R_i = S(t_i) / S(t_{i-1}) - 1` is clamped to
+/// `[local_floor, local_cap]`; the sum of the clamped returns is then clamped to
+/// `[global_floor, global_cap]` and paid at the last reset date on a notional of `s0`:
+///
+/// `s0 * min(max(sum_i min(max(R_i, local_floor), local_cap), global_floor), global_cap)`
+///
+/// Use `f64::INFINITY` / `f64::NEG_INFINITY` for absent caps and floors.
+///
+/// # Arguments
+///  - s0: spot price at inception, also the notional
+///  - local_cap: cap on each periodic return
+///  - local_floor: floor on each periodic return
+///  - global_cap: cap on the sum of clamped returns
+///  - global_floor: floor on the sum of clamped returns
+///  - reset_dates: increasing reset times in years, the first period starting at 0
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - n_sims: number of simulated paths
+///  - seed: random seed
+///
+/// # Returns
+/// (estimate, std_err)
+#[allow(clippy::too_many_arguments)]
+pub fn cliquet_mc(
+    s0: f64,
+    local_cap: f64,
+    local_floor: f64,
+    global_cap: f64,
+    global_floor: f64,
+    reset_dates: &[f64],
+    r: f64,
+    sigma: f64,
+    n_sims: u64,
+    seed: u64,
+) -> (f64, f64) {
+    let maturity = reset_dates.last().copied().unwrap_or(0.0);
+    let df = (-r * maturity).exp();
+    // Log-return drift and volatility of each reset period.
+    let periods: Vec<(f64, f64)> = std::iter::once(0.0)
+        .chain(reset_dates.iter().copied())
+        .collect::<Vec<f64>>()
+        .windows(2)
+        .map(|w| {
+            let dt = w[1] - w[0];
+            ((r - 0.5 * sigma * sigma) * dt, sigma * dt.sqrt())
+        })
+        .collect();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let samples: Vec<f64> = (0..n_sims)
+        .map(|_| {
+            let total: f64 = periods
+                .iter()
+                .map(|&(drift, vol)| {
+                    let z: f64 = StandardNormal.sample(&mut rng);
+                    ((drift + vol * z).exp() - 1.0)
+                        .max(local_floor)
+                        .min(local_cap)
+                })
+                .sum();
+            df * s0 * total.max(global_floor).min(global_cap)
+        })
+        .collect();
+    mean_std_err(&samples)
+}
+
+/// Prices a long calendar spread, long a `t2`-expiry call and short a `t1`-expiry call at the
+/// same strike, by Monte Carlo under GBM with a deterministic volatility term structure.
+///
+/// `sigma1` and `sigma2` are the implied volatilities of the two expiries. Paths are simulated to
+/// `t1` with `sigma1` and on to `t2` with the forward volatility
+/// `sqrt((sigma2^2 t2 - sigma1^2 t1) / (t2 - t1))`, so that both legs reprice to Black-Scholes.
+/// The short call settles at `t1` and the long call at `t2`; the spread gains when the far
+/// expiry's volatility is high relative to the near one's.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: common strike (K)
+///  - t1: expiry of the short call in years
+///  - t2: expiry of the long call in years, `t2 > t1`
+///  - r: continuously compounded risk-free rate
+///  - sigma1: implied volatility to `t1`
+///  - sigma2: implied volatility to `t2`
+///  - n_sims: number of simulated paths
+///  - seed: random seed
+///
+/// # Returns
+/// (estimate, std_err), NaN if total variance decreases from `t1` to `t2`
+#[allow(clippy::too_many_arguments)]
+pub fn calendar_spread_mc(
+    s: f64,
+    k: f64,
+    t1: f64,
+    t2: f64,
+    r: f64,
+    sigma1: f64,
+    sigma2: f64,
+    n_sims: u64,
+    seed: u64,
+) -> (f64, f64) {
+    let forward_var = (sigma2 * sigma2 * t2 - sigma1 * sigma1 * t1) / (t2 - t1);
+    if forward_var.is_nan() || forward_var < 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let legs = [
+        (t1, sigma1 * sigma1 * t1),
+        (t2 - t1, forward_var * (t2 - t1)),
+    ]
+    .map(|(dt, var)| (r * dt - 0.5 * var, var.sqrt()));
+    let (df1, df2) = ((-r * t1).exp(), (-r * t2).exp());
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let samples: Vec<f64> = (0..n_sims)
+        .map(|_| {
+            let mut step = |(drift, vol): (f64, f64)| {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                (drift + vol * z).exp()
+            };
+            let s1 = s * step(legs[0]);
+            let s2 = s1 * step(legs[1]);
+            df2 * (s2 - k).max(0.0) - df1 * (s1 - k).max(0.0)
+        })
+        .collect();
+    mean_std_err(&samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+
+    const INF: f64 = f64::INFINITY;
+
+    #[test]
+    fn test_calendar_spread_matches_legs() {
+        let (s, k, t1, t2, r) = (100.0, 100.0, 0.5, 1.0, 0.03);
+        let (price, se) = calendar_spread_mc(s, k, t1, t2, r, 0.2, 0.25, 200_000, 5);
+        let (near, _) = black_scholes(s, k, t1, r, 0.2);
+        let (far, _) = black_scholes(s, k, t2, r, 0.25);
+        assert!(
+            (price - (far - near)).abs() < 3.0 * se,
+            "mc={} expected={} se={}",
+            price,
+            far - near,
+            se
+        );
+        assert!(price < far);
+        assert!(calendar_spread_mc(s, k, t1, t2, r, 0.4, 0.2, 100, 5)
+            .0
+            .is_nan());
+    }
+
+    #[test]
+    fn test_calendar_spread_increases_with_vol_difference() {
+        let prices: Vec<f64> = [0.2, 0.22, 0.25, 0.3]
+            .iter()
+            .map(|&sigma2| {
+                calendar_spread_mc(100.0, 105.0, 0.25, 1.0, 0.02, 0.2, sigma2, 50_000, 9).0
+            })
+            .collect();
+        assert!(prices.windows(2).all(|w| w[1] > w[0]), "{:?}", prices);
+    }
+
+    #[test]
+    fn test_calendar_spread_std_err_scaling() {
+        let (_, se_small) = calendar_spread_mc(100.0, 100.0, 0.5, 1.0, 0.03, 0.2, 0.25, 10_000, 1);
+        let (_, se_large) = calendar_spread_mc(100.0, 100.0, 0.5, 1.0, 0.03, 0.2, 0.25, 160_000, 1);
+        let ratio = se_small / se_large;
+        assert!((ratio - 4.0).abs() < 0.2, "ratio={}", ratio);
+    }
+
+    #[test]
+    fn test_uncapped_cliquet_is_forward_start_strip() {
+        let (s0, r, sigma) = (100.0, 0.04, 0.25);
+        let resets = [0.25, 0.5, 0.75, 1.0];
+        let (price, se) = cliquet_mc(s0, INF, 0.0, INF, 0.0, &resets, r, sigma, 200_000, 7);
+        // Each period pays an ATM forward-start call on unit spot, deferred to maturity.
+        let (unit_call, _) = black_scholes(1.0, 1.0, 0.25, r, sigma);
+        let deferral = (-r * 0.75_f64).exp();
+        let expected = s0 * 4.0 * unit_call * deferral;
+        assert!(
+            (price - expected).abs() < 3.0 * se,
+            "mc={} expected={} se={}",
+            price,
+            expected,
+            se
+        );
+    }
+
+    #[test]
+    fn test_global_cap_reduces_price() {
+        let resets = [0.25, 0.5, 0.75, 1.0];
+        let (uncapped, _) = cliquet_mc(100.0, 0.1, 0.0, INF, 0.0, &resets, 0.03, 0.2, 50_000, 3);
+        let (capped, _) = cliquet_mc(100.0, 0.1, 0.0, 0.15, 0.0, &resets, 0.03, 0.2, 50_000, 3);
+        assert!(capped < uncapped, "capped={} uncapped={}", capped, uncapped);
+    }
+
+    #[test]
+    fn test_price_increases_with_resets() {
+        let prices: Vec<f64> = [1, 2, 4, 12]
+            .iter()
+            .map(|&n| {
+                let resets: Vec<f64> = (1..=n).map(|i| i as f64 / n as f64).collect();
+                cliquet_mc(100.0, INF, 0.0, INF, 0.0, &resets, 0.03, 0.2, 50_000, 11).0
+            })
+            .collect();
+        assert!(prices.windows(2).all(|w| w[1] > w[0]), "{:?}", prices);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::math::Complex;
+
+/// Heston characteristic function `u -> E[exp(i u ln S_T)]` of the log-price, for complex
+/// arguments.
+///
+/// Uses the formulation of Albrecher et al. (2007), "The little Heston trap", which keeps the
+/// complex logarithm on its principal branch for long maturities:
+///
+/// - `d = sqrt((rho xi i u - kappa)^2 + xi^2 (i u + u^2))`
+/// - `g = (kappa - rho xi i u - d) / (kappa - rho xi i u + d)`
+/// - `C = kappa theta / xi^2 * ((kappa - rho xi i u - d) T - 2 ln((1 - g e^{-dT}) / (1 - g)))`
+/// - `D = (kappa - rho xi i u - d) / xi^2 * (1 - e^{-dT}) / (1 - g e^{-dT})`
+/// - `phi(u) = exp(i u (ln S + r T) + C + D v0)`
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - v0: initial variance
+///  - kappa: mean-reversion speed of the variance
+///  - theta: long-run variance
+///  - xi: volatility of variance
+///  - rho: correlation between spot and variance shocks
+///  - r: continuously compounded risk-free rate
+///  - t: time to maturity in years (T)
+#[allow(clippy::too_many_arguments)]
+pub fn heston_char_fn_complex(
+    s: f64,
+    v0: f64,
+    kappa: f64,
+    theta: f64,
+    xi: f64,
+    rho: f64,
+    r: f64,
+    t: f64,
+) -> impl Fn(Complex) -> Complex {
+    let one = Complex::new(1.0, 0.0);
+    move |u| {
+        let iu = Complex::i() * u;
+        let beta = kappa - rho * xi * iu;
+        let d = (beta * beta + xi * xi * (iu + u * u)).sqrt();
+        let g = (beta - d) / (beta + d);
+        let e = (-d * t).exp();
+        let c =
+            kappa * theta / (xi * xi) * ((beta - d) * t - 2.0 * ((one - g * e) / (one - g)).ln());
+        let dd = (beta - d) / (xi * xi) * (one - e) / (one - g * e);
+        (iu * (s.ln() + r * t) + c + dd * v0).exp()
+    }
+}
+
+/// Heston characteristic function of the log-price on the real line.
+///
+/// # Returns
+/// Closure mapping `u` to `(Re phi(u), Im phi(u))`, see [`heston_char_fn_complex`]
+#[allow(clippy::too_many_arguments)]
+pub fn heston_char_fn(
+    s: f64,
+    v0: f64,
+    kappa: f64,
+    theta: f64,
+    xi: f64,
+    rho: f64,
+    r: f64,
+    t: f64,
+) -> impl Fn(f64) -> (f64, f64) {
+    let phi = heston_char_fn_complex(s, v0, kappa, theta, xi, rho, r, t);
+    move |u| {
+        let z = phi(Complex::new(u, 0.0));
+        (z.re, z.im)
+    }
+}
+
+/// Maps Heston parameters to lognormal (`beta = 1`) SABR parameters for one expiry.
+///
+/// With `sigma = sqrt(v)`, Ito's lemma turns `dv = kappa (theta - v) dt + xi sqrt(v) dW` into
+/// `d sigma = (...) dt + xi / 2 dW`, a volatility whose own lognormal volatility is
+/// `xi / (2 sigma)`. SABR has no mean reversion, so `alpha` is set to the root of the expected
+/// variance averaged over `[0, T]`, `theta + (v0 - theta) (1 - exp(-kappa T)) / (kappa T)`,
+/// which carries `kappa` and `theta` into the level of the smile, and `nu = xi / (2 alpha)`
+/// with the same correlation. The convexity of the volatility, `-xi^2 / (8 sigma)` in its
+/// drift, has no counterpart in SABR, so SABR overstates the ATM vol by an amount growing
+/// with expiry and vol of variance.
+///
+/// # Arguments
+///  - v0: initial variance
+///  - kappa: mean-reversion speed of the variance
+///  - theta: long-run variance
+///  - xi: volatility of variance
+///  - rho: correlation between spot and variance shocks
+///  - t: expiry the SABR parameters are used for, in years
+///
+/// # Returns
+/// (alpha, rho_sabr, nu) for use with `beta = 1`
+pub fn heston_to_sabr_approx(
+    v0: f64,
+    kappa: f64,
+    theta: f64,
+    xi: f64,
+    rho: f64,
+    t: f64,
+) -> (f64, f64, f64) {
+    let x = kappa * t;
+    // (1 - exp(-x)) / x -> 1 as x -> 0.
+    let weight = if x.abs() < 1e-10 {
+        1.0
+    } else {
+        -(-x).exp_m1() / x
+    };
+    let alpha = (theta + (v0 - theta) * weight).sqrt();
+    (alpha, rho, xi / (2.0 * alpha))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::fft::lewis_call_price;
+    use crate::options::{bs_char_fn, sabr_vol, try_implied_volatility};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_phi_zero_is_one() {
+        for &(kappa, theta, xi, rho, t) in &[
+            (2.0, 0.04, 0.5, -0.7, 1.0),
+            (0.5, 0.09, 1.2, 0.3, 10.0),
+            (5.0, 0.01, 0.1, -0.99, 0.1),
+        ] {
+            let (re, im) = heston_char_fn(100.0, 0.05, kappa, theta, xi, rho, 0.02, t)(0.0);
+            assert_relative_eq!(re, 1.0, epsilon = 1e-14);
+            assert_relative_eq!(im, 0.0, epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_zero_vol_of_vol_is_black_scholes() {
+        let (s, v0, r, t) = (100.0, 0.04_f64, 0.03, 2.0);
+        let bs = bs_char_fn(s, r, v0.sqrt(), t);
+        let max_error = |xi: f64| {
+            let heston = heston_char_fn(s, v0, 1.5, v0, xi, -0.5, r, t);
+            (0..40)
+                .map(|i| {
+                    let u = i as f64 * 0.25;
+                    let (h_re, h_im) = heston(u);
+                    let (b_re, b_im) = bs(u);
+                    (h_re - b_re).hypot(h_im - b_im)
+                })
+                .fold(0.0, f64::max)
+        };
+        let errors: Vec<f64> = [1e-2, 1e-3, 1e-4].iter().map(|&xi| max_error(xi)).collect();
+        assert!(errors.windows(2).all(|w| w[1] < w[0]), "{:?}", errors);
+        assert!(errors[2] < 1e-4, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_martingale_and_continuity() {
+        let (s, r, t) = (100.0, 0.03, 5.0);
+        let phi = heston_char_fn_complex(s, 0.04, 1.0, 0.06, 0.8, -0.8, r, t);
+        // E[S_T] = phi(-i) = S * exp(rT)
+        let forward = phi(-Complex::i());
+        assert_relative_eq!(forward.re, s * (r * t).exp(), max_relative = 1e-12);
+        assert_relative_eq!(forward.im, 0.0, epsilon = 1e-9);
+        // No jumps from branch cuts along the real axis.
+        let grid: Vec<Complex> = (0..2000)
+            .map(|i| phi(Complex::new(i as f64 * 0.01, 0.0)))
+            .collect();
+        assert!(grid.windows(2).all(|w| (w[1] - w[0]).norm() < 0.05));
+    }
+
+    #[test]
+    fn test_sabr_mapping() {
+        let (alpha, rho, nu) = heston_to_sabr_approx(0.04, 1.5, 0.04, 0.3, -0.6, 1.0);
+        assert_relative_eq!(alpha, 0.2);
+        assert_eq!(rho, -0.6);
+        // xi is the vol of variance; the vol of volatility is xi / (2 sigma).
+        assert_relative_eq!(nu, 0.3 / 0.4);
+        let (_, _, nu_low) = heston_to_sabr_approx(0.09, 1.5, 0.04, 0.3, -0.6, 1.0);
+        assert!(nu_low < nu);
+    }
+
+    #[test]
+    fn test_sabr_alpha_averages_expected_variance() {
+        // Short expiries see today's variance, long ones the long-run variance.
+        let alpha = |t: f64| heston_to_sabr_approx(0.09, 2.0, 0.04, 0.3, -0.6, t).0;
+        assert_relative_eq!(alpha(0.0), 0.3);
+        assert_relative_eq!(alpha(1e-6), 0.3, max_relative = 1e-6);
+        assert_relative_eq!(alpha(500.0), 0.2, max_relative = 1e-3);
+        let mut prev = alpha(0.0);
+        for &t in &[0.25, 1.0, 5.0, 20.0] {
+            assert!(alpha(t) < prev);
+            prev = alpha(t);
+        }
+        // Without mean reversion alpha is the root of today's variance at every expiry.
+        assert_relative_eq!(
+            heston_to_sabr_approx(0.09, 0.0, 0.04, 0.3, -0.6, 2.0).0,
+            0.3
+        );
+    }
+
+    #[test]
+    fn test_sabr_mapping_matches_heston_atm_vol() {
+        let (s, r) = (100.0, 0.02);
+        for &(v0, kappa, theta, xi, rho, t) in &[
+            (0.04, 1.5, 0.04, 0.3, -0.6, 0.25),
+            (0.04, 1.5, 0.04, 0.2, -0.6, 0.5),
+            (0.0625, 2.0, 0.0625, 0.3, -0.3, 0.25),
+            (0.04, 1.0, 0.05, 0.3, 0.2, 0.1),
+            (0.09, 3.0, 0.04, 0.2, -0.5, 1.0),
+            (0.02, 2.0, 0.05, 0.15, -0.4, 2.0),
+        ] {
+            let phi = heston_char_fn_complex(s, v0, kappa, theta, xi, rho, r, t);
+            let forward = s * (r * t).exp();
+            let price = lewis_call_price(phi, s, forward, t, r);
+            let heston_vol = try_implied_volatility(price, s, forward, t, r, true).unwrap();
+            let (alpha, rho_sabr, nu) = heston_to_sabr_approx(v0, kappa, theta, xi, rho, t);
+            let sabr = sabr_vol(forward, forward, t, alpha, 1.0, rho_sabr, nu);
+            assert!(
+                (heston_vol - sabr).abs() < 0.005,
+                "t={} heston={} sabr={}",
+                t,
+                heston_vol,
+                sabr
+            );
+        }
+    }
+}
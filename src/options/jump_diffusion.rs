@@ -0,0 +1,207 @@
+use crate::math::Complex;
+use crate::options::black_scholes::black_scholes;
+use crate::options::fft::lewis_call_price;
+
+/// Mean relative jump size `E[exp(Y)] - 1` of Kou's double exponential jumps.
+///
+/// Finite only if `eta1 > 1`, i.e. upward jumps average less than 100%.
+fn kou_jump_mean(p_up: f64, eta1: f64, eta2: f64) -> f64 {
+    p_up * eta1 / (eta1 - 1.0) + (1.0 - p_up) * eta2 / (eta2 + 1.0) - 1.0
+}
+
+/// Characteristic function `u -> E[exp(i u ln S_T)]` of the log-price in Kou's (2002) double
+/// exponential jump-diffusion, for complex arguments.
+///
+/// Jumps arrive at rate `lambda`; a jump in the log-price is exponential with mean `1 / eta1`
+/// upwards with probability `p_up` and exponential with mean `1 / eta2` downwards otherwise. With
+/// `zeta` the mean relative jump size, the log-price has exponent
+/// `i u (ln S + (r - sigma^2 / 2 - lambda zeta) T) - sigma^2 u^2 T / 2
+/// + lambda T (p_up eta1 / (eta1 - i u) + (1 - p_up) eta2 / (eta2 + i u) - 1)`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - r: continuously compounded risk-free rate
+///  - sigma: diffusion volatility
+///  - lambda: jump intensity per year
+///  - p_up: probability that a jump is upwards
+///  - eta1: rate of the upward jump sizes, `eta1 > 1`
+///  - eta2: rate of the downward jump sizes, `eta2 > 0`
+///  - t: time to maturity in years (T)
+#[allow(clippy::too_many_arguments)]
+pub fn kou_char_fn(
+    s: f64,
+    r: f64,
+    sigma: f64,
+    lambda: f64,
+    p_up: f64,
+    eta1: f64,
+    eta2: f64,
+    t: f64,
+) -> impl Fn(Complex) -> Complex {
+    let drift = s.ln() + (r - 0.5 * sigma * sigma - lambda * kou_jump_mean(p_up, eta1, eta2)) * t;
+    move |u| {
+        let i = Complex::i();
+        let iu = i * u;
+        let jumps = p_up * eta1 / (eta1 - iu) + (1.0 - p_up) * eta2 / (eta2 + iu) - 1.0;
+        (iu * drift - 0.5 * sigma * sigma * t * u * u + lambda * t * jumps).exp()
+    }
+}
+
+/// Prices a European option in Kou's double exponential jump-diffusion model.
+///
+/// Calls are priced with Lewis's (2001) single Fourier integral over [`kou_char_fn`] and puts
+/// follow from put-call parity. Without jumps the model is Black-Scholes and its closed form is
+/// used.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: diffusion volatility
+///  - lambda: jump intensity per year
+///  - p_up: probability that a jump is upwards
+///  - eta1: rate of the upward jump sizes, `eta1 > 1`
+///  - eta2: rate of the downward jump sizes, `eta2 > 0`
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// (price) of the option, NaN if the jump parameters admit no finite forward
+#[allow(clippy::too_many_arguments)]
+pub fn kou_price(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    lambda: f64,
+    p_up: f64,
+    eta1: f64,
+    eta2: f64,
+    is_call: bool,
+) -> f64 {
+    if t <= 0.0 || lambda == 0.0 {
+        let (call, put) = black_scholes(s, k, t, r, sigma);
+        return if is_call { call } else { put };
+    }
+    if eta1 <= 1.0 || eta2 <= 0.0 || !(0.0..=1.0).contains(&p_up) || lambda < 0.0 {
+        return f64::NAN;
+    }
+    let phi = kou_char_fn(s, r, sigma, lambda, p_up, eta1, eta2, t);
+    let call = lewis_call_price(phi, s, k, t, r);
+    if is_call {
+        call
+    } else {
+        call - s + k * (-r * t).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::monte_carlo::mean_std_err;
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rand_distr::{Distribution, Exp, Poisson, StandardNormal};
+
+    #[test]
+    fn test_no_jumps_is_black_scholes() {
+        let (s, t, r, sigma) = (100.0, 0.8, 0.03, 0.25);
+        for &k in &[80.0, 100.0, 125.0] {
+            let (call, put) = black_scholes(s, k, t, r, sigma);
+            assert_eq!(
+                kou_price(s, k, t, r, sigma, 0.0, 0.4, 10.0, 5.0, true),
+                call
+            );
+            // The Fourier route agrees with the closed form for vanishing intensity.
+            let phi = kou_char_fn(s, r, sigma, 0.0, 0.4, 10.0, 5.0, t);
+            assert_relative_eq!(lewis_call_price(phi, s, k, t, r), call, epsilon = 1e-8);
+            assert_eq!(
+                kou_price(s, k, t, r, sigma, 0.0, 0.4, 10.0, 5.0, false),
+                put
+            );
+        }
+    }
+
+    #[test]
+    fn test_char_fn_martingale() {
+        let phi = kou_char_fn(100.0, 0.04, 0.2, 1.5, 0.3, 8.0, 4.0, 2.0);
+        let at_zero = phi(Complex::new(0.0, 0.0));
+        assert_relative_eq!(at_zero.re, 1.0, epsilon = 1e-15);
+        let forward = phi(Complex::new(0.0, -1.0));
+        assert_relative_eq!(forward.re, 100.0 * 0.08_f64.exp(), max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_upward_jumps_raise_otm_calls() {
+        let mut prev = 0.0;
+        for &p_up in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+            let call = kou_price(100.0, 120.0, 1.0, 0.03, 0.15, 2.0, p_up, 6.0, 6.0, true);
+            assert!(call > prev, "p_up={} call={}", p_up, call);
+            prev = call;
+        }
+    }
+
+    #[test]
+    fn test_heavy_up_tail_gives_nan() {
+        // Upward jumps with eta1 <= 1 have no finite mean, so there is no forward.
+        assert!(kou_price(100.0, 100.0, 1.5, 0.05, 0.2, 1.0, 0.3, 0.8, 6.0, true).is_nan());
+        assert!(kou_price(100.0, 100.0, 1.5, 0.05, 0.2, 1.0, 0.3, 0.8, 6.0, false).is_nan());
+    }
+
+    #[test]
+    fn test_matches_monte_carlo() {
+        let (s, t, r, sigma) = (100.0, 1.0, 0.03, 0.2);
+        let (lambda, p_up, eta1, eta2): (f64, f64, f64, f64) = (3.0, 0.35, 12.0, 6.0);
+        let drift = (r - 0.5 * sigma * sigma - lambda * kou_jump_mean(p_up, eta1, eta2)) * t;
+        let count = Poisson::new(lambda * t).unwrap();
+        let (up, down) = (Exp::new(eta1).unwrap(), Exp::new(eta2).unwrap());
+        let mut rng = StdRng::seed_from_u64(7);
+        let terminal: Vec<f64> = (0..100_000)
+            .map(|_| {
+                let n: f64 = count.sample(&mut rng);
+                let jumps: f64 = (0..n as usize)
+                    .map(|_| {
+                        if rng.gen::<f64>() < p_up {
+                            up.sample(&mut rng)
+                        } else {
+                            -down.sample(&mut rng)
+                        }
+                    })
+                    .sum();
+                let z: f64 = StandardNormal.sample(&mut rng);
+                s * (drift + sigma * t.sqrt() * z + jumps).exp()
+            })
+            .collect();
+        let df = (-r * t).exp();
+        for &(k, is_call) in &[
+            (85.0, false),
+            (100.0, true),
+            (100.0, false),
+            (120.0, true),
+            (120.0, false),
+        ] {
+            let payoffs: Vec<f64> = terminal
+                .iter()
+                .map(|&st| {
+                    df * if is_call {
+                        (st - k).max(0.0)
+                    } else {
+                        (k - st).max(0.0)
+                    }
+                })
+                .collect();
+            let (mc, se) = mean_std_err(&payoffs);
+            let price = kou_price(s, k, t, r, sigma, lambda, p_up, eta1, eta2, is_call);
+            assert!(
+                (price - mc).abs() < 2.0 * se,
+                "k={} price={} mc={} se={}",
+                k,
+                price,
+                mc,
+                se
+            );
+        }
+    }
+}
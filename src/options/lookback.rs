@@ -0,0 +1,207 @@
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Floating strike lookback option at inception (Goldman, Sosin and Gatto, 1979).
+///
+/// The call pays `S_T - min S` and the put `max S - S_T` over the life of the option, with the
+/// extremum monitored continuously from today. This is
+/// [`lookback_floating_partial`] with the running extremum at spot and no time elapsed.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - is_call: true for call option, false for put option
+pub fn lookback_floating(s: f64, t: f64, r: f64, sigma: f64, is_call: bool) -> f64 {
+    lookback_floating_partial(s, s, 0.0, t, r, sigma, is_call)
+}
+
+/// Floating strike lookback option part way through its life (Conze and Viswanathan, 1991).
+///
+/// The extremum already observed, `s_extreme`, is the running minimum for a call and the
+/// running maximum for a put. With `tau = t_total - t_elapsed`,
+/// `a1 = (ln(S / m) + (r + sigma^2 / 2) tau) / (sigma sqrt(tau))` and `a2 = a1 - sigma sqrt(tau)`,
+///
+/// `call = S N(a1) - m e^{-r tau} N(a2) + S e^{-r tau} sigma^2 / (2r)
+/// ((S / m)^(-2r / sigma^2) N(-a1 + 2r sqrt(tau) / sigma) - e^{r tau} N(-a1))`
+///
+/// and symmetrically for the put with the running maximum `M`. The formula has a removable
+/// singularity at `r = 0`, so rates closer to zero than `1e-8` are evaluated at `1e-8`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - s_extreme: running minimum (call) or maximum (put) observed so far
+///  - t_elapsed: time already elapsed since inception in years
+///  - t_total: total life of the option in years
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - is_call: true for call option, false for put option
+pub fn lookback_floating_partial(
+    s: f64,
+    s_extreme: f64,
+    t_elapsed: f64,
+    t_total: f64,
+    r: f64,
+    sigma: f64,
+    is_call: bool,
+) -> f64 {
+    let tau = t_total - t_elapsed;
+    let extreme = if is_call {
+        s_extreme.min(s)
+    } else {
+        s_extreme.max(s)
+    };
+    if tau <= 0.0 || sigma <= 0.0 {
+        return if is_call { s - extreme } else { extreme - s };
+    }
+    let r = if r.abs() < 1e-8 { 1e-8 } else { r };
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let sqrt_tau = tau.sqrt();
+    let a1 = ((s / extreme).ln() + (r + 0.5 * sigma * sigma) * tau) / (sigma * sqrt_tau);
+    let a2 = a1 - sigma * sqrt_tau;
+    let df = (-r * tau).exp();
+    let reflection = (s / extreme).powf(-2.0 * r / (sigma * sigma));
+    let shift = 2.0 * r * sqrt_tau / sigma;
+    let scale = s * df * sigma * sigma / (2.0 * r);
+    if is_call {
+        s * stdn.cdf(a1) - extreme * df * stdn.cdf(a2)
+            + scale * (reflection * stdn.cdf(-a1 + shift) - stdn.cdf(-a1) / df)
+    } else {
+        extreme * df * stdn.cdf(-a2) - s * stdn.cdf(-a1)
+            + scale * (stdn.cdf(a1) / df - reflection * stdn.cdf(a1 - shift))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+    use crate::options::monte_carlo::mean_std_err;
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+    use statrs::distribution::Continuous;
+
+    #[test]
+    fn test_inception_matches_lookback_floating() {
+        for &is_call in &[true, false] {
+            assert_eq!(
+                lookback_floating_partial(100.0, 100.0, 0.0, 1.0, 0.05, 0.3, is_call),
+                lookback_floating(100.0, 1.0, 0.05, 0.3, is_call)
+            );
+        }
+        // Only the remaining life matters.
+        assert_eq!(
+            lookback_floating_partial(100.0, 100.0, 0.25, 1.0, 0.05, 0.3, true),
+            lookback_floating(100.0, 0.75, 0.05, 0.3, true)
+        );
+    }
+
+    #[test]
+    fn test_expiry_is_intrinsic() {
+        assert_eq!(
+            lookback_floating_partial(110.0, 90.0, 1.0, 1.0, 0.05, 0.3, true),
+            20.0
+        );
+        assert_eq!(
+            lookback_floating_partial(110.0, 130.0, 1.0, 1.0, 0.05, 0.3, false),
+            20.0
+        );
+    }
+
+    #[test]
+    fn test_dominates_vanilla() {
+        for &(s, extreme, is_call) in &[
+            (100.0, 90.0, true),
+            (100.0, 100.0, true),
+            (100.0, 115.0, false),
+            (100.0, 100.0, false),
+        ] {
+            for &(t_elapsed, r) in &[(0.0, 0.05), (0.5, 0.0), (0.9, 0.1)] {
+                let lookback =
+                    lookback_floating_partial(s, extreme, t_elapsed, 1.0, r, 0.25, is_call);
+                let (call, put) = black_scholes(s, extreme, 1.0 - t_elapsed, r, 0.25);
+                let vanilla = if is_call { call } else { put };
+                assert!(
+                    lookback >= vanilla,
+                    "lookback={} vanilla={}",
+                    lookback,
+                    vanilla
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_monte_carlo() {
+        // Continuous extrema sampled exactly from the Brownian bridge between grid points.
+        let (s, t, r, sigma) = (100.0, 0.5, 0.04, 0.3);
+        let (steps, paths) = (50, 40_000);
+        let dt = t / steps as f64;
+        let drift = (r - 0.5 * sigma * sigma) * dt;
+        let mut rng = StdRng::seed_from_u64(3);
+        let (mut calls, mut puts) = (Vec::new(), Vec::new());
+        for _ in 0..paths {
+            let (mut x, mut lo, mut hi) = (0.0_f64, 0.0_f64, 0.0_f64);
+            for _ in 0..steps {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                let next = x + drift + sigma * dt.sqrt() * z;
+                let spread =
+                    |u: f64| ((next - x).powi(2) - 2.0 * sigma * sigma * dt * u.ln()).sqrt();
+                lo = lo.min(0.5 * (x + next - spread(rng.gen::<f64>())));
+                hi = hi.max(0.5 * (x + next + spread(rng.gen::<f64>())));
+                x = next;
+            }
+            let df = (-r * t).exp();
+            // Running minimum of 95 and maximum of 104 already observed.
+            calls.push(df * s * (x.exp() - lo.exp().min(0.95)));
+            puts.push(df * s * (hi.exp().max(1.04) - x.exp()));
+        }
+        for (samples, extreme, is_call) in [(calls, 95.0, true), (puts, 104.0, false)] {
+            let (mc, se) = mean_std_err(&samples);
+            let price = lookback_floating_partial(s, extreme, 0.5, 1.0, r, sigma, is_call);
+            assert!(
+                (price - mc).abs() < 3.0 * se,
+                "price={} mc={} se={}",
+                price,
+                mc,
+                se
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_rate_limit() {
+        // Expanding the reflection term to first order in r gives, at r = 0,
+        // call = S N(a1) - m N(a2) + S sigma sqrt(tau) (n(a1) - a1 N(-a1)) and
+        // put = M N(-a2) - S N(-a1) + S sigma sqrt(tau) (n(a1) + a1 N(a1)).
+        let stdn = Normal::new(0.0, 1.0).unwrap();
+        let (s, sigma) = (100.0_f64, 0.3);
+        for &(extreme, tau, is_call) in &[
+            (100.0, 1.0, true),
+            (90.0, 0.5, true),
+            (100.0, 2.0, false),
+            (115.0, 0.25, false),
+        ] {
+            let sd = sigma * f64::sqrt(tau);
+            let a1 = ((s / extreme).ln() + 0.5 * sd * sd) / sd;
+            let a2 = a1 - sd;
+            let expected = if is_call {
+                s * stdn.cdf(a1) - extreme * stdn.cdf(a2)
+                    + s * sd * (stdn.pdf(a1) - a1 * stdn.cdf(-a1))
+            } else {
+                extreme * stdn.cdf(-a2) - s * stdn.cdf(-a1)
+                    + s * sd * (stdn.pdf(a1) + a1 * stdn.cdf(a1))
+            };
+            for &r in &[0.0, 1e-12, -1e-10] {
+                let price = lookback_floating_partial(s, extreme, 0.0, tau, r, sigma, is_call);
+                assert_relative_eq!(price, expected, max_relative = 1e-6);
+            }
+            // Small rates on either side approach the same value.
+            let up = lookback_floating_partial(s, extreme, 0.0, tau, 1e-4, sigma, is_call);
+            let down = lookback_floating_partial(s, extreme, 0.0, tau, -1e-4, sigma, is_call);
+            assert_relative_eq!(0.5 * (up + down), expected, max_relative = 1e-6);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::math::{linear_regression, predict};
+use crate::options::lsm_basis::BasisFunction;
+use crate::options::monte_carlo::mean_std_err;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// Prices an American option with the Longstaff-Schwartz least-squares Monte Carlo method.
+///
+/// Simulates GBM paths, then works backwards through the exercise dates regressing the
+/// discounted realised cash flows of in-the-money paths on `basis` evaluated at `S / K`. A path
+/// is exercised when intrinsic value exceeds the fitted continuation value.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility (annualized)
+///  - n_steps: number of exercise dates, at least 1 (0 is treated as 1)
+///  - n_paths: number of simulated paths
+///  - seed: random seed
+///  - is_call: true for call option, false for put option
+///  - basis: regression basis for the continuation value
+///
+/// # Returns
+/// (estimate, std_err)
+#[allow(clippy::too_many_arguments)]
+pub fn american_lsm(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    n_steps: usize,
+    n_paths: usize,
+    seed: u64,
+    is_call: bool,
+    basis: &dyn BasisFunction,
+) -> (f64, f64) {
+    let n_steps = n_steps.max(1);
+    let dt = t / n_steps as f64;
+    let disc = (-r * dt).exp();
+    let drift = (r - 0.5 * sigma * sigma) * dt;
+    let vol = sigma * dt.sqrt();
+    let payoff = |spot: f64| {
+        if is_call {
+            (spot - k).max(0.0)
+        } else {
+            (k - spot).max(0.0)
+        }
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    // paths[i][j] is the spot of path i at exercise date j + 1.
+    let paths: Vec<Vec<f64>> = (0..n_paths)
+        .map(|_| {
+            let mut spot = s;
+            (0..n_steps)
+                .map(|_| {
+                    let z: f64 = StandardNormal.sample(&mut rng);
+                    spot *= (drift + vol * z).exp();
+                    spot
+                })
+                .collect()
+        })
+        .collect();
+
+    // Cash flow of each path, valued at the current exercise date.
+    let mut cash_flows: Vec<f64> = paths.iter().map(|p| payoff(p[n_steps - 1])).collect();
+    for step in (0..n_steps - 1).rev() {
+        for cf in cash_flows.iter_mut() {
+            *cf *= disc;
+        }
+        let itm: Vec<usize> = (0..n_paths)
+            .filter(|&i| payoff(paths[i][step]) > 0.0)
+            .collect();
+        let x: Vec<Vec<f64>> = itm
+            .iter()
+            .map(|&i| basis.evaluate(paths[i][step] / k))
+            .collect();
+        let y: Vec<f64> = itm.iter().map(|&i| cash_flows[i]).collect();
+        let Ok(beta) = linear_regression(&x, &y) else {
+            continue;
+        };
+        for (&i, row) in itm.iter().zip(&x) {
+            let continuation = predict(&beta, row);
+            let exercise = payoff(paths[i][step]);
+            if exercise > continuation {
+                cash_flows[i] = exercise;
+            }
+        }
+    }
+    let discounted: Vec<f64> = cash_flows.iter().map(|cf| disc * cf).collect();
+    mean_std_err(&discounted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::binomial::binomial_crr;
+    use crate::options::lsm_basis::{LaguerreBasis, MonomialBasis};
+
+    #[test]
+    fn test_lsm_american_put_matches_binomial() {
+        let (s, k, t, r, sigma) = (36.0, 40.0, 1.0, 0.06, 0.2);
+        let crr = binomial_crr(s, k, t, r, sigma, 1000, false, true);
+        let (price, se) = american_lsm(
+            s,
+            k,
+            t,
+            r,
+            sigma,
+            50,
+            20_000,
+            1,
+            false,
+            &LaguerreBasis { degree: 3 },
+        );
+        assert!(
+            (price - crr).abs() < 3.0 * se + 0.02,
+            "lsm={} crr={} se={}",
+            price,
+            crr,
+            se
+        );
+    }
+
+    #[test]
+    fn test_richer_basis_closer_to_binomial() {
+        let (s, k, t, r, sigma) = (36.0, 40.0, 1.0, 0.06, 0.2);
+        let crr = binomial_crr(s, k, t, r, sigma, 1000, false, true);
+        let (laguerre, _) = american_lsm(
+            s,
+            k,
+            t,
+            r,
+            sigma,
+            50,
+            20_000,
+            2,
+            false,
+            &LaguerreBasis { degree: 4 },
+        );
+        let (monomial, _) = american_lsm(
+            s,
+            k,
+            t,
+            r,
+            sigma,
+            50,
+            20_000,
+            2,
+            false,
+            &MonomialBasis { degree: 2 },
+        );
+        assert!(
+            (laguerre - crr).abs() < (monomial - crr).abs(),
+            "laguerre={} monomial={} crr={}",
+            laguerre,
+            monomial,
+            crr
+        );
+    }
+
+    #[test]
+    fn test_single_exercise_date_is_european() {
+        let (s, k, t, r, sigma) = (36.0, 40.0, 1.0, 0.06, 0.2);
+        let basis = LaguerreBasis { degree: 3 };
+        let (_, put) = crate::options::black_scholes(s, k, t, r, sigma);
+        let (price, se) = american_lsm(s, k, t, r, sigma, 1, 20_000, 3, false, &basis);
+        assert!((price - put).abs() < 3.0 * se, "lsm={} bs={}", price, put);
+        // No exercise dates at all is clamped to exercising at expiry only.
+        let (clamped, _) = american_lsm(s, k, t, r, sigma, 0, 20_000, 3, false, &basis);
+        assert_eq!(clamped, price);
+    }
+}
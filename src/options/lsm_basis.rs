@@ -0,0 +1,146 @@
+/// Basis functions for the continuation value regression in Longstaff-Schwartz.
+pub trait BasisFunction {
+    /// Evaluates every basis function at `x`, including the constant term.
+    fn evaluate(&self, x: f64) -> Vec<f64>;
+}
+
+/// Laguerre polynomials `L_0, ..., L_degree`, orthonormal on `[0, inf)` under the weight `exp(-x)`.
+///
+/// Generated by the recurrence `(n + 1) L_{n+1} = (2n + 1 - x) L_n - n L_{n-1}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaguerreBasis {
+    pub degree: usize,
+}
+
+impl BasisFunction for LaguerreBasis {
+    fn evaluate(&self, x: f64) -> Vec<f64> {
+        let mut values = Vec::with_capacity(self.degree + 1);
+        values.push(1.0);
+        if self.degree >= 1 {
+            values.push(1.0 - x);
+        }
+        for n in 1..self.degree {
+            let nf = n as f64;
+            let next = ((2.0 * nf + 1.0 - x) * values[n] - nf * values[n - 1]) / (nf + 1.0);
+            values.push(next);
+        }
+        values
+    }
+}
+
+/// Probabilists' Hermite polynomials `He_0, ..., He_degree`, orthogonal under the standard
+/// normal density.
+///
+/// Generated by the recurrence `He_{n+1} = x He_n - n He_{n-1}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HermiteBasis {
+    pub degree: usize,
+}
+
+impl BasisFunction for HermiteBasis {
+    fn evaluate(&self, x: f64) -> Vec<f64> {
+        let mut values = Vec::with_capacity(self.degree + 1);
+        values.push(1.0);
+        if self.degree >= 1 {
+            values.push(x);
+        }
+        for n in 1..self.degree {
+            let next = x * values[n] - n as f64 * values[n - 1];
+            values.push(next);
+        }
+        values
+    }
+}
+
+/// Monomials `1, x, ..., x^degree`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonomialBasis {
+    pub degree: usize,
+}
+
+impl BasisFunction for MonomialBasis {
+    fn evaluate(&self, x: f64) -> Vec<f64> {
+        (0..=self.degree).map(|i| x.powi(i as i32)).collect()
+    }
+}
+
+/// Gaussian radial basis functions `exp(-((x - c) / width)^2)` around each center, plus a
+/// constant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialBasis {
+    pub centers: Vec<f64>,
+    pub width: f64,
+}
+
+impl BasisFunction for RadialBasis {
+    fn evaluate(&self, x: f64) -> Vec<f64> {
+        std::iter::once(1.0)
+            .chain(
+                self.centers
+                    .iter()
+                    .map(|c| (-((x - c) / self.width).powi(2)).exp()),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_laguerre_values() {
+        let v = LaguerreBasis { degree: 3 }.evaluate(2.0);
+        // L2(x) = (x^2 - 4x + 2) / 2, L3(x) = (-x^3 + 9x^2 - 18x + 6) / 6
+        assert_relative_eq!(v[1], -1.0);
+        assert_relative_eq!(v[2], -1.0);
+        assert_relative_eq!(v[3], -1.0 / 3.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_laguerre_orthonormal() {
+        let basis = LaguerreBasis { degree: 4 };
+        let (upper, n) = (80.0, 200_000);
+        let h = upper / n as f64;
+        let mut gram = [[0.0; 5]; 5];
+        for i in 0..=n {
+            let x = i as f64 * h;
+            let w = if i == 0 || i == n { 0.5 * h } else { h } * (-x).exp();
+            let v = basis.evaluate(x);
+            for a in 0..5 {
+                for b in 0..5 {
+                    gram[a][b] += w * v[a] * v[b];
+                }
+            }
+        }
+        for (a, row) in gram.iter().enumerate() {
+            for (b, &g) in row.iter().enumerate() {
+                let expected = if a == b { 1.0 } else { 0.0 };
+                assert_relative_eq!(g, expected, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hermite_and_monomial_values() {
+        let he = HermiteBasis { degree: 3 }.evaluate(2.0);
+        assert_eq!(he, vec![1.0, 2.0, 3.0, 2.0]);
+        assert_eq!(
+            MonomialBasis { degree: 3 }.evaluate(2.0),
+            vec![1.0, 2.0, 4.0, 8.0]
+        );
+    }
+
+    #[test]
+    fn test_radial_basis() {
+        let rbf = RadialBasis {
+            centers: vec![0.0, 1.0],
+            width: 0.5,
+        };
+        let v = rbf.evaluate(1.0);
+        assert_eq!(v.len(), 3);
+        assert_relative_eq!(v[1], (-4.0_f64).exp());
+        assert_relative_eq!(v[2], 1.0);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::math::bivariate_normal_cdf;
+use crate::optimization::{auto_step, greek_fd_richardson};
+use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+
+/// Volatility of the ratio of two lognormal assets.
+fn spread_vol(sigma1: f64, sigma2: f64, rho: f64) -> f64 {
+    (sigma1 * sigma1 + sigma2 * sigma2 - 2.0 * rho * sigma1 * sigma2).sqrt()
+}
+
+/// Prices an option to exchange asset 2 for asset 1, paying `max(S1 - S2, 0)` at expiry
+/// (Margrabe, 1978).
+///
+/// # Arguments
+///  - s1: spot price of the asset received
+///  - s2: spot price of the asset delivered
+///  - t: time to expiration in years
+///  - r: continuously compounded risk-free rate
+///  - sigma1: volatility of asset 1
+///  - sigma2: volatility of asset 2
+///  - rho: correlation between the two assets' returns
+///
+/// # Returns
+/// (price) of the exchange option; it does not depend on `r`, since both legs are traded assets
+pub fn exchange_option(
+    s1: f64,
+    s2: f64,
+    t: f64,
+    r: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+) -> f64 {
+    let sigma = spread_vol(sigma1, sigma2, rho);
+    let (f1, f2) = (s1 * (r * t).exp(), s2 * (r * t).exp());
+    let d1 = ((f1 / f2).ln() + 0.5 * sigma * sigma * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    (-r * t).exp() * (f1 * stdn.cdf(d1) - f2 * stdn.cdf(d2))
+}
+
+/// Sensitivity of the [`exchange_option`] price to the correlation `rho`.
+///
+/// The price depends on `rho` only through the ratio volatility `sigma`, so this is the vega
+/// with respect to `sigma`, `S1 * n(d1) * sqrt(T)`, times
+/// `d(sigma)/d(rho) = -sigma1 * sigma2 / sigma`.
+///
+/// # Returns
+/// `d(Price)/d(rho)`, which is never positive
+pub fn exchange_option_rho_corr(
+    s1: f64,
+    s2: f64,
+    t: f64,
+    r: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+) -> f64 {
+    let sigma = spread_vol(sigma1, sigma2, rho);
+    let (f1, f2) = (s1 * (r * t).exp(), s2 * (r * t).exp());
+    let d1 = ((f1 / f2).ln() + 0.5 * sigma * sigma * t) / (sigma * t.sqrt());
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let vega = (-r * t).exp() * f1 * stdn.pdf(d1) * t.sqrt();
+    -vega * sigma1 * sigma2 / sigma
+}
+
+/// Effective volatility and `d(sigma)/d(rho)` of Kirk's approximation.
+fn kirk_vol(f2: f64, k: f64, sigma1: f64, sigma2: f64, rho: f64) -> (f64, f64) {
+    let b = f2 / (f2 + k);
+    let sigma =
+        (sigma1 * sigma1 - 2.0 * rho * sigma1 * sigma2 * b + sigma2 * sigma2 * b * b).sqrt();
+    (sigma, -sigma1 * sigma2 * b / sigma)
+}
+
+/// Prices a spread option paying `max(S1 - S2 - K, 0)` (call) or `max(K - S1 + S2, 0)` (put)
+/// with Kirk's (1995) approximation.
+///
+/// `S2 + K` is treated as a single lognormal asset, which makes the option an exchange option.
+///
+/// # Arguments
+///  - s1: spot price of the long asset
+///  - s2: spot price of the short asset
+///  - k: strike of the spread
+///  - t: time to expiration in years
+///  - r: continuously compounded risk-free rate
+///  - sigma1: volatility of asset 1
+///  - sigma2: volatility of asset 2
+///  - rho: correlation between the two assets' returns
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// (price) of the spread option
+#[allow(clippy::too_many_arguments)]
+pub fn kirk_spread_option(
+    s1: f64,
+    s2: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    is_call: bool,
+) -> f64 {
+    let (f1, f2) = (s1 * (r * t).exp(), s2 * (r * t).exp());
+    let (sigma, _) = kirk_vol(f2, k, sigma1, sigma2, rho);
+    let d1 = ((f1 / (f2 + k)).ln() + 0.5 * sigma * sigma * t) / (sigma * t.sqrt());
+    let d2 = d1 - sigma * t.sqrt();
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let df = (-r * t).exp();
+    if is_call {
+        df * (f1 * stdn.cdf(d1) - (f2 + k) * stdn.cdf(d2))
+    } else {
+        df * ((f2 + k) * stdn.cdf(-d2) - f1 * stdn.cdf(-d1))
+    }
+}
+
+/// Sensitivity of the [`kirk_spread_option`] price to the correlation `rho`.
+///
+/// Calls and puts share the same value, Black's vega in the effective volatility times
+/// `d(sigma)/d(rho)`.
+///
+/// # Returns
+/// `d(Price)/d(rho)`
+#[allow(clippy::too_many_arguments)]
+pub fn kirk_spread_rho_corr(
+    s1: f64,
+    s2: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+) -> f64 {
+    let (f1, f2) = (s1 * (r * t).exp(), s2 * (r * t).exp());
+    let (sigma, dsigma_drho) = kirk_vol(f2, k, sigma1, sigma2, rho);
+    let d1 = ((f1 / (f2 + k)).ln() + 0.5 * sigma * sigma * t) / (sigma * t.sqrt());
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let vega = (-r * t).exp() * f1 * stdn.pdf(d1) * t.sqrt();
+    vega * dsigma_drho
+}
+
+/// Prices a call on the maximum of two assets, paying `max(max(S1, S2) - K, 0)` (Stulz, 1982).
+///
+/// # Arguments
+///  - s1: spot price of asset 1
+///  - s2: spot price of asset 2
+///  - k: strike (K)
+///  - t: time to expiration in years
+///  - r: continuously compounded risk-free rate
+///  - sigma1: volatility of asset 1
+///  - sigma2: volatility of asset 2
+///  - rho: correlation between the two assets' returns
+///
+/// # Returns
+/// (price) of the call on the maximum
+#[allow(clippy::too_many_arguments)]
+pub fn call_on_max(
+    s1: f64,
+    s2: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+) -> f64 {
+    let sqrt_t = t.sqrt();
+    let sigma = spread_vol(sigma1, sigma2, rho);
+    let d = ((s1 / s2).ln() + 0.5 * sigma * sigma * t) / (sigma * sqrt_t);
+    let y1 = ((s1 / k).ln() + (r + 0.5 * sigma1 * sigma1) * t) / (sigma1 * sqrt_t);
+    let y2 = ((s2 / k).ln() + (r + 0.5 * sigma2 * sigma2) * t) / (sigma2 * sqrt_t);
+    let rho1 = (sigma1 - rho * sigma2) / sigma;
+    let rho2 = (sigma2 - rho * sigma1) / sigma;
+    s1 * bivariate_normal_cdf(y1, d, rho1) + s2 * bivariate_normal_cdf(y2, sigma * sqrt_t - d, rho2)
+        - k * (-r * t).exp()
+            * (1.0 - bivariate_normal_cdf(sigma1 * sqrt_t - y1, sigma2 * sqrt_t - y2, rho))
+}
+
+/// Sensitivity of the [`call_on_max`] price to the correlation `rho`, by Richardson-extrapolated
+/// central differences.
+///
+/// The step is kept small enough that every bumped correlation stays inside `(-1, 1)`.
+///
+/// # Returns
+/// `d(Price)/d(rho)`
+#[allow(clippy::too_many_arguments)]
+pub fn call_on_max_rho_corr(
+    s1: f64,
+    s2: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+) -> f64 {
+    let h = auto_step(rho).min(0.25 * (1.0 - rho.abs()));
+    greek_fd_richardson(|x| call_on_max(s1, s2, k, t, r, sigma1, sigma2, x), rho, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const EPS: f64 = 1e-5;
+
+    #[test]
+    fn test_exchange_rho_corr_matches_finite_difference() {
+        for &(s1, s2, t, sigma1, sigma2, rho) in &[
+            (100.0, 95.0, 1.0, 0.2, 0.3, 0.5),
+            (80.0, 100.0, 0.5, 0.4, 0.25, -0.3),
+            (100.0, 100.0, 2.0, 0.15, 0.15, 0.9),
+        ] {
+            let price = |x: f64| exchange_option(s1, s2, t, 0.03, sigma1, sigma2, x);
+            let fd = (price(rho + EPS) - price(rho - EPS)) / (2.0 * EPS);
+            let analytic = exchange_option_rho_corr(s1, s2, t, 0.03, sigma1, sigma2, rho);
+            assert_relative_eq!(analytic, fd, epsilon = 1e-6);
+            assert!(analytic < 0.0);
+        }
+    }
+
+    #[test]
+    fn test_kirk_rho_corr_matches_finite_difference() {
+        for &(k, rho) in &[(5.0, 0.6), (0.0, -0.2), (15.0, 0.95)] {
+            for &is_call in &[true, false] {
+                let price =
+                    |x: f64| kirk_spread_option(110.0, 100.0, k, 0.75, 0.04, 0.3, 0.25, x, is_call);
+                let fd = (price(rho + EPS) - price(rho - EPS)) / (2.0 * EPS);
+                let analytic = kirk_spread_rho_corr(110.0, 100.0, k, 0.75, 0.04, 0.3, 0.25, rho);
+                assert_relative_eq!(analytic, fd, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kirk_zero_strike_is_exchange_option() {
+        let kirk = kirk_spread_option(105.0, 100.0, 0.0, 1.0, 0.05, 0.2, 0.3, 0.4, true);
+        let margrabe = exchange_option(105.0, 100.0, 1.0, 0.05, 0.2, 0.3, 0.4);
+        assert_relative_eq!(kirk, margrabe, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_call_on_max_zero_strike() {
+        // max(S1, S2) = S2 + max(S1 - S2, 0)
+        let max = call_on_max(100.0, 90.0, 1e-12, 1.0, 0.05, 0.25, 0.35, 0.3);
+        let exchange = exchange_option(100.0, 90.0, 1.0, 0.05, 0.25, 0.35, 0.3);
+        assert_relative_eq!(max, 90.0 + exchange, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_call_on_max_rho_corr_negative() {
+        for &rho in &[-0.9, -0.5, 0.0, 0.5, 0.9] {
+            for &k in &[80.0, 100.0, 120.0] {
+                for &(s1, s2) in &[(100.0, 100.0), (110.0, 95.0)] {
+                    let greek = call_on_max_rho_corr(s1, s2, k, 1.0, 0.05, 0.2, 0.3, rho);
+                    assert!(greek < 0.0, "rho={} k={} greek={}", rho, k, greek);
+                    let price = |x: f64| call_on_max(s1, s2, k, 1.0, 0.05, 0.2, 0.3, x);
+                    let fd = (price(rho + EPS) - price(rho - EPS)) / (2.0 * EPS);
+                    assert_relative_eq!(greek, fd, epsilon = 1e-6);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::math::Complex;
+use crate::options::fft::lewis_call_price;
+
+/// Martingale correction `omega` making `exp(X_t + omega t)` a martingale for NIG increments,
+/// `omega = -mu - delta * (sqrt(alpha^2 - beta^2) - sqrt(alpha^2 - (beta + 1)^2))`.
+///
+/// NaN unless `alpha > |beta|` and `alpha > |beta + 1|`.
+fn nig_omega(alpha: f64, beta: f64, delta: f64, mu: f64) -> f64 {
+    -mu - delta
+        * ((alpha * alpha - beta * beta).sqrt() - (alpha * alpha - (beta + 1.0).powi(2)).sqrt())
+}
+
+/// Characteristic function `u -> E[exp(i u ln S_T)]` of the log-price in the normal inverse
+/// Gaussian model of Barndorff-Nielsen (1997), for complex arguments.
+///
+/// The log-price is `ln S + (r + omega) T + X_T`, where `X_T` is NIG with parameters
+/// `(alpha, beta, delta T, mu T)` and `omega` the martingale correction, so that
+/// `phi(u) = exp(i u (ln S + (r + omega + mu) T) + delta T (sqrt(alpha^2 - beta^2)
+/// - sqrt(alpha^2 - (beta + i u)^2)))`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - r: continuously compounded risk-free rate
+///  - alpha: tail heaviness, `alpha > |beta|`
+///  - beta: asymmetry, negative for a left skew
+///  - delta: scale
+///  - mu: location
+///  - t: time to maturity in years (T)
+pub fn nig_char_fn(
+    s: f64,
+    r: f64,
+    alpha: f64,
+    beta: f64,
+    delta: f64,
+    mu: f64,
+    t: f64,
+) -> impl Fn(Complex) -> Complex {
+    let drift = s.ln() + (r + nig_omega(alpha, beta, delta, mu) + mu) * t;
+    let gamma = (alpha * alpha - beta * beta).sqrt();
+    move |u| {
+        let i = Complex::i();
+        let shifted = beta + i * u;
+        let root = (alpha * alpha - shifted * shifted).sqrt();
+        (i * u * drift + delta * t * (gamma - root)).exp()
+    }
+}
+
+/// Prices a European option in the normal inverse Gaussian (NIG) model.
+///
+/// Calls are priced with Lewis's (2001) single Fourier integral over [`nig_char_fn`] and puts
+/// follow from put-call parity. Under the risk-neutral measure the location `mu` is absorbed
+/// by the martingale correction, so prices do not depend on it.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - alpha: tail heaviness, `alpha > |beta|` and `alpha > |beta + 1|`
+///  - beta: asymmetry, negative for a left skew
+///  - delta: scale
+///  - mu: location
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// (price) of the option, NaN if the parameters admit no finite forward
+#[allow(clippy::too_many_arguments)]
+pub fn nig_price(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    alpha: f64,
+    beta: f64,
+    delta: f64,
+    mu: f64,
+    is_call: bool,
+) -> f64 {
+    if t <= 0.0 {
+        return if is_call {
+            (s - k).max(0.0)
+        } else {
+            (k - s).max(0.0)
+        };
+    }
+    if nig_omega(alpha, beta, delta, mu).is_nan() || delta <= 0.0 {
+        return f64::NAN;
+    }
+    let call = lewis_call_price(nig_char_fn(s, r, alpha, beta, delta, mu, t), s, k, t, r);
+    if is_call {
+        call
+    } else {
+        call - s + k * (-r * t).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::monte_carlo::mean_std_err;
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, InverseGaussian, StandardNormal};
+
+    #[test]
+    fn test_char_fn_at_zero() {
+        let phi = nig_char_fn(100.0, 0.03, 15.0, -3.0, 0.5, 0.02, 1.0);
+        let at_zero = phi(Complex::new(0.0, 0.0));
+        assert_relative_eq!(at_zero.re, 1.0, epsilon = 1e-15);
+        assert_relative_eq!(at_zero.im, 0.0, epsilon = 1e-15);
+        // Martingale: E[S_T] = S exp(rT), i.e. phi(-i) = S exp(rT).
+        let forward = phi(Complex::new(0.0, -1.0));
+        assert_relative_eq!(forward.re, 100.0 * 0.03_f64.exp(), max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_symmetric_when_beta_zero() {
+        let (s, r, t, delta, mu) = (100.0_f64, 0.02, 0.5, 0.4, 0.0);
+        let drift = s.ln() + (r + nig_omega(12.0, 0.0, delta, mu) + mu) * t;
+        let phi = nig_char_fn(s, r, 12.0, 0.0, delta, mu, t);
+        for &u in &[0.5, 1.0, 5.0, 20.0] {
+            // The centred log-price has a real characteristic function.
+            let centred = phi(Complex::new(u, 0.0)) * (-Complex::i() * u * drift).exp();
+            assert!(centred.im.abs() < 1e-14, "u={} {:?}", u, centred);
+        }
+        // A non-zero beta breaks the symmetry.
+        let phi = nig_char_fn(s, r, 12.0, -4.0, delta, mu, t);
+        let drift = s.ln() + (r + nig_omega(12.0, -4.0, delta, mu) + mu) * t;
+        let centred = phi(Complex::new(5.0, 0.0)) * (-Complex::i() * 5.0 * drift).exp();
+        assert!(centred.im.abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_matches_monte_carlo() {
+        let (s, t, r) = (100.0, 1.0, 0.03);
+        let (alpha, beta, delta, mu): (f64, f64, f64, f64) = (15.0, -3.0, 0.5, 0.0);
+        let gamma = (alpha * alpha - beta * beta).sqrt();
+        let omega = nig_omega(alpha, beta, delta, mu);
+        // X_T = mu T + beta Z + sqrt(Z) W with Z ~ IG(delta T / gamma, (delta T)^2).
+        let clock = InverseGaussian::new(delta * t / gamma, (delta * t).powi(2)).unwrap();
+        let mut rng = StdRng::seed_from_u64(11);
+        let terminal: Vec<f64> = (0..100_000)
+            .map(|_| {
+                let z: f64 = clock.sample(&mut rng);
+                let w: f64 = StandardNormal.sample(&mut rng);
+                let x = mu * t + beta * z + z.sqrt() * w;
+                s * ((r + omega) * t + x).exp()
+            })
+            .collect();
+        let df = (-r * t).exp();
+        for &(k, is_call) in &[
+            (90.0, false),
+            (100.0, true),
+            (100.0, false),
+            (115.0, true),
+            (115.0, false),
+        ] {
+            let payoffs: Vec<f64> = terminal
+                .iter()
+                .map(|&st| {
+                    df * if is_call {
+                        (st - k).max(0.0)
+                    } else {
+                        (k - st).max(0.0)
+                    }
+                })
+                .collect();
+            let (mc, se) = mean_std_err(&payoffs);
+            let price = nig_price(s, k, t, r, alpha, beta, delta, mu, is_call);
+            assert!(
+                (price - mc).abs() < 2.0 * se,
+                "k={} price={} mc={} se={}",
+                k,
+                price,
+                mc,
+                se
+            );
+        }
+    }
+
+    #[test]
+    fn test_location_invariance() {
+        let (s, k, t, r) = (100.0, 105.0, 0.75, 0.04);
+        for &is_call in &[true, false] {
+            let price = nig_price(s, k, t, r, 20.0, -5.0, 0.6, 0.0, is_call);
+            let shifted = nig_price(s, k, t, r, 20.0, -5.0, 0.6, 0.3, is_call);
+            assert!(price > 0.0);
+            assert_relative_eq!(shifted, price, epsilon = 1e-10);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+/// Market and contract inputs of a European option under Black-Scholes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionParams {
+    /// Spot price (S).
+    pub s: f64,
+    /// Strike (K).
+    pub k: f64,
+    /// Time to maturity in years (T).
+    pub t: f64,
+    /// Continuously compounded risk-free rate.
+    pub r: f64,
+    /// Volatility (annualized).
+    pub sigma: f64,
+}
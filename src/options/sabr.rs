@@ -0,0 +1,282 @@
+use crate::optimization::nelder_mead;
+
+/// Black implied volatility of the SABR model from Hagan's lognormal expansion (Hagan et al., 2002).
+///
+/// The forward follows `dF = alpha_t F^beta dW`, `d alpha_t = nu alpha_t dZ` with
+/// `d<W, Z> = rho dt`. At the money the `z / x(z)` factor tends to 1 and the formula reduces to
+/// `alpha / F^(1 - beta)` times the same time correction.
+///
+/// # Arguments
+///  - f: forward (F)
+///  - k: strike (K)
+///  - t: time to expiry in years
+///  - alpha: initial volatility level
+///  - beta: CEV exponent, `0 <= beta <= 1`
+///  - rho: correlation between forward and volatility
+///  - nu: volatility of volatility
+pub fn sabr_vol(f: f64, k: f64, t: f64, alpha: f64, beta: f64, rho: f64, nu: f64) -> f64 {
+    let one_beta = 1.0 - beta;
+    let log_fk = (f / k).ln();
+    let fk_pow = (f * k).powf(0.5 * one_beta);
+    let z = nu / alpha * fk_pow * log_fk;
+    let z_over_x = if z.abs() < 1e-12 {
+        1.0
+    } else {
+        let x = (((1.0 - 2.0 * rho * z + z * z).sqrt() + z - rho) / (1.0 - rho)).ln();
+        z / x
+    };
+    let log2 = log_fk * log_fk;
+    let denom =
+        fk_pow * (1.0 + one_beta.powi(2) / 24.0 * log2 + one_beta.powi(4) / 1920.0 * log2 * log2);
+    let correction = 1.0
+        + (one_beta.powi(2) / 24.0 * alpha * alpha / (fk_pow * fk_pow)
+            + 0.25 * rho * beta * nu * alpha / fk_pow
+            + (2.0 - 3.0 * rho * rho) / 24.0 * nu * nu)
+            * t;
+    alpha / denom * z_over_x * correction
+}
+
+/// Normal (Bachelier) implied volatility of the SABR model from Hagan's expansion (Hagan et al.,
+/// 2002).
+///
+/// With `q(x) = x^(1 - beta) / (1 - beta)` (`ln x` for `beta = 1`) and
+/// `zeta = nu / alpha * (q(F) - q(K))`,
+///
+/// `sigma_N = alpha (F - K) / (q(F) - q(K)) * zeta / x(zeta) * (1 + (beta (beta - 2) / 24
+/// alpha^2 / (F K)^(1 - beta) + rho beta nu alpha / (4 (F K)^((1 - beta) / 2))
+/// + (2 - 3 rho^2) / 24 nu^2) T)`,
+///
+/// which tends to `alpha F^beta` times the same correction at the money. Unlike
+/// [`sabr_vol`] it stays meaningful for negative strikes and forwards when `beta = 0`.
+///
+/// # Arguments
+///  - f: forward (F)
+///  - k: strike (K)
+///  - t: time to expiry in years
+///  - alpha: initial volatility level
+///  - beta: CEV exponent, `0 <= beta <= 1`
+///  - rho: correlation between forward and volatility
+///  - nu: volatility of volatility
+pub fn sabr_normal_vol(f: f64, k: f64, t: f64, alpha: f64, beta: f64, rho: f64, nu: f64) -> f64 {
+    let one_beta = 1.0 - beta;
+    let q = |x: f64| {
+        if one_beta == 0.0 {
+            x.ln()
+        } else {
+            x.powf(one_beta) / one_beta
+        }
+    };
+    let dq = q(f) - q(k);
+    let fk = f * k;
+    let (scale, z_over_x) = if (f - k).abs() < 1e-12 * f.abs().max(1e-12) {
+        (alpha * f.powf(beta), 1.0)
+    } else {
+        let zeta = nu / alpha * dq;
+        let z_over_x = if zeta.abs() < 1e-12 {
+            1.0
+        } else {
+            let x =
+                (((1.0 - 2.0 * rho * zeta + zeta * zeta).sqrt() + zeta - rho) / (1.0 - rho)).ln();
+            zeta / x
+        };
+        (alpha * (f - k) / dq, z_over_x)
+    };
+    // The beta terms vanish for the normal model, where `F K` may be zero or negative.
+    let beta_terms = if beta == 0.0 {
+        0.0
+    } else {
+        beta * (beta - 2.0) / 24.0 * alpha * alpha / fk.powf(one_beta)
+            + 0.25 * rho * beta * nu * alpha / fk.powf(0.5 * one_beta)
+    };
+    let correction = 1.0 + (beta_terms + (2.0 - 3.0 * rho * rho) / 24.0 * nu * nu) * t;
+    scale * z_over_x * correction
+}
+
+/// Calibrates the SABR parameters `(alpha, rho, nu)` of one expiry slice with `beta` fixed.
+///
+/// Minimises the sum of squared differences between [`sabr_vol`] and the market volatilities
+/// with Nelder-Mead over `(ln alpha, atanh rho, ln nu)`, which keeps the parameters admissible.
+/// Several starting skews and vol-of-vols are tried, with `alpha` started from the quote
+/// closest to the money.
+///
+/// # Arguments
+///  - forward: forward rate or price of the slice
+///  - beta: fixed CEV exponent
+///  - expiry: time to expiry in years
+///  - strikes: quoted strikes
+///  - market_vols: Black implied volatility at each strike
+///
+/// # Returns
+/// [`Result`]<`(alpha, rho, nu)`, `&'static str`>, an error for fewer than three quotes or
+/// non-positive forward or expiry
+pub fn calibrate_sabr_slice(
+    forward: f64,
+    beta: f64,
+    expiry: f64,
+    strikes: &[f64],
+    market_vols: &[f64],
+) -> Result<(f64, f64, f64), &'static str> {
+    if strikes.len() != market_vols.len() || strikes.len() < 3 {
+        return Err("at least three quotes of equal length are needed");
+    }
+    if forward <= 0.0 || expiry <= 0.0 {
+        return Err("forward and expiry must be positive");
+    }
+    let unpack = |x: &[f64]| (x[0].exp(), x[1].tanh(), x[2].exp());
+    let objective = |x: &[f64]| {
+        let (alpha, rho, nu) = unpack(x);
+        strikes
+            .iter()
+            .zip(market_vols)
+            .map(|(&k, &v)| (sabr_vol(forward, k, expiry, alpha, beta, rho, nu) - v).powi(2))
+            .sum::<f64>()
+    };
+    let atm = (0..strikes.len())
+        .min_by(|&i, &j| {
+            let dist = |k: f64| (k / forward).ln().abs();
+            dist(strikes[i]).total_cmp(&dist(strikes[j]))
+        })
+        .unwrap();
+    let alpha0 = market_vols[atm] * forward.powf(1.0 - beta);
+    let best = [-0.5, 0.0, 0.5]
+        .iter()
+        .flat_map(|&rho0: &f64| [0.2, 0.6, 1.5].map(move |nu0: f64| (rho0, nu0)))
+        .map(|(rho0, nu0)| {
+            let x0 = [alpha0.ln(), rho0.atanh(), nu0.ln()];
+            nelder_mead(objective, &x0, 0.1, 1e-12, 1e-20, 5_000)
+        })
+        .min_by(|a, b| a.fx.total_cmp(&b.fx))
+        .unwrap();
+    if !best.fx.is_finite() {
+        return Err("SABR calibration failed");
+    }
+    Ok(unpack(&best.x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+    use approx::assert_relative_eq;
+    use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+
+    const F: f64 = 0.03;
+    const T: f64 = 2.0;
+
+    fn strikes() -> Vec<f64> {
+        (0..11).map(|i| F * (0.5 + 0.1 * i as f64)).collect()
+    }
+
+    #[test]
+    fn test_atm_continuity() {
+        let (alpha, beta, rho, nu) = (0.04, 0.5, -0.3, 0.5);
+        let atm = sabr_vol(F, F, T, alpha, beta, rho, nu);
+        let near = sabr_vol(F, F * (1.0 + 1e-7), T, alpha, beta, rho, nu);
+        assert_relative_eq!(atm, near, max_relative = 1e-6);
+        // Lognormal SABR without vol of vol is Black-Scholes with volatility alpha.
+        assert_relative_eq!(sabr_vol(F, 0.02, T, 0.2, 1.0, 0.0, 0.0), 0.2);
+    }
+
+    /// Undiscounted Bachelier call price with normal volatility `sigma_n`.
+    fn bachelier_call(f: f64, k: f64, t: f64, sigma_n: f64) -> f64 {
+        let sd = sigma_n * t.sqrt();
+        let d = (f - k) / sd;
+        let stdn = Normal::new(0.0, 1.0).unwrap();
+        (f - k) * stdn.cdf(d) + sd * stdn.pdf(d)
+    }
+
+    #[test]
+    fn test_normal_vol_prices_like_lognormal_vol() {
+        for &(alpha, beta, rho, nu) in &[
+            (0.04, 0.5, -0.3, 0.5),
+            (0.2, 1.0, 0.2, 0.8),
+            (0.006, 0.0, -0.6, 0.3),
+        ] {
+            for k in strikes() {
+                let lognormal = sabr_vol(F, k, T, alpha, beta, rho, nu);
+                let normal = sabr_normal_vol(F, k, T, alpha, beta, rho, nu);
+                assert!(normal > 0.0);
+                let black = black_scholes(F, k, T, 0.0, lognormal).0;
+                let bachelier = bachelier_call(F, k, T, normal);
+                // The two expansions differ at second order, most in the wings at high vol of vol.
+                assert!(
+                    (black - bachelier).abs() < 0.02 * black,
+                    "k={} black={} bachelier={}",
+                    k,
+                    black,
+                    bachelier
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_vol_atm_and_positive() {
+        let (alpha, beta, rho, nu) = (0.05, 0.5, 0.3, 0.6);
+        let atm = sabr_normal_vol(F, F, T, alpha, beta, rho, nu);
+        let near = sabr_normal_vol(F, F * (1.0 + 1e-6), T, alpha, beta, rho, nu);
+        assert_relative_eq!(atm, near, max_relative = 1e-5);
+        // Without vol of vol and at short expiry the normal vol is the local vol alpha F^beta.
+        assert_relative_eq!(
+            sabr_normal_vol(F, F, 1e-12, alpha, beta, 0.0, 0.0),
+            alpha * F.sqrt(),
+            max_relative = 1e-10
+        );
+        for &rho in &[-0.9, 0.0, 0.9] {
+            for &nu in &[0.1, 0.5, 1.5] {
+                for k in strikes() {
+                    assert!(sabr_normal_vol(F, k, T, alpha, beta, rho, nu) > 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_vol_negative_strikes() {
+        let (alpha, rho, nu) = (0.008, -0.2, 0.3);
+        let vol = sabr_normal_vol(0.01, -0.005, 1.0, alpha, 0.0, rho, nu);
+        assert!(vol.is_finite() && vol > 0.0, "vol={}", vol);
+        // With beta = 0 the model only sees F - K, so shifting both leaves the vol unchanged.
+        for &(f, k) in &[
+            (0.01, -0.005),
+            (-0.01, -0.02),
+            (0.0, 0.01),
+            (-0.005, -0.005),
+        ] {
+            let shifted = sabr_normal_vol(f + 0.05, k + 0.05, 1.0, alpha, 0.0, rho, nu);
+            let vol = sabr_normal_vol(f, k, 1.0, alpha, 0.0, rho, nu);
+            assert!(vol.is_finite(), "f={} k={}", f, k);
+            assert_relative_eq!(vol, shifted, max_relative = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_recovers_synthetic_slice() {
+        for &(alpha, beta, rho, nu) in &[
+            (0.04, 0.5, -0.3, 0.5),
+            (0.2, 1.0, 0.2, 0.8),
+            (0.006, 0.0, -0.6, 0.3),
+        ] {
+            let k = strikes();
+            let vols: Vec<f64> = k
+                .iter()
+                .map(|&ki| sabr_vol(F, ki, T, alpha, beta, rho, nu))
+                .collect();
+            let (a, r, n) = calibrate_sabr_slice(F, beta, T, &k, &vols).unwrap();
+            assert_relative_eq!(a, alpha, max_relative = 0.1);
+            assert_relative_eq!(r, rho, max_relative = 0.1);
+            assert_relative_eq!(n, nu, max_relative = 0.1);
+            for (&ki, &v) in k.iter().zip(&vols) {
+                let fitted = sabr_vol(F, ki, T, a, beta, r, n);
+                assert!((fitted - v).abs() < 1e-3, "k={} error {}", ki, fitted - v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert!(calibrate_sabr_slice(F, 0.5, T, &[0.02, 0.03], &[0.2, 0.2]).is_err());
+        assert!(calibrate_sabr_slice(F, 0.5, T, &strikes(), &[0.2; 3]).is_err());
+        assert!(calibrate_sabr_slice(F, 0.5, 0.0, &strikes(), &[0.2; 11]).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::optimization::brent;
+
+/// Weights of a static replication of a European payoff by vanilla options on a strike grid.
+///
+/// By the Carr-Madan spanning formula (see also Carr, Ellis and Gupta, 1998; Carr and Chou,
+/// 1997), any twice differentiable payoff decomposes around the forward `F` as
+///
+/// `f(S) = f(F) + f'(F) (S - F) + int_0^F f''(K) (K - S)+ dK + int_F^inf f''(K) (S - K)+ dK`,
+///
+/// i.e. a bond, a forward and a strip of out-of-the-money puts below and calls above the money.
+/// On the grid the density `f''(K) dK` is the numerical second derivative
+/// `(f(K_{i+1}) - f(K_i)) / (K_{i+1} - K_i) - (f(K_i) - f(K_{i-1})) / (K_i - K_{i-1})`, which is
+/// exact for payoffs that are piecewise linear between strikes, so kinks are captured as
+/// well as curvature. By put-call parity a strike's weight is the same whether it is held as a
+/// put or a call, so the weights do not depend on where the money is; the endpoint strikes get
+/// weight 0 and the payoff is extended linearly beyond the grid.
+///
+/// # Arguments
+///  - payoff_fn: payoff as a function of the terminal price
+///  - strikes: increasing strike grid covering the region where the payoff has curvature
+///
+/// # Returns
+/// Number of options held at each strike
+pub fn static_replication_weights(payoff_fn: impl Fn(f64) -> f64, strikes: &[f64]) -> Vec<f64> {
+    let mut weights = vec![0.0; strikes.len()];
+    if strikes.len() < 3 {
+        return weights;
+    }
+    let values: Vec<f64> = strikes.iter().map(|&k| payoff_fn(k)).collect();
+    for i in 1..strikes.len() - 1 {
+        let slope_right = (values[i + 1] - values[i]) / (strikes[i + 1] - strikes[i]);
+        let slope_left = (values[i] - values[i - 1]) / (strikes[i] - strikes[i - 1]);
+        weights[i] = slope_right - slope_left;
+    }
+    weights
+}
+
+/// Terminal spot prices at which a multi-leg option strategy breaks even.
+///
+/// The net payoff at expiry, `sum_i q_i * payoff_i(S) - premium_paid`, is piecewise linear with
+/// kinks at the strikes. Each segment between consecutive strikes, and the segment from 0 to
+/// the lowest strike, is searched with [`brent`] when its endpoints bracket a sign change. Past
+/// the highest strike the payoff is linear, so a bracket is found by extrapolating it. Segments
+/// on which the payoff equals the premium identically only report their endpoints.
+///
+/// # Arguments
+///  - legs: `(quantity, strike, is_call, implied_vol)` per leg, negative quantities for short
+///    legs; the volatility does not enter the payoff at expiry
+///  - premium_paid: net premium paid to enter the strategy, negative for a net credit
+///
+/// # Returns
+/// Increasing breakeven spot prices
+pub fn strategy_breakeven_points(legs: &[(f64, f64, bool, f64)], premium_paid: f64) -> Vec<f64> {
+    let net = |s: f64| {
+        legs.iter()
+            .map(|&(quantity, k, is_call, _)| {
+                quantity
+                    * if is_call {
+                        (s - k).max(0.0)
+                    } else {
+                        (k - s).max(0.0)
+                    }
+            })
+            .sum::<f64>()
+            - premium_paid
+    };
+    let mut nodes: Vec<f64> = legs.iter().map(|leg| leg.1).collect();
+    nodes.push(0.0);
+    nodes.sort_by(f64::total_cmp);
+    nodes.dedup();
+    let last = *nodes.last().unwrap();
+    let slope = net(last + 1.0) - net(last);
+    if slope * net(last) < 0.0 {
+        nodes.push(last + 2.0 * (net(last) / slope).abs());
+    }
+
+    let tol = 1e-12 * last.max(1.0);
+    let mut points: Vec<f64> = Vec::new();
+    for pair in nodes.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (fa, fb) = (net(a), net(b));
+        if fa == 0.0 && points.last() != Some(&a) {
+            points.push(a);
+        }
+        if fa * fb < 0.0 {
+            if let Ok(found) = brent(net, a, b, tol, 200) {
+                points.push(found.root);
+            }
+        }
+    }
+    if net(last) == 0.0 && points.last() != Some(&last) {
+        points.push(last);
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+    use crate::options::monte_carlo::mean_std_err;
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    const S: f64 = 100.0;
+    const T: f64 = 1.0;
+    const R: f64 = 0.03;
+    const SIGMA: f64 = 0.2;
+
+    fn grid(lo: f64, hi: f64, step: f64) -> Vec<f64> {
+        let n = ((hi - lo) / step).round() as usize;
+        (0..=n).map(|i| lo + i as f64 * step).collect()
+    }
+
+    /// Prices the replicating portfolio: a bond and a forward struck at the lowest strike, then
+    /// out-of-the-money puts below the forward and calls above it.
+    fn replication_price(payoff: impl Fn(f64) -> f64, strikes: &[f64]) -> f64 {
+        let weights = static_replication_weights(&payoff, strikes);
+        let df = (-R * T).exp();
+        let forward = S / df;
+        let (k0, k1) = (strikes[0], strikes[1]);
+        let slope = (payoff(k1) - payoff(k0)) / (k1 - k0);
+        let mut price = df * (payoff(k0) + slope * (forward - k0));
+        for (&k, &w) in strikes.iter().zip(&weights) {
+            let (call, put) = black_scholes(S, k, T, R, SIGMA);
+            // A call equals a put plus a forward struck at K.
+            price += w * if k < forward {
+                put + df * (forward - k)
+            } else {
+                call
+            };
+        }
+        price
+    }
+
+    fn monte_carlo_price(payoff: impl Fn(f64) -> f64) -> (f64, f64) {
+        let mut rng = StdRng::seed_from_u64(42);
+        let df = (-R * T).exp();
+        let samples: Vec<f64> = (0..200_000)
+            .map(|_| {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                let st = S * ((R - 0.5 * SIGMA * SIGMA) * T + SIGMA * T.sqrt() * z).exp();
+                df * payoff(st)
+            })
+            .collect();
+        mean_std_err(&samples)
+    }
+
+    fn call(k: f64) -> f64 {
+        black_scholes(S, k, T, R, SIGMA).0
+    }
+
+    fn put(k: f64) -> f64 {
+        black_scholes(S, k, T, R, SIGMA).1
+    }
+
+    #[test]
+    fn test_straddle_breakevens() {
+        let legs = [(1.0, 100.0, true, SIGMA), (1.0, 100.0, false, SIGMA)];
+        let premium = call(100.0) + put(100.0);
+        let points = strategy_breakeven_points(&legs, premium);
+        assert_eq!(points.len(), 2);
+        assert_relative_eq!(points[0], 100.0 - premium, epsilon = 1e-9);
+        assert_relative_eq!(points[1], 100.0 + premium, epsilon = 1e-9);
+        assert_relative_eq!(points[0] + points[1], 200.0, epsilon = 1e-9);
+        // Short, the same spots are where the credit is used up.
+        let short: Vec<_> = legs.iter().map(|&(q, k, c, v)| (-q, k, c, v)).collect();
+        assert_eq!(strategy_breakeven_points(&short, -premium), points);
+    }
+
+    #[test]
+    fn test_bull_spread_breakeven() {
+        let legs = [(1.0, 95.0, true, SIGMA), (-1.0, 105.0, true, SIGMA)];
+        let premium = call(95.0) - call(105.0);
+        let points = strategy_breakeven_points(&legs, premium);
+        assert_eq!(points.len(), 1);
+        assert!(points[0] > 95.0 && points[0] < 105.0);
+        assert_relative_eq!(points[0], 95.0 + premium, epsilon = 1e-9);
+        // The same spread built from puts breaks even at the same spot for its own premium.
+        let put_legs = [(1.0, 95.0, false, SIGMA), (-1.0, 105.0, false, SIGMA)];
+        let put_premium = put(95.0) - put(105.0);
+        assert_relative_eq!(
+            strategy_breakeven_points(&put_legs, put_premium)[0],
+            105.0 + put_premium,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_butterfly_breakevens() {
+        let legs = [
+            (1.0, 90.0, true, SIGMA),
+            (-2.0, 100.0, true, SIGMA),
+            (1.0, 110.0, true, SIGMA),
+        ];
+        let premium = call(90.0) - 2.0 * call(100.0) + call(110.0);
+        let points = strategy_breakeven_points(&legs, premium);
+        assert_eq!(points.len(), 2);
+        assert_relative_eq!(points[0], 90.0 + premium, epsilon = 1e-9);
+        assert_relative_eq!(points[1], 110.0 - premium, epsilon = 1e-9);
+        // A premium above the maximum payoff can never be recovered.
+        assert!(strategy_breakeven_points(&legs, 12.0).is_empty());
+    }
+
+    #[test]
+    fn test_replication_matches_monte_carlo() {
+        let strikes = grid(20.0, 400.0, 1.0);
+        let squared_call = |s: f64| (s - 100.0).max(0.0).powi(2);
+        let log_contract = |s: f64| -(s / 150.0).ln();
+        let capped_call = |s: f64| (s - 95.0).clamp(0.0, 20.0);
+        for payoff in [
+            &squared_call as &dyn Fn(f64) -> f64,
+            &log_contract,
+            &capped_call,
+        ] {
+            let replicated = replication_price(payoff, &strikes);
+            let (mc, _) = monte_carlo_price(payoff);
+            assert!(
+                ((replicated - mc) / mc).abs() < 0.01,
+                "replicated={} mc={}",
+                replicated,
+                mc
+            );
+        }
+    }
+
+    #[test]
+    fn test_kinked_payoff_is_exact() {
+        // A butterfly is one call at 90 and 110 and minus two at 100.
+        let butterfly = |s: f64| (10.0 - (s - 100.0).abs()).max(0.0);
+        let strikes = grid(80.0, 120.0, 5.0);
+        let weights = static_replication_weights(butterfly, &strikes);
+        let expected = [0.0, 0.0, 1.0, 0.0, -2.0, 0.0, 1.0, 0.0, 0.0];
+        for (w, e) in weights.iter().zip(expected) {
+            assert_relative_eq!(*w, e, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_weights_stable_under_refinement() {
+        // Weights per unit strike converge to the payoff's second derivative.
+        let payoff = |s: f64| s.sqrt() * 10.0;
+        let coarse = grid(50.0, 200.0, 2.0);
+        let fine = grid(50.0, 200.0, 0.5);
+        let coarse_w = static_replication_weights(payoff, &coarse);
+        let fine_w = static_replication_weights(payoff, &fine);
+        for (i, &k) in coarse.iter().enumerate().skip(1).take(coarse.len() - 2) {
+            let j = fine.iter().position(|&x| (x - k).abs() < 1e-9).unwrap();
+            let density = -2.5 * k.powf(-1.5);
+            assert_relative_eq!(coarse_w[i] / 2.0, density, max_relative = 1e-3);
+            assert_relative_eq!(fine_w[j] / 0.5, coarse_w[i] / 2.0, max_relative = 1e-3);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use crate::math::Complex;
+use crate::options::black_scholes::black_scholes;
+use crate::options::fft::lewis_call_price;
+
+/// Martingale correction `omega = ln(1 - theta nu - sigma^2 nu / 2) / nu` of the VG model.
+///
+/// NaN when `1 - theta nu - sigma^2 nu / 2 <= 0`, where the model has no finite forward. At
+/// `nu = 0` it is the limit `-theta - sigma^2 / 2`.
+fn vg_omega(sigma: f64, nu: f64, theta: f64) -> f64 {
+    let x = -theta * nu - 0.5 * sigma * sigma * nu;
+    if nu == 0.0 {
+        -theta - 0.5 * sigma * sigma
+    } else {
+        x.ln_1p() / nu
+    }
+}
+
+/// Characteristic function `u -> E[exp(i u ln S_T)]` of the log-price in the Variance Gamma
+/// model of Madan, Carr and Chang (1998), for complex arguments.
+///
+/// The log-price is `ln S + (r + omega) T + X_T` with `X_T = theta g + sigma W(g)` a Brownian
+/// motion with drift, run on a gamma clock `g` of mean `T` and variance `nu T`, so that
+/// `phi(u) = exp(i u (ln S + (r + omega) T)) * (1 - i u theta nu + sigma^2 nu u^2 / 2)^(-T/nu)`.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility of the subordinated Brownian motion
+///  - nu: variance rate of the gamma clock (kurtosis)
+///  - theta: drift of the subordinated Brownian motion (skew)
+///  - t: time to maturity in years (T)
+pub fn variance_gamma_char_fn(
+    s: f64,
+    r: f64,
+    sigma: f64,
+    nu: f64,
+    theta: f64,
+    t: f64,
+) -> impl Fn(Complex) -> Complex {
+    let drift = s.ln() + (r + vg_omega(sigma, nu, theta)) * t;
+    move |u| {
+        let i = Complex::i();
+        let exponent = -i * u * theta + 0.5 * sigma * sigma * u * u;
+        // The gamma clock becomes deterministic as nu -> 0.
+        let log_clock = if nu == 0.0 {
+            exponent * t
+        } else {
+            (t / nu) * (1.0 + exponent * nu).ln()
+        };
+        (i * u * drift - log_clock).exp()
+    }
+}
+
+/// Prices a European option in the Variance Gamma model.
+///
+/// Calls are priced with Lewis's (2001) single Fourier integral, see [`lewis_call_price`], and
+/// puts follow from put-call parity. At `nu = 0` the gamma clock is deterministic and the
+/// price is Black-Scholes with volatility `sigma`, the drift `theta` being absorbed by the
+/// martingale correction.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility of the subordinated Brownian motion
+///  - nu: variance rate of the gamma clock; Black-Scholes is the limit `nu -> 0`
+///  - theta: drift of the subordinated Brownian motion; negative values skew to the left
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// (price) of the option, NaN if `1 - theta nu - sigma^2 nu / 2 <= 0`
+#[allow(clippy::too_many_arguments)]
+pub fn variance_gamma_price(
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    nu: f64,
+    theta: f64,
+    is_call: bool,
+) -> f64 {
+    if t <= 0.0 {
+        return if is_call {
+            (s - k).max(0.0)
+        } else {
+            (k - s).max(0.0)
+        };
+    }
+    if nu == 0.0 {
+        let (call, put) = black_scholes(s, k, t, r, sigma);
+        return if is_call { call } else { put };
+    }
+    if vg_omega(sigma, nu, theta).is_nan() {
+        return f64::NAN;
+    }
+    let phi = variance_gamma_char_fn(s, r, sigma, nu, theta, t);
+    let call = lewis_call_price(phi, s, k, t, r);
+    if is_call {
+        call
+    } else {
+        call - s + k * (-r * t).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::fft::carr_madan_fft;
+    use crate::options::monte_carlo::mean_std_err;
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, Gamma, StandardNormal};
+
+    #[test]
+    fn test_converges_to_black_scholes() {
+        let (s, t, r, sigma) = (100.0, 0.75, 0.03, 0.25);
+        for &k in &[80.0, 100.0, 120.0] {
+            let (call, put) = black_scholes(s, k, t, r, sigma);
+            let mut prev_err = f64::INFINITY;
+            for &nu in &[1e-2, 1e-3, 1e-4] {
+                let err = (variance_gamma_price(s, k, t, r, sigma, nu, 0.0, true) - call).abs()
+                    + (variance_gamma_price(s, k, t, r, sigma, nu, 0.0, false) - put).abs();
+                assert!(err < prev_err, "k={} nu={} err={}", k, nu, err);
+                prev_err = err;
+            }
+            assert!(prev_err < 1e-3, "k={} err={}", k, prev_err);
+        }
+    }
+
+    #[test]
+    fn test_fatter_tails_than_lognormal() {
+        // With theta = 0 the return variance is sigma^2 T in both models, but VG puts more
+        // weight on large moves, raising far out-of-the-money prices.
+        let (s, t, r, sigma, nu) = (100.0, 0.5, 0.02, 0.2, 0.3);
+        let (_, bs_put) = black_scholes(s, 70.0, t, r, sigma);
+        let (bs_call, _) = black_scholes(s, 140.0, t, r, sigma);
+        assert!(variance_gamma_price(s, 70.0, t, r, sigma, nu, 0.0, false) > bs_put);
+        assert!(variance_gamma_price(s, 140.0, t, r, sigma, nu, 0.0, true) > bs_call);
+        // ... and less near the money.
+        let (bs_atm, _) = black_scholes(s, 100.0, t, r, sigma);
+        assert!(variance_gamma_price(s, 100.0, t, r, sigma, nu, 0.0, true) < bs_atm);
+    }
+
+    #[test]
+    fn test_zero_nu_is_black_scholes() {
+        let (s, t, r, sigma) = (100.0, 0.75, 0.03, 0.25);
+        for &k in &[80.0, 100.0, 120.0] {
+            let (call, put) = black_scholes(s, k, t, r, sigma);
+            for &theta in &[0.0, -0.2] {
+                assert_eq!(
+                    variance_gamma_price(s, k, t, r, sigma, 0.0, theta, true),
+                    call
+                );
+                assert_eq!(
+                    variance_gamma_price(s, k, t, r, sigma, 0.0, theta, false),
+                    put
+                );
+                // The Fourier route agrees in the limit.
+                let phi = variance_gamma_char_fn(s, r, sigma, 0.0, theta, t);
+                assert_relative_eq!(lewis_call_price(phi, s, k, t, r), call, epsilon = 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_monte_carlo() {
+        let (s, t, r) = (100.0, 1.0, 0.04);
+        let (sigma, nu, theta) = (0.2, 0.2, -0.15);
+        let drift = (r + vg_omega(sigma, nu, theta)) * t;
+        // Gamma clock with mean T and variance nu T.
+        let clock = Gamma::new(t / nu, nu).unwrap();
+        let mut rng = StdRng::seed_from_u64(5);
+        let terminal: Vec<f64> = (0..100_000)
+            .map(|_| {
+                let g: f64 = clock.sample(&mut rng);
+                let z: f64 = StandardNormal.sample(&mut rng);
+                s * (drift + theta * g + sigma * g.sqrt() * z).exp()
+            })
+            .collect();
+        let df = (-r * t).exp();
+        for &(k, is_call) in &[
+            (70.0, false),
+            (95.0, false),
+            (100.0, true),
+            (130.0, false),
+            (130.0, true),
+        ] {
+            let payoffs: Vec<f64> = terminal
+                .iter()
+                .map(|&st| {
+                    df * if is_call {
+                        (st - k).max(0.0)
+                    } else {
+                        (k - st).max(0.0)
+                    }
+                })
+                .collect();
+            let (mc, se) = mean_std_err(&payoffs);
+            let price = variance_gamma_price(s, k, t, r, sigma, nu, theta, is_call);
+            assert!(
+                (price - mc).abs() < 3.0 * se,
+                "k={} call={} price={} mc={} se={}",
+                k,
+                is_call,
+                price,
+                mc,
+                se
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_carr_madan_fft() {
+        let (s, t, r, sigma, nu, theta) = (100.0, 1.0, 0.03, 0.2, 0.25, -0.2);
+        let phi = variance_gamma_char_fn(s, r, sigma, nu, theta, t);
+        let strip = carr_madan_fft(phi, s, r, t, 4096, 0.25, 1.5);
+        for &(strike, price) in strip.iter().filter(|(k, _)| (70.0..140.0).contains(k)) {
+            let lewis = variance_gamma_price(s, strike, t, r, sigma, nu, theta, true);
+            assert_relative_eq!(lewis, price, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters_give_nan() {
+        // 1 - theta nu - sigma^2 nu / 2 < 0.
+        assert!(variance_gamma_price(100.0, 100.0, 1.0, 0.02, 0.5, 2.0, 0.5, true).is_nan());
+    }
+}
@@ -1,5 +1,6 @@
-use crate::optimization::secant;
+use crate::optimization::{brent, secant, SecantError};
 use crate::options::black_scholes;
+use std::f64::consts::PI;
 
 /// Calculates implied volatility by solving it for the market price, using the Black-Scholes formula.
 /// 
@@ -12,26 +13,254 @@ use crate::options::black_scholes;
 ///  - is_call: true for call option, false for put option
 /// # Returns 
 /// (volatility)
+///
+/// # Panics
+/// If the root search does not converge; see [`try_implied_volatility`].
 pub fn implied_volatility(p: f64, s: f64, k: f64, t: f64, r: f64, is_call: bool) -> f64 {
-    if is_call {
-        let f = |sigma: f64| {
-            let (call_price, _) = black_scholes(s, k, t, r, sigma);
+    try_implied_volatility(p, s, k, t, r, is_call).expect("Implied volatility calculation failed")
+}
+
+/// Fallible version of [`implied_volatility`].
+///
+/// Prices outside the no-arbitrage bounds of [`call_price_bounds`] or [`put_price_bounds`] are
+/// rejected with [`SecantError::OutOfBounds`] before the root search starts.
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]> with the implied volatility
+pub fn try_implied_volatility(
+    p: f64,
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    is_call: bool,
+) -> Result<f64, SecantError> {
+    let valid = if is_call {
+        is_valid_call_price(p, s, k, t, r)
+    } else {
+        is_valid_put_price(p, s, k, t, r)
+    };
+    if !valid {
+        return Err(SecantError::OutOfBounds);
+    }
+    let f = |sigma: f64| {
+        let (call_price, put_price) = black_scholes(s, k, t, r, sigma);
+        if is_call {
             call_price - p
-        };
-        secant(f, 0.1, 0.3, 1e-6, 1e-6, 50).expect("Implied volatility calculation failed").root
+        } else {
+            put_price - p
+        }
+    };
+    let guess = bs_initial_vol_guess(p, s, k, t, r, is_call);
+    secant(f, guess, 1.1 * guess, 1e-6, 1e-6, 50).map(|res| res.root)
+}
+
+/// Implied volatility restricted to `[lower_vol, upper_vol]`, robust for deep out-of-the-money
+/// options.
+///
+/// Tiny prices have almost no vega, so the secant iteration of [`try_implied_volatility`] can
+/// overshoot into negative or spurious volatilities. Here the price is first checked against
+/// the no-arbitrage bounds and the bracket, i.e. the Black-Scholes prices at `lower_vol` and
+/// `upper_vol` must straddle `p`, and the root is then found with Brent's method, which never
+/// leaves the bracket.
+///
+/// # Arguments
+///  - p: market price of the option
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - is_call: true for call option, false for put option
+///  - lower_vol: lower end of the volatility search interval
+///  - upper_vol: upper end of the volatility search interval
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]> with the implied volatility, or
+/// [`SecantError::OutOfBounds`] if no volatility in `[lower_vol, upper_vol]` reproduces `p`
+#[allow(clippy::too_many_arguments)]
+pub fn implied_volatility_regularized(
+    p: f64,
+    s: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    is_call: bool,
+    lower_vol: f64,
+    upper_vol: f64,
+) -> Result<f64, SecantError> {
+    let valid = if is_call {
+        is_valid_call_price(p, s, k, t, r)
     } else {
-        let f = |sigma: f64| {
-            let (_, put_price) = black_scholes(s, k, t, r, sigma);
+        is_valid_put_price(p, s, k, t, r)
+    };
+    if !valid || lower_vol >= upper_vol {
+        return Err(SecantError::OutOfBounds);
+    }
+    let f = |sigma: f64| {
+        let (call_price, put_price) = black_scholes(s, k, t, r, sigma);
+        if is_call {
+            call_price - p
+        } else {
             put_price - p
-        };
-        secant(f, 0.1, 0.3, 1e-6, 1e-6, 50).expect("Implied volatility calculation failed").root
+        }
+    };
+    brent(f, lower_vol, upper_vol, 1e-12, 200).map(|res| res.root)
+}
+
+/// No-arbitrage bounds on the price of a European call on a non-dividend-paying stock.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///
+/// # Returns
+/// (lower_bound, upper_bound) = `(max(S - K * exp(-r*T), 0), S)`
+pub fn call_price_bounds(s: f64, k: f64, t: f64, r: f64) -> (f64, f64) {
+    ((s - k * (-r * t).exp()).max(0.0), s)
+}
+
+/// No-arbitrage bounds on the price of a European put on a non-dividend-paying stock.
+///
+/// # Returns
+/// (lower_bound, upper_bound) = `(max(K * exp(-r*T) - S, 0), K * exp(-r*T))`
+pub fn put_price_bounds(s: f64, k: f64, t: f64, r: f64) -> (f64, f64) {
+    let pv_strike = k * (-r * t).exp();
+    ((pv_strike - s).max(0.0), pv_strike)
+}
+
+/// Whether `p` lies within `bounds`, allowing for rounding at either end.
+fn within_bounds(p: f64, (lower, upper): (f64, f64)) -> bool {
+    const REL_TOL: f64 = 1e-12;
+    let slack = REL_TOL * upper;
+    p >= lower - slack && p <= upper + slack
+}
+
+/// Whether `p` lies within [`call_price_bounds`], bounds included up to rounding.
+pub fn is_valid_call_price(p: f64, s: f64, k: f64, t: f64, r: f64) -> bool {
+    within_bounds(p, call_price_bounds(s, k, t, r))
+}
+
+/// Whether `p` lies within [`put_price_bounds`], bounds included up to rounding.
+pub fn is_valid_put_price(p: f64, s: f64, k: f64, t: f64, r: f64) -> bool {
+    within_bounds(p, put_price_bounds(s, k, t, r))
+}
+
+/// Brenner-Subrahmanyam approximation of at-the-money implied volatility,
+/// `sqrt(2*pi/T) * C/S`.
+///
+/// Exact to first order in `sigma * sqrt(T)` for a call struck at the forward
+/// `K = S * exp(r*T)`, whose price `S * (2 N(sigma sqrt(T) / 2) - 1)` does not depend on `r`.
+/// It underestimates by a relative `sigma^2 T / 24`, so it is best for short maturities and
+/// low volatilities. Inverse of [`atm_call_approx`].
+///
+/// # Arguments
+///  - atm_call_price: price of the at-the-money-forward call
+///  - s: spot price (S)
+///  - t: time to maturity in years (T)
+pub fn atm_vol_approx(atm_call_price: f64, s: f64, t: f64) -> f64 {
+    (2.0 * PI / t).sqrt() * atm_call_price / s
+}
+
+/// Brenner-Subrahmanyam approximation of the at-the-money-forward call price,
+/// `S * sigma * sqrt(T / (2*pi))`, i.e. about `0.4 * S * sigma * sqrt(T)`.
+///
+/// Inverse of [`atm_vol_approx`].
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - t: time to maturity in years (T)
+///  - sigma: volatility (annualized)
+pub fn atm_call_approx(s: f64, t: f64, sigma: f64) -> f64 {
+    s * sigma * (t / (2.0 * PI)).sqrt()
+}
+
+/// Closed-form starting point for the implied volatility root search.
+///
+/// At the money this is Brenner-Subrahmanyam's `sqrt(2*pi/T) * C/S`. Away from the money it
+/// uses the Corrado-Miller correction,
+/// `sqrt(2*pi/T) / (S + X) * (C - (S - X)/2 + sqrt((C - (S - X)/2)^2 - (S - X)^2/pi))`
+/// with `X = K * exp(-r*T)`, which reduces to the former when `S = X`. Puts are converted to calls through put-call parity.
+///
+/// # Arguments
+///  - p: market price of the option
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - is_call: true for call option, false for put option
+///
+/// # Returns
+/// (volatility) guess, floored at a small positive value
+pub fn bs_initial_vol_guess(p: f64, s: f64, k: f64, t: f64, r: f64, is_call: bool) -> f64 {
+    const MIN_GUESS: f64 = 1e-3;
+    let x = k * (-r * t).exp();
+    let call = if is_call { p } else { p + s - x };
+    let scale = (2.0 * PI / t).sqrt();
+    let half_moneyness = 0.5 * (s - x);
+    let excess = call - half_moneyness;
+    let discriminant = excess * excess - 4.0 * half_moneyness * half_moneyness / PI;
+    // A negative discriminant lies outside the range of the correction; clipping it at zero is
+    // the usual remedy.
+    let guess = scale / (s + x) * (excess + discriminant.max(0.0).sqrt());
+    if guess.is_finite() {
+        guess.max(MIN_GUESS)
+    } else {
+        MIN_GUESS
     }
 }
 
+/// Calculates the implied repo (funding) rate from put-call parity,
+/// `C - P = S * exp(-q*T) - K * exp(-r*T)`, solved for `r` with the secant method.
+///
+/// # Arguments
+///  - call: market price of the call
+///  - put: market price of the put with the same strike and maturity
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - q: continuously compounded dividend yield
+///
+/// # Returns
+/// [`Result`]<`f64`, [`SecantError`]> with the continuously compounded repo rate
+pub fn implied_repo_rate(
+    call: f64,
+    put: f64,
+    s: f64,
+    k: f64,
+    t: f64,
+    q: f64,
+) -> Result<f64, SecantError> {
+    let forward_value = s * (-q * t).exp();
+    let f = |r: f64| forward_value - k * (-r * t).exp() - (call - put);
+    secant(f, 0.0, 0.05, 1e-12, 1e-12, 100).map(|res| res.root)
+}
+
+/// Calculates the dividend yield implied by put-call parity,
+/// `q = -ln((C - P + K * exp(-r*T)) / S) / T`.
+///
+/// Most reliable for at-the-money options, where both the call and the put are liquid.
+///
+/// # Arguments
+///  - call: market price of the call
+///  - put: market price of the put with the same strike and maturity
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///
+/// # Returns
+/// (dividend_yield) as a continuously compounded rate
+pub fn implied_dividend_yield(call: f64, put: f64, s: f64, k: f64, t: f64, r: f64) -> f64 {
+    -((call - put + k * (-r * t).exp()) / s).ln() / t
+}
+
 #[cfg(test)]
 mod tests {
-    use super::implied_volatility;
-    use crate::options::black_scholes;
+    use super::*;
+    use crate::optimization::secant;
+    use crate::options::{black_scholes, black_scholes_merton};
     use approx::assert_relative_eq;
 
     #[test]
@@ -59,4 +288,223 @@ mod tests {
         let implied_vol = implied_volatility(put_price, s, k, t, r, false);
         assert_relative_eq!(implied_vol, sigma, epsilon=1e-4);
     }
+
+    #[test]
+    fn test_implied_repo_rate_round_trip() {
+        for &(r, t) in &[(0.05, 1.0), (-0.005, 2.0), (0.03, 1.0 / 365.0)] {
+            let (s, k, q, sigma) = (100.0, 105.0, 0.02, 0.25);
+            let (call, put) = black_scholes_merton(s, k, t, r, q, sigma);
+            let repo = implied_repo_rate(call, put, s, k, t, q).expect("repo rate should converge");
+            assert_relative_eq!(repo, r, epsilon = 1e-8);
+            let (call_back, put_back) = black_scholes_merton(s, k, t, repo, q, sigma);
+            assert_relative_eq!(call_back, call, epsilon = 1e-8);
+            assert_relative_eq!(put_back, put, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_implied_dividend_yield_round_trip() {
+        let (s, k, t, r, q, sigma) = (100.0, 100.0, 0.5, 0.04, 0.025, 0.2);
+        let (call, put) = black_scholes_merton(s, k, t, r, q, sigma);
+        let implied_q = implied_dividend_yield(call, put, s, k, t, r);
+        assert_relative_eq!(implied_q, q, epsilon = 1e-10);
+        let (call_back, put_back) = black_scholes_merton(s, k, t, r, implied_q, sigma);
+        assert_relative_eq!(call_back, call, epsilon = 1e-8);
+        assert_relative_eq!(put_back, put, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_implied_dividend_yield_non_dividend_stock() {
+        let (call, put) = black_scholes(100.0, 100.0, 1.0, 0.05, 0.3);
+        assert!(implied_dividend_yield(call, put, 100.0, 100.0, 1.0, 0.05).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_initial_vol_guess_reduces_iterations() {
+        let (s, r, sigma) = (100.0, 0.03, 0.25);
+        let iterations = |p: f64, k: f64, t: f64, x0: f64, x1: f64| {
+            let f = |vol: f64| black_scholes(s, k, t, r, vol).0 - p;
+            secant(f, x0, x1, 1e-6, 1e-6, 50).map_or(50, |res| res.iterations)
+        };
+        let (mut fixed, mut adaptive, mut count) = (0usize, 0usize, 0usize);
+        for &t in &[0.25, 0.5, 1.0, 2.0] {
+            for k in (0..9).map(|i| 80.0 + 5.0 * i as f64) {
+                let (call, _) = black_scholes(s, k, t, r, sigma);
+                let guess = bs_initial_vol_guess(call, s, k, t, r, true);
+                fixed += iterations(call, k, t, 0.1, 0.3);
+                adaptive += iterations(call, k, t, guess, 1.1 * guess);
+                count += 1;
+            }
+        }
+        assert!(
+            fixed as f64 / count as f64 - adaptive as f64 / count as f64 >= 1.0,
+            "fixed={} adaptive={} over {} options",
+            fixed,
+            adaptive,
+            count
+        );
+    }
+
+    #[test]
+    fn test_initial_vol_guess_positive() {
+        let (s, r) = (100.0, 0.05);
+        for &t in &[1.0 / 365.0, 0.1, 1.0, 5.0] {
+            for &sigma in &[0.05, 0.2, 0.8] {
+                for k in (0..13).map(|i| 40.0 + 10.0 * i as f64) {
+                    let (call, put) = black_scholes(s, k, t, r, sigma);
+                    for &(p, is_call) in &[(call, true), (put, false)] {
+                        let guess = bs_initial_vol_guess(p, s, k, t, r, is_call);
+                        assert!(
+                            guess > 0.0 && guess.is_finite(),
+                            "k={} t={} guess={}",
+                            k,
+                            t,
+                            guess
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_initial_vol_guess_at_the_money() {
+        let (s, t, r, sigma): (f64, f64, f64, f64) = (100.0, 0.5, 0.02, 0.3);
+        let k = s * (r * t).exp();
+        let (call, put) = black_scholes(s, k, t, r, sigma);
+        assert_relative_eq!(
+            bs_initial_vol_guess(call, s, k, t, r, true),
+            sigma,
+            epsilon = 2e-3
+        );
+        assert_relative_eq!(
+            bs_initial_vol_guess(put, s, k, t, r, false),
+            sigma,
+            epsilon = 2e-3
+        );
+    }
+
+    #[test]
+    fn test_black_scholes_prices_within_bounds() {
+        for &r in &[-0.01, 0.0, 0.05] {
+            for &t in &[1.0 / 365.0, 0.25, 1.0, 10.0] {
+                for &sigma in &[0.01, 0.2, 1.5] {
+                    for k in (0..13).map(|i| 40.0 + 10.0 * i as f64) {
+                        let (call, put) = black_scholes(100.0, k, t, r, sigma);
+                        assert!(
+                            is_valid_call_price(call, 100.0, k, t, r),
+                            "k={} call={}",
+                            k,
+                            call
+                        );
+                        assert!(
+                            is_valid_put_price(put, 100.0, k, t, r),
+                            "k={} put={}",
+                            k,
+                            put
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_price_bounds() {
+        let (lower, upper) = call_price_bounds(100.0, 90.0, 1.0, 0.05);
+        assert_relative_eq!(lower, 100.0 - 90.0 * (-0.05_f64).exp(), epsilon = 1e-12);
+        assert_eq!(upper, 100.0);
+        let (lower, upper) = put_price_bounds(100.0, 90.0, 1.0, 0.05);
+        assert_eq!(lower, 0.0);
+        assert_relative_eq!(upper, 90.0 * (-0.05_f64).exp(), epsilon = 1e-12);
+        assert!(!is_valid_call_price(101.0, 100.0, 90.0, 1.0, 0.05));
+        assert!(!is_valid_put_price(-0.01, 100.0, 90.0, 1.0, 0.05));
+    }
+
+    #[test]
+    fn test_implied_volatility_rejects_out_of_bounds_price() {
+        // Below intrinsic value and above the spot.
+        assert_eq!(
+            try_implied_volatility(5.0, 100.0, 90.0, 1.0, 0.05, true),
+            Err(SecantError::OutOfBounds)
+        );
+        assert_eq!(
+            try_implied_volatility(120.0, 100.0, 90.0, 1.0, 0.05, false),
+            Err(SecantError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_regularized_deep_otm() {
+        let (s, t, r) = (100.0, 0.25, 0.02);
+        // A true volatility behind a price far below 1e-10.
+        let (call, _) = black_scholes(s, 250.0, t, r, 0.25);
+        assert!(call < 1e-10);
+        let vol = implied_volatility_regularized(call, s, 250.0, t, r, true, 0.01, 3.0).unwrap();
+        assert_relative_eq!(vol, 0.25, epsilon = 1e-6);
+        // A quoted price of exactly 1e-10 on a deep out-of-the-money put.
+        let vol = implied_volatility_regularized(1e-10, s, 50.0, t, r, false, 0.01, 3.0).unwrap();
+        let (_, put) = black_scholes(s, 50.0, t, r, vol);
+        assert_relative_eq!(put, 1e-10, max_relative = 1e-6);
+    }
+
+    #[test]
+    fn test_regularized_stays_in_bracket() {
+        let (s, k, t, r) = (100.0, 130.0, 0.5, 0.03);
+        for &(lower, upper) in &[(0.05, 0.5), (0.3, 0.31), (0.01, 5.0)] {
+            for &sigma in &[0.1, 0.305, 0.8] {
+                let (call, _) = black_scholes(s, k, t, r, sigma);
+                match implied_volatility_regularized(call, s, k, t, r, true, lower, upper) {
+                    Ok(vol) => {
+                        assert!((lower..=upper).contains(&vol), "vol={}", vol);
+                        assert_relative_eq!(vol, sigma, epsilon = 1e-8);
+                    }
+                    Err(e) => {
+                        assert_eq!(e, SecantError::OutOfBounds);
+                        assert!(!(lower..=upper).contains(&sigma));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_regularized_matches_unrestricted_solver() {
+        let (s, t, r) = (100.0, 1.0, 0.05);
+        for &k in &[80.0, 100.0, 120.0] {
+            for &is_call in &[true, false] {
+                let (call, put) = black_scholes(s, k, t, r, 0.3);
+                let p = if is_call { call } else { put };
+                let unrestricted = try_implied_volatility(p, s, k, t, r, is_call).unwrap();
+                let regularized =
+                    implied_volatility_regularized(p, s, k, t, r, is_call, 1e-3, 5.0).unwrap();
+                assert_relative_eq!(regularized, unrestricted, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_atm_approx_round_trip() {
+        for &(s, t, sigma) in &[(100.0, 1.0, 0.2), (42.0, 0.1, 0.6), (5000.0, 3.0, 0.15)] {
+            let c = atm_call_approx(s, t, sigma);
+            assert_relative_eq!(atm_vol_approx(c, s, t), sigma, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_atm_approx_matches_black_scholes() {
+        let (s, r, sigma): (f64, f64, f64) = (100.0, 0.04, 0.25);
+        let mut prev_err = 0.0;
+        for &t in &[0.05, 0.25, 0.5, 1.0, 2.0] {
+            let forward = s * (r * t).exp();
+            let (call, _) = black_scholes(s, forward, t, r, sigma);
+            let price_err = (atm_call_approx(s, t, sigma) - call).abs() / call;
+            assert!(price_err < 0.01, "t={} err={}", t, price_err);
+            // The error is second order in sigma * sqrt(T), so it grows with maturity.
+            let vol_err = (atm_vol_approx(call, s, t) - sigma).abs() / sigma;
+            assert_relative_eq!(vol_err, sigma * sigma * t / 24.0, max_relative = 0.05);
+            assert!(vol_err > prev_err);
+            prev_err = vol_err;
+        }
+    }
 }
@@ -1,9 +1,16 @@
 use pyo3::prelude::*;
-use crate::options::volatility::implied_volatility;
+use crate::options::volatility::{implied_dividend_yield, implied_volatility};
 
 #[pyfunction]
 pub fn implied_volatility_py(
     p: f64, s: f64, k: f64, t: f64, r: f64, is_call: bool
 ) -> PyResult<f64> {
     Ok(implied_volatility(p, s, k, t, r, is_call))
+}
+
+#[pyfunction]
+pub fn implied_dividend_yield_py(
+    call: f64, put: f64, s: f64, k: f64, t: f64, r: f64
+) -> PyResult<f64> {
+    Ok(implied_dividend_yield(call, put, s, k, t, r))
 }
\ No newline at end of file
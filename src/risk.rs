@@ -0,0 +1,20 @@
+pub mod backtest;
+pub mod evt;
+pub mod hedge;
+pub mod pnl;
+pub mod portfolio;
+pub mod rolling;
+pub mod scenario;
+pub mod var;
+
+pub use backtest::{christoffersen_test, kupiec_test};
+pub use evt::{fit_gev_mle, fit_gpd_mle, gev_quantile, pot_var, GevParams};
+pub use hedge::{
+    delta_hedge_pnl_distribution, hedge_effectiveness, minimum_variance_hedge_ratio,
+    simulate_delta_hedge, variance_optimal_hedge_ratio,
+};
+pub use pnl::{attribute_pnl, PnlAttribution};
+pub use portfolio::{cross_gamma_matrix, MultiAssetPosition};
+pub use rolling::{rolling_ewma_vol, rolling_historical_var, rolling_historical_vol};
+pub use scenario::{apply_scenario, batch_scenarios, Scenario};
+pub use var::{component_var, historical_var, incremental_var, marginal_var};
@@ -0,0 +1,167 @@
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+
+/// Critical value of the chi-squared distribution with one degree of freedom at 5% significance.
+pub const CHI2_1DF_CRITICAL_5PCT: f64 = 3.841458820694124;
+
+/// `x * ln(y)` with the convention `0 * ln(0) = 0`.
+fn xlny(x: f64, y: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        x * y.ln()
+    }
+}
+
+/// Kupiec proportion-of-failures (unconditional coverage) test for VaR backtesting.
+///
+/// Compares the observed exception rate `p_hat = n_exceptions / n_observations` with the
+/// nominal rate `p = 1 - confidence` via the likelihood ratio
+/// `LR = 2 * ln((p_hat^x * (1 - p_hat)^(n - x)) / (p^x * (1 - p)^(n - x)))`, which is
+/// asymptotically chi-squared with one degree of freedom.
+///
+/// # Arguments
+///  - n_exceptions: number of days on which the loss exceeded VaR
+///  - n_observations: number of days in the backtest
+///  - confidence: VaR confidence level, e.g. 0.99
+///
+/// # Returns
+/// (test_statistic, p_value, rejected_at_5pct)
+pub fn kupiec_test(
+    n_exceptions: usize,
+    n_observations: usize,
+    confidence: f64,
+) -> (f64, f64, bool) {
+    let n = n_observations as f64;
+    let x = n_exceptions as f64;
+    let p = 1.0 - confidence;
+    let p_hat = x / n;
+
+    let log_l_hat = xlny(x, p_hat) + xlny(n - x, 1.0 - p_hat);
+    let log_l_null = xlny(x, p) + xlny(n - x, 1.0 - p);
+    let stat = (2.0 * (log_l_hat - log_l_null)).max(0.0);
+
+    let chi2 = ChiSquared::new(1.0).unwrap();
+    let p_value = 1.0 - chi2.cdf(stat);
+    (stat, p_value, stat > CHI2_1DF_CRITICAL_5PCT)
+}
+
+/// Christoffersen independence test for VaR exceptions.
+///
+/// Counts the transitions `n_ij` from state `i` to state `j` between consecutive days, where
+/// state 1 is an exception, and compares a first-order Markov chain with transition
+/// probabilities `pi_01 = n_01 / (n_00 + n_01)` and `pi_11 = n_11 / (n_10 + n_11)` against the
+/// independent alternative with a single exception probability `pi`. The likelihood ratio is
+/// asymptotically chi-squared with one degree of freedom; a rejection indicates clustering of
+/// exceptions that [`kupiec_test`] cannot detect.
+///
+/// # Arguments
+///  - exceptions: daily exception indicators in chronological order
+///
+/// # Returns
+/// (test_statistic, p_value, rejected_at_5pct)
+pub fn christoffersen_test(exceptions: &[bool]) -> (f64, f64, bool) {
+    let mut counts = [[0.0_f64; 2]; 2];
+    for pair in exceptions.windows(2) {
+        counts[pair[0] as usize][pair[1] as usize] += 1.0;
+    }
+    let [[n00, n01], [n10, n11]] = counts;
+
+    let pi01 = if n00 + n01 > 0.0 {
+        n01 / (n00 + n01)
+    } else {
+        0.0
+    };
+    let pi11 = if n10 + n11 > 0.0 {
+        n11 / (n10 + n11)
+    } else {
+        0.0
+    };
+    let pi = (n01 + n11) / (n00 + n01 + n10 + n11);
+
+    let log_l_markov =
+        xlny(n00, 1.0 - pi01) + xlny(n01, pi01) + xlny(n10, 1.0 - pi11) + xlny(n11, pi11);
+    let log_l_indep = xlny(n00 + n10, 1.0 - pi) + xlny(n01 + n11, pi);
+    let stat = (2.0 * (log_l_markov - log_l_indep)).max(0.0);
+
+    let chi2 = ChiSquared::new(1.0).unwrap();
+    let p_value = 1.0 - chi2.cdf(stat);
+    (stat, p_value, stat > CHI2_1DF_CRITICAL_5PCT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn independent_exceptions(n: usize, p: f64, seed: u64) -> Vec<bool> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n).map(|_| rng.gen::<f64>() < p).collect()
+    }
+
+    #[test]
+    fn test_critical_value() {
+        let chi2 = ChiSquared::new(1.0).unwrap();
+        assert_relative_eq!(chi2.cdf(CHI2_1DF_CRITICAL_5PCT), 0.95, epsilon = 1e-9);
+        assert_relative_eq!(CHI2_1DF_CRITICAL_5PCT, 3.841, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_nominal_frequency_not_rejected() {
+        let (stat, p_value, rejected) = kupiec_test(10, 1000, 0.99);
+        assert_relative_eq!(stat, 0.0, epsilon = 1e-9);
+        assert_relative_eq!(p_value, 1.0, epsilon = 1e-9);
+        assert!(!rejected);
+    }
+
+    #[test]
+    fn test_zero_exceptions() {
+        // With a single expected exception, a clean record is consistent with the model.
+        let (stat, _, rejected) = kupiec_test(0, 100, 0.99);
+        assert_relative_eq!(stat, -200.0 * 0.99_f64.ln(), epsilon = 1e-12);
+        assert!(!rejected);
+    }
+
+    #[test]
+    fn test_too_many_exceptions_rejected() {
+        let (_, p_value, rejected) = kupiec_test(30, 250, 0.99);
+        assert!(rejected);
+        assert!(p_value < 1e-6);
+    }
+
+    #[test]
+    fn test_independent_series_not_rejected() {
+        let exceptions = independent_exceptions(1000, 0.05, 1);
+        let (_, _, rejected) = christoffersen_test(&exceptions);
+        assert!(!rejected);
+    }
+
+    #[test]
+    fn test_clustered_series_rejected() {
+        let mut exceptions = vec![false; 250];
+        for e in exceptions.iter_mut().skip(100).take(8) {
+            *e = true;
+        }
+        let (_, p_value, rejected) = christoffersen_test(&exceptions);
+        assert!(rejected);
+        assert!(p_value < 1e-3);
+    }
+
+    #[test]
+    fn test_statistic_approximately_chi_squared() {
+        // Under independence the statistic is asymptotically chi-squared(1): mean 1 and a 5%
+        // rejection rate. With rare exceptions the finite-sample test is somewhat oversized.
+        let stats: Vec<(f64, bool)> = (0..1000)
+            .map(|seed| {
+                let (stat, _, rejected) =
+                    christoffersen_test(&independent_exceptions(1000, 0.05, seed));
+                (stat, rejected)
+            })
+            .collect();
+        let mean = stats.iter().map(|s| s.0).sum::<f64>() / stats.len() as f64;
+        let rate = stats.iter().filter(|s| s.1).count() as f64 / stats.len() as f64;
+        assert!((0.7..1.4).contains(&mean), "mean statistic={}", mean);
+        assert!((0.02..0.12).contains(&rate), "rejection rate={}", rate);
+    }
+}
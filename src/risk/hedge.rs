@@ -0,0 +1,380 @@
+use crate::options::monte_carlo::sample_variance;
+use crate::options::{black_scholes, black_scholes_delta};
+use crate::risk::var::beta;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// Simulates discrete delta hedging of a short European call along a spot path.
+///
+/// At time 0 the hedger sells the call at its Black-Scholes price and buys delta shares,
+/// financing the difference in a cash account accruing at `r`. The hedge is rebalanced to the
+/// Black-Scholes delta every `rebalance_freq` steps. The path is assumed to be observed on a
+/// uniform grid from time 0 to expiry `t`.
+///
+/// # Arguments
+///  - s_path: spot prices from time 0 to expiry, inclusive
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility used for pricing and hedging
+///  - rebalance_freq: number of path steps between rebalances (1 rebalances every step)
+///
+/// # Returns
+/// Mark-to-market P&L of the hedged position at each point of the path
+///
+/// # Panics
+/// If `s_path` has fewer than two points.
+pub fn simulate_delta_hedge(
+    s_path: &[f64],
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma: f64,
+    rebalance_freq: usize,
+) -> Vec<f64> {
+    assert!(
+        s_path.len() >= 2,
+        "the path must hold at least the spot today and at expiry"
+    );
+    let n_steps = s_path.len() - 1;
+    let dt = t / n_steps as f64;
+    let growth = (r * dt).exp();
+    let rebalance_freq = rebalance_freq.max(1);
+
+    let (premium, _) = black_scholes(s_path[0], k, t, r, sigma);
+    let mut shares = black_scholes_delta(s_path[0], k, t, r, sigma).0;
+    let mut cash = premium - shares * s_path[0];
+
+    let mut pnl = Vec::with_capacity(n_steps + 1);
+    pnl.push(0.0);
+    for (i, &s) in s_path.iter().enumerate().skip(1) {
+        cash *= growth;
+        let tau = t - i as f64 * dt;
+        let (call, _) = black_scholes(s, k, tau, r, sigma);
+        pnl.push(cash + shares * s - call);
+        if i < n_steps && i % rebalance_freq == 0 {
+            let new_shares = black_scholes_delta(s, k, tau, r, sigma).0;
+            cash -= (new_shares - shares) * s;
+            shares = new_shares;
+        }
+    }
+    pnl
+}
+
+/// Monte Carlo distribution of the terminal P&L of a delta-hedged long European call.
+///
+/// The call is bought at its Black-Scholes price at `sigma_hedge` and hedged to the
+/// Black-Scholes delta at that volatility on `rebal_freq` equally spaced dates, while the spot
+/// follows a risk-neutral GBM with volatility `sigma_realized`. Each path is run through
+/// [`simulate_delta_hedge`] and the sign flipped, so the P&L is that of the long-gamma book,
+/// valued at expiry. To first order it is `1/2 * int exp(r(T-t)) Gamma S^2 (sigma_realized^2 -
+/// sigma_hedge^2) dt` plus a discrete hedging error whose variance is of order
+/// `Gamma^2 S^4 sigma_realized^4 T dt / 2`.
+///
+/// # Arguments
+///  - s0: initial spot price
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma_hedge: volatility used for pricing and hedging
+///  - sigma_realized: volatility of the simulated spot paths
+///  - rebal_freq: number of rebalancing intervals over the life of the option
+///  - n_sims: number of simulated paths
+///  - seed: RNG seed
+///
+/// # Returns
+/// Terminal P&L of each path
+#[allow(clippy::too_many_arguments)]
+pub fn delta_hedge_pnl_distribution(
+    s0: f64,
+    k: f64,
+    t: f64,
+    r: f64,
+    sigma_hedge: f64,
+    sigma_realized: f64,
+    rebal_freq: usize,
+    n_sims: u64,
+    seed: u64,
+) -> Vec<f64> {
+    let n_steps = rebal_freq.max(1);
+    let dt = t / n_steps as f64;
+    let drift = (r - 0.5 * sigma_realized * sigma_realized) * dt;
+    let diffusion = sigma_realized * dt.sqrt();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut path = vec![s0; n_steps + 1];
+    (0..n_sims)
+        .map(|_| {
+            for i in 1..=n_steps {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                path[i] = path[i - 1] * (drift + diffusion * z).exp();
+            }
+            -simulate_delta_hedge(&path, k, t, r, sigma_hedge, 1)[n_steps]
+        })
+        .collect()
+}
+
+/// Variance-optimal number of shares per short call when the stock drifts at `mu` instead of `r`.
+///
+/// With the market price of risk scaled as `lambda = (mu - r) / sigma^2`, the hedge is
+/// `delta + lambda * (V - delta * S) / S`, where `V` is the Black-Scholes call value. For
+/// `mu = r` this is the Black-Scholes delta. For a positive risk premium it holds fewer shares,
+/// because some of the stock's excess return is kept rather than hedged away. The cash position
+/// `V - delta * S` is divided by `S` so that the correction is a number of shares.
+///
+/// # Arguments
+///  - s: spot price (S)
+///  - k: strike (K)
+///  - t: time to maturity in years (T)
+///  - r: continuously compounded risk-free rate
+///  - sigma: volatility of the underlying asset
+///  - mu: expected (real-world) return of the underlying asset
+///
+/// # Returns
+/// Hedge ratio in shares per option
+pub fn variance_optimal_hedge_ratio(s: f64, k: f64, t: f64, r: f64, sigma: f64, mu: f64) -> f64 {
+    let (call, _) = black_scholes(s, k, t, r, sigma);
+    let (delta, _) = black_scholes_delta(s, k, t, r, sigma);
+    delta + (mu - r) / (sigma * sigma) * (call - delta * s) / s
+}
+
+/// Minimum variance hedge ratio, the OLS slope `cov(asset, hedge) / var(hedge)`.
+///
+/// Holding `-h` units of the hedge per unit of the asset minimises the variance of the hedged
+/// position's returns.
+pub fn minimum_variance_hedge_ratio(asset_returns: &[f64], hedge_returns: &[f64]) -> f64 {
+    beta(asset_returns, hedge_returns)
+}
+
+/// Hedge effectiveness, the R-squared of regressing asset returns on hedge returns.
+///
+/// The fraction of the asset's return variance removed by the minimum variance hedge: 1 for a
+/// perfect hedge and 0 for an uncorrelated one.
+pub fn hedge_effectiveness(asset_returns: &[f64], hedge_returns: &[f64]) -> f64 {
+    let h = minimum_variance_hedge_ratio(asset_returns, hedge_returns);
+    let residuals: Vec<f64> = asset_returns
+        .iter()
+        .zip(hedge_returns)
+        .map(|(a, x)| a - h * x)
+        .collect();
+    1.0 - sample_variance(&residuals) / sample_variance(asset_returns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes_gamma;
+    use crate::options::monte_carlo::mean_std_err;
+    use crate::simulation::correlated_gbm_paths;
+    use crate::test_utils::normal_sample;
+    use approx::assert_relative_eq;
+
+    fn gbm_paths(
+        s0: f64,
+        mu: f64,
+        sigma: f64,
+        t: f64,
+        n_steps: usize,
+        n_paths: usize,
+        seed: u64,
+    ) -> Vec<Vec<f64>> {
+        correlated_gbm_paths(
+            &[s0],
+            &[mu],
+            &[sigma],
+            &[vec![1.0]],
+            t,
+            n_steps,
+            n_paths,
+            seed,
+        )
+        .into_iter()
+        .map(|mut p| p.remove(0))
+        .collect()
+    }
+
+    fn terminal_pnls(paths: &[Vec<f64>], rebalance_freq: usize) -> Vec<f64> {
+        paths
+            .iter()
+            .map(|p| {
+                *simulate_delta_hedge(p, 100.0, 0.25, 0.03, 0.2, rebalance_freq)
+                    .last()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_frequent_rebalancing_reduces_variance() {
+        let paths = gbm_paths(100.0, 0.08, 0.2, 0.25, 60, 500, 1);
+        let (_, daily_se) = mean_std_err(&terminal_pnls(&paths, 1));
+        let (_, weekly_se) = mean_std_err(&terminal_pnls(&paths, 5));
+        let (_, monthly_se) = mean_std_err(&terminal_pnls(&paths, 20));
+        assert!(daily_se < weekly_se && weekly_se < monthly_se);
+    }
+
+    #[test]
+    fn test_mean_pnl_close_to_zero() {
+        let paths = gbm_paths(100.0, 0.08, 0.2, 0.25, 60, 2_000, 2);
+        let (mean, se) = mean_std_err(&terminal_pnls(&paths, 1));
+        assert!(mean.abs() < 3.0 * se, "mean={} se={}", mean, se);
+    }
+
+    #[test]
+    fn test_large_move_dominated_by_gamma() {
+        // A sudden 5% move over a tiny time step: the short-gamma loss is -0.5 * gamma * dS^2.
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 0.25, 0.03, 0.2);
+        let mut path = vec![105.0; 1001];
+        path[0] = s0;
+        let pnl = simulate_delta_hedge(&path, k, t, r, sigma, 1);
+        let gamma = black_scholes_gamma(s0, k, t, r, sigma);
+        assert!(pnl[1] < 0.0);
+        assert_relative_eq!(pnl[1], -0.5 * gamma * 25.0, max_relative = 0.15);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least the spot today and at expiry")]
+    fn test_path_without_expiry_panics() {
+        simulate_delta_hedge(&[100.0], 100.0, 0.25, 0.03, 0.2, 1);
+    }
+
+    #[test]
+    fn test_vol_mismatch_mean_pnl() {
+        let (s0, k, t, r) = (100.0, 100.0, 0.5, 0.03);
+        for &(sigma_hedge, sigma_realized) in &[(0.2, 0.3), (0.2, 0.15), (0.25, 0.25)] {
+            let pnl = delta_hedge_pnl_distribution(
+                s0,
+                k,
+                t,
+                r,
+                sigma_hedge,
+                sigma_realized,
+                50,
+                4_000,
+                8,
+            );
+            let (mean, se) = mean_std_err(&pnl);
+            // Hedging at the wrong vol earns the price difference, carried to expiry.
+            let expected = (r * t).exp()
+                * (black_scholes(s0, k, t, r, sigma_realized).0
+                    - black_scholes(s0, k, t, r, sigma_hedge).0);
+            assert!(
+                (mean - expected).abs() < 4.0 * se + 0.02,
+                "hedge={} realized={} mean={} expected={}",
+                sigma_hedge,
+                sigma_realized,
+                mean,
+                expected
+            );
+            if sigma_realized > sigma_hedge {
+                assert!(mean > 3.0 * se);
+            } else if sigma_realized < sigma_hedge {
+                assert!(mean < -3.0 * se);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hedging_error_variance_scaling() {
+        // Var ~ c * Gamma^2 S^4 sigma^4 T dt / 2 with the same c across contracts and frequencies;
+        // c exceeds 1 because gamma grows near expiry.
+        let mut ratios = Vec::new();
+        for &(k, t, sigma, n) in &[
+            (100.0, 0.5, 0.2, 20),
+            (100.0, 0.5, 0.2, 80),
+            (100.0, 0.5, 0.3, 40),
+            (110.0, 1.0, 0.25, 40),
+        ] {
+            let pnl = delta_hedge_pnl_distribution(100.0, k, t, 0.03, sigma, sigma, n, 4_000, 9);
+            let gamma = black_scholes_gamma(100.0, k, t, 0.03, sigma);
+            let scale = 0.5 * gamma.powi(2) * 100.0_f64.powi(4) * sigma.powi(4) * t * t / n as f64;
+            ratios.push(sample_variance(&pnl) / scale);
+        }
+        for &ratio in &ratios {
+            assert!((1.0..2.2).contains(&ratio), "{:?}", ratios);
+        }
+        // Quadrupling the rebalancing frequency quarters the variance.
+        assert_relative_eq!(ratios[0], ratios[1], max_relative = 0.25);
+    }
+
+    #[test]
+    fn test_hedging_error_approximately_normal() {
+        let pnl = delta_hedge_pnl_distribution(100.0, 100.0, 0.5, 0.03, 0.2, 0.2, 50, 5_000, 10);
+        let (mean, _) = mean_std_err(&pnl);
+        let sd = sample_variance(&pnl).sqrt();
+        let n = pnl.len() as f64;
+        let skew = pnl.iter().map(|x| ((x - mean) / sd).powi(3)).sum::<f64>() / n;
+        let kurtosis = pnl.iter().map(|x| ((x - mean) / sd).powi(4)).sum::<f64>() / n;
+        let within = pnl.iter().filter(|x| (*x - mean).abs() < sd).count() as f64 / n;
+        assert!(skew.abs() < 0.5, "skew={}", skew);
+        assert!(kurtosis < 6.0, "kurtosis={}", kurtosis);
+        assert!((0.64..0.8).contains(&within), "within one sd: {}", within);
+    }
+
+    #[test]
+    fn test_perfect_hedge() {
+        let hedge = normal_sample(500, 0.01, 3);
+        let asset: Vec<f64> = hedge.iter().map(|x| 1.5 * x).collect();
+        assert_relative_eq!(
+            minimum_variance_hedge_ratio(&asset, &hedge),
+            1.5,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(hedge_effectiveness(&asset, &hedge), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_uncorrelated_hedge() {
+        let asset = normal_sample(5_000, 0.01, 4);
+        let hedge = normal_sample(5_000, 0.01, 5);
+        assert!(hedge_effectiveness(&asset, &hedge) < 1e-3);
+    }
+
+    #[test]
+    fn test_beta_recovered_from_correlated_returns() {
+        let hedge = normal_sample(10_000, 0.01, 6);
+        let noise = normal_sample(10_000, 0.01, 7);
+        let asset: Vec<f64> = hedge
+            .iter()
+            .zip(&noise)
+            .map(|(x, e)| 0.8 * x + 0.6 * e)
+            .collect();
+        assert_relative_eq!(
+            minimum_variance_hedge_ratio(&asset, &hedge),
+            0.8,
+            epsilon = 0.02
+        );
+        // R^2 = 0.8^2 / (0.8^2 + 0.6^2) = 0.64.
+        assert_relative_eq!(hedge_effectiveness(&asset, &hedge), 0.64, epsilon = 0.02);
+    }
+
+    #[test]
+    fn test_variance_optimal_reduces_to_delta_when_risk_neutral() {
+        for &k in &[80.0, 100.0, 120.0] {
+            let (delta, _) = black_scholes_delta(100.0, k, 0.5, 0.03, 0.25);
+            assert_relative_eq!(
+                variance_optimal_hedge_ratio(100.0, k, 0.5, 0.03, 0.25, 0.03),
+                delta,
+                epsilon = 1e-14
+            );
+        }
+    }
+
+    #[test]
+    fn test_variance_optimal_call_ratio_in_unit_interval() {
+        // lambda = (mu - r) / sigma^2 stays in [0, 1] for these drifts.
+        for &mu in &[0.03, 0.05, 0.08] {
+            for k in (0..9).map(|i| 60.0 + 10.0 * i as f64) {
+                let h = variance_optimal_hedge_ratio(100.0, k, 1.0, 0.03, 0.25, mu);
+                assert!((0.0..=1.0).contains(&h), "mu={} k={} h={}", mu, k, h);
+            }
+        }
+    }
+
+    #[test]
+    fn test_variance_optimal_sign_change_out_of_the_money() {
+        let (s, k, t, r, sigma) = (100.0, 130.0, 0.5, 0.03, 0.2);
+        assert!(variance_optimal_hedge_ratio(s, k, t, r, sigma, r) > 0.0);
+        assert!(variance_optimal_hedge_ratio(s, k, t, r, sigma, 0.5) < 0.0);
+    }
+}
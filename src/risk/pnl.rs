@@ -0,0 +1,106 @@
+use crate::options::black_scholes::Greeks;
+
+/// Breakdown of a position's P&L over one period into risk factor contributions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PnlAttribution {
+    /// First-order spot contribution, `delta * dS`.
+    pub delta_pnl: f64,
+    /// Second-order spot contribution, `gamma * dS^2 / 2`.
+    pub gamma_pnl: f64,
+    /// Time decay, `theta * dt`.
+    pub theta_pnl: f64,
+    /// Volatility contribution, `vega * d_sigma`.
+    pub vega_pnl: f64,
+    /// Residual not explained by the Greeks (higher-order and cross terms, rates, model error).
+    pub unexplained: f64,
+}
+
+/// Attributes a realised P&L to the Greeks of the position at the start of the period.
+///
+/// The Taylor expansion `delta dS + gamma dS^2 / 2 + theta dt + vega d_sigma` explains the
+/// move; whatever is left of `actual_pnl` is reported as unexplained, so the components always
+/// sum to `actual_pnl`.
+///
+/// # Arguments
+///  - greeks: Greeks at the start of the period, with theta per year
+///  - ds: change in spot
+///  - d_sigma: change in implied volatility
+///  - dt: elapsed time in years
+///  - actual_pnl: realised change in the position's value
+pub fn attribute_pnl(
+    greeks: &Greeks,
+    ds: f64,
+    d_sigma: f64,
+    dt: f64,
+    actual_pnl: f64,
+) -> PnlAttribution {
+    let delta_pnl = greeks.delta * ds;
+    let gamma_pnl = 0.5 * greeks.gamma * ds * ds;
+    let theta_pnl = greeks.theta * dt;
+    let vega_pnl = greeks.vega * d_sigma;
+    PnlAttribution {
+        delta_pnl,
+        gamma_pnl,
+        theta_pnl,
+        vega_pnl,
+        unexplained: actual_pnl - delta_pnl - gamma_pnl - theta_pnl - vega_pnl,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{black_scholes, black_scholes_greeks};
+    use approx::assert_relative_eq;
+
+    const K: f64 = 100.0;
+    const R: f64 = 0.03;
+
+    fn call(s: f64, t: f64, sigma: f64) -> f64 {
+        black_scholes(s, K, t, R, sigma).0
+    }
+
+    fn total(a: &PnlAttribution) -> f64 {
+        a.delta_pnl + a.gamma_pnl + a.theta_pnl + a.vega_pnl + a.unexplained
+    }
+
+    #[test]
+    fn test_components_sum_to_actual() {
+        let greeks = black_scholes_greeks(100.0, K, 0.5, R, 0.2, true);
+        let (ds, d_sigma, dt) = (1.8, -0.01, 1.0 / 252.0);
+        let actual = call(100.0 + ds, 0.5 - dt, 0.2 + d_sigma) - call(100.0, 0.5, 0.2);
+        let pnl = attribute_pnl(&greeks, ds, d_sigma, dt, actual);
+        assert_relative_eq!(total(&pnl), actual, epsilon = 1e-14);
+        assert!(pnl.unexplained.abs() < 0.01 * actual.abs());
+    }
+
+    #[test]
+    fn test_pure_spot_move() {
+        let greeks = black_scholes_greeks(100.0, K, 1.0, R, 0.25, true);
+        let actual = call(97.0, 1.0, 0.25) - call(100.0, 1.0, 0.25);
+        let pnl = attribute_pnl(&greeks, -3.0, 0.0, 0.0, actual);
+        assert_eq!(pnl.vega_pnl, 0.0);
+        assert_eq!(pnl.theta_pnl, 0.0);
+        assert_relative_eq!(pnl.delta_pnl, -3.0 * greeks.delta);
+        assert_relative_eq!(total(&pnl), actual, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_residual_shrinks_with_order() {
+        let (s, t, sigma) = (100.0, 0.75, 0.2);
+        let greeks = black_scholes_greeks(s, K, t, R, sigma, true);
+        let (ds, d_sigma, dt) = (4.0, 0.02, 2.0 / 252.0);
+        let actual = call(s + ds, t - dt, sigma + d_sigma) - call(s, t, sigma);
+        let pnl = attribute_pnl(&greeks, ds, d_sigma, dt, actual);
+        // Residuals after adding each order of the expansion.
+        let first_order = actual - pnl.delta_pnl;
+        let with_gamma = first_order - pnl.gamma_pnl;
+        let with_time_and_vol = with_gamma - pnl.theta_pnl - pnl.vega_pnl;
+        assert_relative_eq!(with_time_and_vol, pnl.unexplained, epsilon = 1e-14);
+        assert!(with_time_and_vol.abs() < first_order.abs());
+        // A pure spot move isolates the gamma term.
+        let spot_only = call(s + ds, t, sigma) - call(s, t, sigma);
+        let spot_pnl = attribute_pnl(&greeks, ds, 0.0, 0.0, spot_only);
+        assert!(spot_pnl.unexplained.abs() < (spot_only - spot_pnl.delta_pnl).abs() / 10.0);
+    }
+}
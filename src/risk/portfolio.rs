@@ -0,0 +1,223 @@
+use crate::optimization::{auto_step, greek_fd_richardson, second_derivative_richardson};
+use crate::options::black_scholes::black_scholes_gamma;
+use crate::options::multi_asset::kirk_spread_option;
+
+/// A position in a multi-asset option portfolio, with assets referred to by index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MultiAssetPosition {
+    /// `quantity` European calls on asset `asset`.
+    Call {
+        asset: usize,
+        quantity: f64,
+        k: f64,
+        t: f64,
+    },
+    /// `quantity` spread calls paying `max(S_long - S_short - K, 0)`, priced with
+    /// [`kirk_spread_option`].
+    Spread {
+        long: usize,
+        short: usize,
+        quantity: f64,
+        k: f64,
+        t: f64,
+    },
+}
+
+/// Cross-gamma matrix `d2V / (dS_i dS_j)` of a multi-asset option portfolio.
+///
+/// A call only depends on its own asset and contributes its Black-Scholes gamma to the
+/// diagonal. A spread depends on two spots; its three second derivatives are nested
+/// Richardson-extrapolated central differences of the Kirk price, which uses the correlation
+/// of its two assets, so cross-gammas only arise from spreads.
+///
+/// # Arguments
+///  - spots: spot price of each asset
+///  - vols: volatility of each asset
+///  - correlations: correlation matrix of the assets' returns
+///  - r: continuously compounded risk-free rate
+///  - positions: positions referring to assets by index
+///
+/// # Returns
+/// Symmetric `n x n` matrix of second derivatives, `n` being the number of assets
+///
+/// # Panics
+/// If `vols` or `correlations` do not match the number of assets, or a position refers to an
+/// asset that does not exist.
+pub fn cross_gamma_matrix(
+    spots: &[f64],
+    vols: &[f64],
+    correlations: &[Vec<f64>],
+    r: f64,
+    positions: &[MultiAssetPosition],
+) -> Vec<Vec<f64>> {
+    let n = spots.len();
+    assert!(vols.len() == n, "vols must have one entry per asset");
+    assert!(
+        correlations.len() == n && correlations.iter().all(|row| row.len() == n),
+        "correlations must be an n x n matrix"
+    );
+    let mut gamma = vec![vec![0.0; n]; n];
+    for position in positions {
+        match *position {
+            MultiAssetPosition::Call {
+                asset,
+                quantity,
+                k,
+                t,
+            } => {
+                gamma[asset][asset] +=
+                    quantity * black_scholes_gamma(spots[asset], k, t, r, vols[asset]);
+            }
+            MultiAssetPosition::Spread {
+                long,
+                short,
+                quantity,
+                k,
+                t,
+            } => {
+                let (s1, s2) = (spots[long], spots[short]);
+                let rho = correlations[long][short];
+                let value = |x1: f64, x2: f64| {
+                    kirk_spread_option(x1, x2, k, t, r, vols[long], vols[short], rho, true)
+                };
+                let (h1, h2) = (auto_step(s1), auto_step(s2));
+                let cross = greek_fd_richardson(
+                    |x2| greek_fd_richardson(|x1| value(x1, x2), s1, h1),
+                    s2,
+                    h2,
+                );
+                gamma[long][long] +=
+                    quantity * second_derivative_richardson(|x| value(x, s2), s1, 0.05 * s1);
+                gamma[short][short] +=
+                    quantity * second_derivative_richardson(|x| value(s1, x), s2, 0.05 * s2);
+                gamma[long][short] += quantity * cross;
+                gamma[short][long] += quantity * cross;
+            }
+        }
+    }
+    gamma
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+    use approx::assert_relative_eq;
+
+    const SPOTS: [f64; 3] = [100.0, 95.0, 50.0];
+    const VOLS: [f64; 3] = [0.2, 0.25, 0.35];
+    const R: f64 = 0.03;
+
+    fn correlations(rho: f64) -> Vec<Vec<f64>> {
+        (0..3)
+            .map(|i| (0..3).map(|j| if i == j { 1.0 } else { rho }).collect())
+            .collect()
+    }
+
+    fn portfolio() -> Vec<MultiAssetPosition> {
+        vec![
+            MultiAssetPosition::Call {
+                asset: 0,
+                quantity: 2.0,
+                k: 105.0,
+                t: 0.5,
+            },
+            MultiAssetPosition::Call {
+                asset: 2,
+                quantity: -1.0,
+                k: 45.0,
+                t: 1.0,
+            },
+            MultiAssetPosition::Spread {
+                long: 0,
+                short: 1,
+                quantity: 3.0,
+                k: 2.0,
+                t: 0.75,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_calls_only_diagonal() {
+        let positions: Vec<_> = portfolio()
+            .into_iter()
+            .filter(|p| matches!(p, MultiAssetPosition::Call { .. }))
+            .collect();
+        let gamma = cross_gamma_matrix(&SPOTS, &VOLS, &correlations(0.5), R, &positions);
+        assert_eq!(
+            gamma[0][0],
+            2.0 * black_scholes_gamma(100.0, 105.0, 0.5, R, 0.2)
+        );
+        assert_eq!(gamma[1][1], 0.0);
+        assert_eq!(gamma[2][2], -black_scholes_gamma(50.0, 45.0, 1.0, R, 0.35));
+        for (i, row) in gamma.iter().enumerate() {
+            for (j, &g) in row.iter().enumerate() {
+                if i != j {
+                    assert_eq!(g, 0.0);
+                }
+            }
+        }
+        // Diagonal gammas agree with a finite difference of the call price.
+        let fd =
+            second_derivative_richardson(|s| black_scholes(s, 105.0, 0.5, R, 0.2).0, 100.0, 5.0);
+        assert_relative_eq!(gamma[0][0], 2.0 * fd, max_relative = 1e-6);
+    }
+
+    #[test]
+    fn test_spread_cross_gamma() {
+        let gamma = cross_gamma_matrix(&SPOTS, &VOLS, &correlations(0.5), R, &portfolio());
+        for (i, row) in gamma.iter().enumerate() {
+            for (j, &g) in row.iter().enumerate() {
+                assert_eq!(g, gamma[j][i]);
+            }
+        }
+        // Long a spread call: long convexity in each asset, negative cross-gamma.
+        assert!(gamma[0][1] < 0.0);
+        assert!(gamma[1][1] > 0.0);
+        assert_eq!(gamma[0][2], 0.0);
+        assert_eq!(gamma[1][2], 0.0);
+        // The cross term matches a plain four-point difference of the spread value.
+        let value =
+            |x1: f64, x2: f64| 3.0 * kirk_spread_option(x1, x2, 2.0, 0.75, R, 0.2, 0.25, 0.5, true);
+        let h = 0.5;
+        let four_point =
+            (value(100.0 + h, 95.0 + h) - value(100.0 + h, 95.0 - h) - value(100.0 - h, 95.0 + h)
+                + value(100.0 - h, 95.0 - h))
+                / (4.0 * h * h);
+        assert_relative_eq!(gamma[0][1], four_point, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn test_exchange_option_homogeneity() {
+        // With a zero strike the spread is homogeneous of degree one in the spots, so
+        // S1 * G11 + S2 * G12 = 0.
+        let positions = [MultiAssetPosition::Spread {
+            long: 0,
+            short: 1,
+            quantity: 1.0,
+            k: 0.0,
+            t: 1.0,
+        }];
+        let gamma = cross_gamma_matrix(&SPOTS, &VOLS, &correlations(0.3), R, &positions);
+        assert_relative_eq!(
+            SPOTS[0] * gamma[0][0],
+            -SPOTS[1] * gamma[0][1],
+            max_relative = 1e-5
+        );
+        assert_relative_eq!(
+            SPOTS[1] * gamma[1][1],
+            -SPOTS[0] * gamma[0][1],
+            max_relative = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_correlation_changes_spread_gamma() {
+        let low = cross_gamma_matrix(&SPOTS, &VOLS, &correlations(-0.5), R, &portfolio());
+        let high = cross_gamma_matrix(&SPOTS, &VOLS, &correlations(0.9), R, &portfolio());
+        // A more correlated spread has lower volatility and more concentrated convexity.
+        assert!(high[0][1] < low[0][1]);
+        assert_eq!(high[2][2], low[2][2]);
+    }
+}
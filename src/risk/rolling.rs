@@ -0,0 +1,116 @@
+use crate::risk::var::historical_var;
+
+/// Historical VaR over a rolling window of P&L.
+///
+/// Element `i` is the [`historical_var`] of `pnl[i + 1 - window..=i]`.
+///
+/// # Returns
+/// A series of the same length as `pnl`, with `NaN` for the first `window - 1` elements
+pub fn rolling_historical_var(pnl: &[f64], window: usize, confidence: f64) -> Vec<f64> {
+    rolling(pnl, window, |w| historical_var(w, confidence))
+}
+
+/// Sample volatility (standard deviation around zero mean) of returns over a rolling window.
+///
+/// # Returns
+/// A series of the same length as `returns`, with `NaN` for the first `window - 1` elements
+pub fn rolling_historical_vol(returns: &[f64], window: usize) -> Vec<f64> {
+    rolling(returns, window, |w| {
+        (w.iter().map(|r| r * r).sum::<f64>() / w.len() as f64).sqrt()
+    })
+}
+
+/// Exponentially weighted volatility of returns over a rolling window.
+///
+/// Within each window the squared return observed `i` days ago gets weight proportional to
+/// `lambda^i`, normalised so the weights sum to one; `lambda = 0.94` is the RiskMetrics choice.
+///
+/// # Returns
+/// A series of the same length as `returns`, with `NaN` for the first `window - 1` elements
+pub fn rolling_ewma_vol(returns: &[f64], window: usize, lambda: f64) -> Vec<f64> {
+    let weight_sum: f64 = (0..window).map(|i| lambda.powi(i as i32)).sum();
+    rolling(returns, window, |w| {
+        let variance: f64 = w
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, r)| lambda.powi(i as i32) * r * r)
+            .sum::<f64>()
+            / weight_sum;
+        variance.sqrt()
+    })
+}
+
+fn rolling(series: &[f64], window: usize, stat: impl Fn(&[f64]) -> f64) -> Vec<f64> {
+    assert!(window > 0, "window must be positive");
+    (0..series.len())
+        .map(|i| {
+            if i + 1 < window {
+                f64::NAN
+            } else {
+                stat(&series[i + 1 - window..=i])
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, Normal};
+
+    /// Calm returns with a high-volatility regime in the middle.
+    fn regime_change_returns() -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(4);
+        let calm = Normal::new(0.0, 0.01).unwrap();
+        let stressed = Normal::new(0.0, 0.05).unwrap();
+        (0..400)
+            .map(|i| {
+                if (200..220).contains(&i) {
+                    stressed.sample(&mut rng)
+                } else {
+                    calm.sample(&mut rng)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rolling_var_length_and_warmup() {
+        let pnl = regime_change_returns();
+        let var = rolling_historical_var(&pnl, 100, 0.99);
+        assert_eq!(var.len(), pnl.len());
+        assert!(var[..99].iter().all(|v| v.is_nan()));
+        assert!(var[99..].iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_rolling_var_reacts_to_losses() {
+        let pnl = regime_change_returns();
+        let var = rolling_historical_var(&pnl, 100, 0.99);
+        assert!(
+            var[230] > 2.0 * var[190],
+            "before={} after={}",
+            var[190],
+            var[230]
+        );
+    }
+
+    #[test]
+    fn test_ewma_vol_decays_faster() {
+        let returns = regime_change_returns();
+        let ewma = rolling_ewma_vol(&returns, 100, 0.94);
+        let hist = rolling_historical_vol(&returns, 100);
+        // Right after the stress both measures are elevated...
+        assert!(ewma[219] > hist[219]);
+        // ...but 40 days later the EWMA estimate has largely forgotten it.
+        assert!(
+            ewma[260] < 0.75 * hist[260],
+            "ewma={} hist={}",
+            ewma[260],
+            hist[260]
+        );
+    }
+}
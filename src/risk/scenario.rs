@@ -0,0 +1,121 @@
+use crate::options::black_scholes;
+use crate::options::params::OptionParams;
+
+/// A stress scenario applied to the inputs of an option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scenario {
+    /// Relative spot move, e.g. `-0.3` for a 30% drop.
+    pub spot_shock: f64,
+    /// Relative volatility move, e.g. `0.5` for vol rising by half of its level.
+    pub vol_shock: f64,
+    /// Absolute rate move, e.g. `0.01` for +100bp.
+    pub rate_shock: f64,
+}
+
+/// Reprices a European call and put under a stressed market.
+///
+/// Spot becomes `s * (1 + spot_shock)`, volatility `sigma * (1 + vol_shock)` and the rate
+/// `r + rate_shock`; strike and maturity are unchanged.
+///
+/// # Returns
+/// (call_price, put_price) under the scenario
+pub fn apply_scenario(params: &OptionParams, scenario: &Scenario) -> (f64, f64) {
+    black_scholes(
+        params.s * (1.0 + scenario.spot_shock),
+        params.k,
+        params.t,
+        params.r + scenario.rate_shock,
+        params.sigma * (1.0 + scenario.vol_shock),
+    )
+}
+
+/// Runs [`apply_scenario`] for each scenario.
+///
+/// # Returns
+/// One (call_price, put_price) pair per scenario
+pub fn batch_scenarios(params: &OptionParams, scenarios: &[Scenario]) -> Vec<(f64, f64)> {
+    scenarios
+        .iter()
+        .map(|sc| apply_scenario(params, sc))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{black_scholes_delta, black_scholes_gamma, black_scholes_vega};
+    use approx::assert_relative_eq;
+
+    const PARAMS: OptionParams = OptionParams {
+        s: 100.0,
+        k: 100.0,
+        t: 1.0,
+        r: 0.05,
+        sigma: 0.2,
+    };
+
+    #[test]
+    fn test_spot_shock() {
+        let (call, put) = black_scholes(PARAMS.s, PARAMS.k, PARAMS.t, PARAMS.r, PARAMS.sigma);
+        let up = Scenario {
+            spot_shock: 0.3,
+            vol_shock: 0.0,
+            rate_shock: 0.0,
+        };
+        let (call_up, put_up) = apply_scenario(&PARAMS, &up);
+        assert!(call_up > call);
+        assert!(put_up < put);
+    }
+
+    #[test]
+    fn test_vol_shock() {
+        let (call, put) = black_scholes(PARAMS.s, PARAMS.k, PARAMS.t, PARAMS.r, PARAMS.sigma);
+        let vol_up = Scenario {
+            spot_shock: 0.0,
+            vol_shock: 0.5,
+            rate_shock: 0.0,
+        };
+        let (call_up, put_up) = apply_scenario(&PARAMS, &vol_up);
+        assert!(call_up > call);
+        assert!(put_up > put);
+    }
+
+    #[test]
+    fn test_small_shock_matches_taylor_expansion() {
+        let p = PARAMS;
+        let (call, _) = black_scholes(p.s, p.k, p.t, p.r, p.sigma);
+        let scenario = Scenario {
+            spot_shock: 0.01,
+            vol_shock: 0.02,
+            rate_shock: 0.0,
+        };
+        let ds = p.s * scenario.spot_shock;
+        let d_sigma = p.sigma * scenario.vol_shock;
+        let taylor = black_scholes_delta(p.s, p.k, p.t, p.r, p.sigma).0 * ds
+            + 0.5 * black_scholes_gamma(p.s, p.k, p.t, p.r, p.sigma) * ds * ds
+            + black_scholes_vega(p.s, p.k, p.t, p.r, p.sigma) * d_sigma;
+        let (call_shocked, _) = apply_scenario(&p, &scenario);
+        assert_relative_eq!(call_shocked - call, taylor, max_relative = 1e-2);
+    }
+
+    #[test]
+    fn test_batch_matches_individual() {
+        let scenarios = [
+            Scenario {
+                spot_shock: -0.2,
+                vol_shock: 0.5,
+                rate_shock: -0.01,
+            },
+            Scenario {
+                spot_shock: 0.1,
+                vol_shock: -0.2,
+                rate_shock: 0.02,
+            },
+        ];
+        let batch = batch_scenarios(&PARAMS, &scenarios);
+        assert_eq!(batch.len(), 2);
+        for (result, sc) in batch.iter().zip(&scenarios) {
+            assert_eq!(*result, apply_scenario(&PARAMS, sc));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+/// Empirical quantile of `sample` at probability `p`, interpolating linearly between order statistics.
+pub(crate) fn empirical_quantile(sample: &[f64], p: f64) -> f64 {
+    let mut sorted = sample.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let pos = p.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (pos - lo as f64) * (sorted[hi] - sorted[lo])
+}
+
+/// Historical simulation Value-at-Risk.
+///
+/// The loss that is not exceeded with probability `confidence`, estimated from the empirical
+/// distribution of P&L: `VaR = -quantile(pnl, 1 - confidence)`. Losses are reported as positive
+/// numbers.
+///
+/// # Arguments
+///  - pnl: P&L observations (profits positive, losses negative)
+///  - confidence: VaR confidence level, e.g. 0.99
+///
+/// # Returns
+/// (var)
+pub fn historical_var(pnl: &[f64], confidence: f64) -> f64 {
+    -empirical_quantile(pnl, 1.0 - confidence)
+}
+
+/// Incremental VaR: the change in portfolio VaR from adding a new position,
+/// `VaR(portfolio + position) - VaR(portfolio)`, with both VaRs from historical simulation.
+///
+/// # Arguments
+///  - portfolio_pnl: P&L series of the existing portfolio
+///  - new_position_pnl: P&L series of the new position over the same dates
+///  - confidence: VaR confidence level, e.g. 0.99
+///
+/// # Returns
+/// (incremental_var), negative when the position diversifies the portfolio
+pub fn incremental_var(portfolio_pnl: &[f64], new_position_pnl: &[f64], confidence: f64) -> f64 {
+    let combined: Vec<f64> = portfolio_pnl
+        .iter()
+        .zip(new_position_pnl)
+        .map(|(p, x)| p + x)
+        .collect();
+    historical_var(&combined, confidence) - historical_var(portfolio_pnl, confidence)
+}
+
+/// Marginal VaR: the sensitivity of portfolio VaR to the size of a position.
+///
+/// Uses the linear (beta) approximation `beta * VaR(portfolio)`, where
+/// `beta = cov(position, portfolio) / var(portfolio)` is the regression coefficient of the
+/// position P&L on the portfolio P&L.
+///
+/// # Returns
+/// (marginal_var) per unit of the position whose P&L is given
+pub fn marginal_var(portfolio_pnl: &[f64], position_pnl: &[f64], confidence: f64) -> f64 {
+    beta(position_pnl, portfolio_pnl) * historical_var(portfolio_pnl, confidence)
+}
+
+/// Component VaR: decomposition of historical portfolio VaR into per-position contributions.
+///
+/// The portfolio P&L is the sum of the position P&Ls, and each position contributes
+/// `beta_i * VaR(portfolio)` (see [`marginal_var`]). Since the betas of the positions on their
+/// sum add up to one, the components sum to the total portfolio VaR.
+///
+/// # Arguments
+///  - position_pnl: one P&L series per position, all over the same dates
+///  - confidence: VaR confidence level, e.g. 0.99
+///
+/// # Returns
+/// One contribution per position; diversifying positions have negative contributions
+pub fn component_var(position_pnl: &[Vec<f64>], confidence: f64) -> Vec<f64> {
+    let n_obs = position_pnl.first().map_or(0, |p| p.len());
+    let portfolio: Vec<f64> = (0..n_obs)
+        .map(|t| position_pnl.iter().map(|p| p[t]).sum())
+        .collect();
+    position_pnl
+        .iter()
+        .map(|position| marginal_var(&portfolio, position, confidence))
+        .collect()
+}
+
+/// Regression coefficient `cov(y, x) / var(x)`.
+pub(crate) fn beta(y: &[f64], x: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let mx = x.iter().sum::<f64>() / n;
+    let my = y.iter().sum::<f64>() / n;
+    let cov: f64 = x.iter().zip(y).map(|(xi, yi)| (xi - mx) * (yi - my)).sum();
+    let var: f64 = x.iter().map(|xi| (xi - mx).powi(2)).sum();
+    cov / var
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::normal_sample;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_historical_var() {
+        let pnl: Vec<f64> = (0..101).map(|i| i as f64 - 50.0).collect();
+        assert_relative_eq!(historical_var(&pnl, 0.95), 45.0, epsilon = 1e-12);
+        assert_relative_eq!(historical_var(&pnl, 0.5), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_incremental_var_perfectly_correlated() {
+        let portfolio = normal_sample(1000, 1_000.0, 1);
+        let position: Vec<f64> = portfolio.iter().map(|p| 0.5 * p).collect();
+        assert_relative_eq!(
+            incremental_var(&portfolio, &position, 0.99),
+            historical_var(&position, 0.99),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_incremental_var_hedge_reduces_var() {
+        let portfolio = normal_sample(1000, 1_000.0, 2);
+        let noise = normal_sample(1000, 100.0, 3);
+        let hedge: Vec<f64> = portfolio
+            .iter()
+            .zip(&noise)
+            .map(|(p, e)| -0.5 * p + e)
+            .collect();
+        assert!(incremental_var(&portfolio, &hedge, 0.99) < 0.0);
+        assert!(marginal_var(&portfolio, &hedge, 0.99) < 0.0);
+    }
+
+    #[test]
+    fn test_marginal_var_of_portfolio_itself() {
+        // Scaling the whole portfolio scales VaR one-for-one.
+        let portfolio = normal_sample(500, 1_000.0, 4);
+        assert_relative_eq!(
+            marginal_var(&portfolio, &portfolio, 0.95),
+            historical_var(&portfolio, 0.95),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_component_var_sums_to_total() {
+        let a = normal_sample(750, 1_000.0, 5);
+        let b = normal_sample(750, 400.0, 6);
+        let hedge: Vec<f64> = a
+            .iter()
+            .zip(normal_sample(750, 50.0, 7))
+            .map(|(x, e)| -0.3 * x + e)
+            .collect();
+        let zero = vec![0.0; 750];
+        let positions = vec![a, b, hedge, zero];
+        let components = component_var(&positions, 0.99);
+
+        let portfolio: Vec<f64> = (0..750)
+            .map(|t| positions.iter().map(|p| p[t]).sum())
+            .collect();
+        assert_relative_eq!(
+            components.iter().sum::<f64>(),
+            historical_var(&portfolio, 0.99),
+            epsilon = 1e-8
+        );
+        assert!(components[2] < 0.0, "hedge component={}", components[2]);
+        assert_eq!(components[3], 0.0);
+    }
+}
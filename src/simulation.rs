@@ -0,0 +1,9 @@
+pub mod brownian;
+pub mod brownian_bridge;
+pub mod copula;
+pub mod gbm;
+
+pub use brownian::brownian_paths;
+pub use brownian_bridge::{brownian_bridge, brownian_bridge_paths};
+pub use copula::gaussian_copula_simulate;
+pub use gbm::correlated_gbm_paths;
\ No newline at end of file
@@ -0,0 +1,40 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// Standard Brownian motion paths built sequentially from independent increments.
+///
+/// Each path holds `n_steps + 1` values `W(t_0), ..., W(t_n)` on the uniform grid
+/// `t_i = i * t_end / n_steps`, starting at `W(0) = 0`.
+///
+/// # Returns
+/// `[path_index][time_step]`
+pub fn brownian_paths(t_end: f64, n_steps: usize, n_paths: usize, seed: u64) -> Vec<Vec<f64>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let sqrt_dt = (t_end / n_steps as f64).sqrt();
+    (0..n_paths)
+        .map(|_| {
+            let mut w = 0.0;
+            let mut path = Vec::with_capacity(n_steps + 1);
+            path.push(w);
+            for _ in 0..n_steps {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                w += sqrt_dt * z;
+                path.push(w);
+            }
+            path
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increment_variance() {
+        let paths = brownian_paths(2.0, 4, 20_000, 1);
+        let var = paths.iter().map(|p| (p[2] - p[1]).powi(2)).sum::<f64>() / paths.len() as f64;
+        assert!((var - 0.5).abs() < 0.02, "var={}", var);
+    }
+}
@@ -0,0 +1,181 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, StandardNormal};
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Fills in a Brownian bridge between `w_start` at time 0 and `w_end` at time `t_end`.
+///
+/// Points on the uniform grid with `n_steps` steps are generated by recursive bisection: each
+/// midpoint is drawn from its conditional distribution given the two already known endpoints,
+/// `N(linear interpolation, (t_m - t_l) * (t_r - t_m) / (t_r - t_l))`. `normals` supplies the
+/// standard normal draws in the order they are consumed and must hold at least `n_steps - 1`
+/// values.
+///
+/// # Returns
+/// The `n_steps + 1` path values, with the first equal to `w_start` and the last to `w_end`.
+pub fn brownian_bridge(
+    w_start: f64,
+    w_end: f64,
+    t_end: f64,
+    n_steps: usize,
+    normals: &[f64],
+) -> Vec<f64> {
+    let dt = t_end / n_steps as f64;
+    let mut path = vec![0.0; n_steps + 1];
+    path[0] = w_start;
+    path[n_steps] = w_end;
+
+    let mut normals = normals.iter();
+    // Breadth-first bisection so the coarse structure uses the first draws.
+    let mut intervals = vec![(0, n_steps)];
+    while !intervals.is_empty() {
+        let mut next = Vec::with_capacity(2 * intervals.len());
+        for (l, r) in intervals {
+            if r - l < 2 {
+                continue;
+            }
+            let m = (l + r) / 2;
+            let (tl, tm, tr) = (l as f64 * dt, m as f64 * dt, r as f64 * dt);
+            let mean = ((tr - tm) * path[l] + (tm - tl) * path[r]) / (tr - tl);
+            let sd = ((tm - tl) * (tr - tm) / (tr - tl)).sqrt();
+            let z = *normals
+                .next()
+                .expect("not enough normal draws for the bridge");
+            path[m] = mean + sd * z;
+            next.push((l, m));
+            next.push((m, r));
+        }
+        intervals = next;
+    }
+    path
+}
+
+/// Standard Brownian motion paths constructed with a Brownian bridge.
+///
+/// The terminal value `W(t_end)` is drawn first and the intermediate points are filled in with
+/// [`brownian_bridge`]. The terminal draws are stratified across paths (one draw per equiprobable
+/// stratum of the normal distribution), which removes most of the sampling noise for payoffs
+/// driven mainly by the end of the path, such as Asian options.
+///
+/// # Returns
+/// `[path_index][time_step]` with `n_steps + 1` values per path, starting at 0.
+pub fn brownian_bridge_paths(
+    t_end: f64,
+    n_steps: usize,
+    n_paths: usize,
+    seed: u64,
+) -> Vec<Vec<f64>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let stdn = Normal::new(0.0, 1.0).unwrap();
+    let mut strata: Vec<usize> = (0..n_paths).collect();
+    // Shuffle so path order carries no information about the stratum.
+    for i in (1..n_paths).rev() {
+        strata.swap(i, rng.gen_range(0..=i));
+    }
+    strata
+        .into_iter()
+        .map(|stratum| {
+            let u = (stratum as f64 + rng.gen::<f64>()) / n_paths as f64;
+            let z_end = stdn.inverse_cdf(u.clamp(f64::MIN_POSITIVE, 1.0 - f64::EPSILON));
+            let normals: Vec<f64> = (0..n_steps.saturating_sub(1))
+                .map(|_| StandardNormal.sample(&mut rng))
+                .collect();
+            brownian_bridge(0.0, t_end.sqrt() * z_end, t_end, n_steps, &normals)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::monte_carlo::{mean_std_err, sample_variance};
+    use crate::simulation::brownian::brownian_paths;
+    use crate::test_utils::normal_sample;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_bridge_hits_endpoints_exactly() {
+        let normals = [0.3, -1.2, 0.8, 0.1, -0.4, 2.0, -0.7];
+        let path = brownian_bridge(0.25, -1.75, 2.0, 8, &normals);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path[0], 0.25);
+        assert_eq!(path[8], -1.75);
+    }
+
+    #[test]
+    fn test_terminal_and_midpoint_distribution() {
+        let t_end = 2.0;
+        let paths = brownian_bridge_paths(t_end, 10, 20_000, 3);
+        let n = paths.len() as f64;
+        let terminal_mean = paths.iter().map(|p| p[10]).sum::<f64>() / n;
+        let terminal_var = paths.iter().map(|p| p[10] * p[10]).sum::<f64>() / n;
+        let mid_var = paths.iter().map(|p| p[5] * p[5]).sum::<f64>() / n;
+        assert!(terminal_mean.abs() < 0.01, "mean={}", terminal_mean);
+        assert!((terminal_var - t_end).abs() < 0.05, "var={}", terminal_var);
+        assert!((mid_var - 0.5 * t_end).abs() < 0.05, "mid var={}", mid_var);
+    }
+
+    #[test]
+    fn test_bridge_conditional_moments() {
+        // Pinned at W(0) = 0.5 and W(2) = -1, the bridge at time t is normal with mean
+        // interpolating the endpoints and variance t * (2 - t) / 2.
+        let (w_start, w_end, t_end, n_steps, n_paths) = (0.5, -1.0, 2.0, 8, 20_000);
+        let normals = normal_sample((n_steps - 1) * n_paths, 1.0, 9);
+        let paths: Vec<Vec<f64>> = normals
+            .chunks(n_steps - 1)
+            .map(|z| brownian_bridge(w_start, w_end, t_end, n_steps, z))
+            .collect();
+        for i in 1..n_steps {
+            let t = i as f64 * t_end / n_steps as f64;
+            let values: Vec<f64> = paths.iter().map(|p| p[i]).collect();
+            let (mean, se) = mean_std_err(&values);
+            let expected_var = t * (t_end - t) / t_end;
+            assert!(
+                (mean - (w_start + (w_end - w_start) * t / t_end)).abs() < 4.0 * se,
+                "t={} mean={}",
+                t,
+                mean
+            );
+            assert_relative_eq!(sample_variance(&values), expected_var, max_relative = 0.05);
+        }
+    }
+
+    fn asian_call_estimate(paths: &[Vec<f64>], s0: f64, k: f64, t: f64, r: f64, sigma: f64) -> f64 {
+        let n_steps = paths[0].len() - 1;
+        let dt = t / n_steps as f64;
+        let payoffs: Vec<f64> = paths
+            .iter()
+            .map(|w| {
+                let avg = (1..=n_steps)
+                    .map(|i| s0 * ((r - 0.5 * sigma * sigma) * i as f64 * dt + sigma * w[i]).exp())
+                    .sum::<f64>()
+                    / n_steps as f64;
+                (-r * t).exp() * (avg - k).max(0.0)
+            })
+            .collect();
+        mean_std_err(&payoffs).0
+    }
+
+    #[test]
+    fn test_bridge_reduces_asian_variance() {
+        let (s0, k, t, r, sigma) = (100.0, 100.0, 1.0, 0.05, 0.2);
+        let (sequential, bridge): (Vec<f64>, Vec<f64>) = (0..30)
+            .map(|seed| {
+                let seq = brownian_paths(t, 16, 1_000, seed);
+                let bb = brownian_bridge_paths(t, 16, 1_000, seed);
+                (
+                    asian_call_estimate(&seq, s0, k, t, r, sigma),
+                    asian_call_estimate(&bb, s0, k, t, r, sigma),
+                )
+            })
+            .unzip();
+        let (_, seq_se) = mean_std_err(&sequential);
+        let (_, bb_se) = mean_std_err(&bridge);
+        assert!(
+            bb_se < 0.75 * seq_se,
+            "bridge se={} sequential se={}",
+            bb_se,
+            seq_se
+        );
+    }
+}
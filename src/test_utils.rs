@@ -0,0 +1,16 @@
+//! Fixtures shared by the unit tests.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+
+/// `n` independent `N(0, scale^2)` draws from a seeded generator.
+pub(crate) fn normal_sample(n: usize, scale: f64, seed: u64) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            scale * z
+        })
+        .collect()
+}
@@ -0,0 +1,5 @@
+pub mod rates;
+pub mod time_value;
+
+pub use rates::{continuous_to_periodic, periodic_to_continuous, simple_to_continuous};
+pub use time_value::{annuity_pv, future_value, present_value};
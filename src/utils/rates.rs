@@ -0,0 +1,81 @@
+/// Converts a continuously compounded rate to the equivalent rate compounded `freq` times a year.
+///
+/// Both grow one unit to the same amount: `(1 + r_per / freq)^freq = exp(r_cont)`, so
+/// `r_per = freq * (exp(r_cont / freq) - 1)`.
+///
+/// # Arguments
+///  - r_cont: continuously compounded rate
+///  - freq: compounding periods per year, e.g. 2 for semi-annual
+pub fn continuous_to_periodic(r_cont: f64, freq: f64) -> f64 {
+    freq * (r_cont / freq).exp_m1()
+}
+
+/// Converts a rate compounded `freq` times a year to the equivalent continuously compounded rate,
+/// `freq * ln(1 + r_per / freq)`.
+///
+/// # Arguments
+///  - r_per: rate compounded `freq` times a year
+///  - freq: compounding periods per year
+pub fn periodic_to_continuous(r_per: f64, freq: f64) -> f64 {
+    freq * (r_per / freq).ln_1p()
+}
+
+/// Converts a simple (money market) rate over `t` years to the equivalent continuously
+/// compounded rate, `ln(1 + r_simple * t) / t`.
+///
+/// # Arguments
+///  - r_simple: simply compounded rate for the period
+///  - t: length of the period in years
+pub fn simple_to_continuous(r_simple: f64, t: f64) -> f64 {
+    (r_simple * t).ln_1p() / t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_round_trip() {
+        for &r in &[-0.01, 0.0, 0.02, 0.05, 0.15] {
+            for &freq in &[1.0, 2.0, 4.0, 12.0, 365.0] {
+                let periodic = continuous_to_periodic(r, freq);
+                assert_relative_eq!(periodic_to_continuous(periodic, freq), r, epsilon = 1e-15);
+                // Compounding more than once a year needs a lower nominal rate.
+                if r > 0.0 && freq > 1.0 {
+                    assert!(periodic < continuous_to_periodic(r, 1.0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_converges_to_continuous() {
+        let r = 0.07;
+        let mut prev = f64::INFINITY;
+        for &freq in &[1.0, 10.0, 100.0, 1e4, 1e6] {
+            let gap = (continuous_to_periodic(r, freq) - r).abs();
+            assert!(gap < prev);
+            prev = gap;
+        }
+        assert!(prev < 1e-8);
+        assert_relative_eq!(periodic_to_continuous(r, 1e8), r, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_simple_matches_annual_compounding_over_one_year() {
+        for &r in &[0.0, 0.01, 0.04, 0.2] {
+            assert_relative_eq!(
+                simple_to_continuous(r, 1.0),
+                periodic_to_continuous(r, 1.0),
+                epsilon = 1e-15
+            );
+        }
+        // Over a quarter a simple rate behaves like quarterly compounding.
+        assert_relative_eq!(
+            simple_to_continuous(0.03, 0.25),
+            periodic_to_continuous(0.03, 4.0),
+            epsilon = 1e-15
+        );
+    }
+}
@@ -0,0 +1,70 @@
+/// Present value of an amount received at `t`, discounted continuously: `fv * exp(-r*t)`.
+///
+/// # Arguments
+///  - fv: amount received at `t`
+///  - r: continuously compounded rate
+///  - t: time in years
+pub fn present_value(fv: f64, r: f64, t: f64) -> f64 {
+    fv * (-r * t).exp()
+}
+
+/// Value at `t` of an amount invested today, compounded continuously: `pv * exp(r*t)`.
+///
+/// # Arguments
+///  - pv: amount invested today
+///  - r: continuously compounded rate
+///  - t: time in years
+pub fn future_value(pv: f64, r: f64, t: f64) -> f64 {
+    pv * (r * t).exp()
+}
+
+/// Present value of `n` payments of `c` at the end of each period, `c * (1 - (1+r)^-n) / r`.
+///
+/// # Arguments
+///  - c: payment per period
+///  - r: rate per period, compounded once per period; `r = 0` gives `n * c`
+///  - n: number of payments
+pub fn annuity_pv(c: f64, r: f64, n: u32) -> f64 {
+    if r == 0.0 {
+        return c * f64::from(n);
+    }
+    c * -(-f64::from(n) * r.ln_1p()).exp_m1() / r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_present_and_future_value_round_trip() {
+        for &(amount, r, t) in &[(100.0, 0.05, 2.0), (1.0, -0.01, 10.0), (250.0, 0.0, 3.0)] {
+            let pv = present_value(amount, r, t);
+            assert_relative_eq!(future_value(pv, r, t), amount, max_relative = 1e-14);
+            assert_relative_eq!(present_value(future_value(amount, r, t), r, t), amount);
+        }
+        assert!(present_value(100.0, 0.05, 1.0) < 100.0);
+    }
+
+    #[test]
+    fn test_annuity_single_payment() {
+        for &r in &[0.01, 0.05, 0.2] {
+            assert_relative_eq!(annuity_pv(100.0, r, 1), 100.0 / (1.0 + r), epsilon = 1e-12);
+        }
+        assert_eq!(annuity_pv(10.0, 0.0, 12), 120.0);
+    }
+
+    #[test]
+    fn test_annuity_sum_and_perpetuity_limit() {
+        let (c, r) = (5.0, 0.04);
+        let sum: f64 = (1..=30).map(|i| c / (1.0_f64 + r).powi(i)).sum();
+        assert_relative_eq!(annuity_pv(c, r, 30), sum, max_relative = 1e-12);
+        let mut prev = 0.0;
+        for &n in &[10, 50, 200] {
+            let pv = annuity_pv(c, r, n);
+            assert!(pv > prev && pv < c / r);
+            prev = pv;
+        }
+        assert_relative_eq!(annuity_pv(c, r, 10_000), c / r, max_relative = 1e-12);
+    }
+}
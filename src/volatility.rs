@@ -0,0 +1,17 @@
+pub mod moments;
+pub mod smile;
+pub mod surface;
+pub mod surface_py;
+pub mod svi;
+pub mod term_structure;
+
+pub use moments::{moment_implied_kurtosis, moment_implied_skew};
+pub use smile::{forward_moneyness, log_moneyness, strike_from_log_moneyness, VolSmile};
+pub use surface::{
+    fill_missing_strikes, vol_surface_from_chain, InterpolationMethod, VolatilitySurface,
+};
+pub use surface_py::vol_surface_from_chain_py;
+pub use svi::{
+    check_calendar_arbitrage, fit_svi, fit_svi_surface_no_arb, svi_smile_data, SviParams,
+};
+pub use term_structure::VolTermStructure;
@@ -0,0 +1,128 @@
+/// Log-moneyness `x = ln(K / F)` of strike `k` relative to forward `f`.
+///
+/// `x = 0` is at-the-money forward; `x > 0` is above the forward.
+pub fn log_moneyness(k: f64, f: f64) -> f64 {
+    (k / f).ln()
+}
+
+/// Forward moneyness `K / F`.
+pub fn forward_moneyness(k: f64, f: f64) -> f64 {
+    k / f
+}
+
+/// Inverse of [`log_moneyness`]: the strike `F * exp(x)`.
+pub fn strike_from_log_moneyness(x: f64, f: f64) -> f64 {
+    f * x.exp()
+}
+
+/// Implied volatility smile at a single expiry, keyed by log-moneyness.
+///
+/// Vols are interpolated linearly between nodes and extrapolated flat beyond the outermost
+/// nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolSmile {
+    points: Vec<(f64, f64)>,
+}
+
+impl VolSmile {
+    /// Creates a smile from `(log_moneyness, implied_vol)` pairs in any order.
+    ///
+    /// # Panics
+    /// If `points` is empty.
+    pub fn new(mut points: Vec<(f64, f64)>) -> Self {
+        assert!(!points.is_empty(), "a smile needs at least one point");
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { points }
+    }
+
+    /// Creates a smile from `(strike, implied_vol)` pairs and the forward.
+    pub fn from_strikes(strikes: &[f64], vols: &[f64], f: f64) -> Self {
+        Self::new(
+            strikes
+                .iter()
+                .zip(vols)
+                .map(|(&k, &v)| (log_moneyness(k, f), v))
+                .collect(),
+        )
+    }
+
+    /// The `(log_moneyness, implied_vol)` nodes, sorted by log-moneyness.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Implied volatility at log-moneyness `x`.
+    pub fn vol(&self, x: f64) -> f64 {
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if x <= first.0 {
+            return first.1;
+        }
+        if x >= last.0 {
+            return last.1;
+        }
+        let i = self.points.partition_point(|p| p.0 <= x);
+        let (x0, v0) = self.points[i - 1];
+        let (x1, v1) = self.points[i];
+        v0 + (v1 - v0) * (x - x0) / (x1 - x0)
+    }
+
+    /// Implied volatility at strike `k` given the forward `f`.
+    pub fn vol_at_strike(&self, k: f64, f: f64) -> f64 {
+        self.vol(log_moneyness(k, f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{black_scholes, implied_volatility};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_zero_log_moneyness_is_atm() {
+        let f = 105.0;
+        assert_eq!(log_moneyness(f, f), 0.0);
+        assert_eq!(forward_moneyness(f, f), 1.0);
+        assert_eq!(strike_from_log_moneyness(0.0, f), f);
+    }
+
+    #[test]
+    fn test_conversion_is_bijective() {
+        let f = 105.0;
+        for &k in &[50.0, 90.0, 105.0, 130.0, 400.0] {
+            let x = log_moneyness(k, f);
+            assert_relative_eq!(strike_from_log_moneyness(x, f), k, max_relative = 1e-14);
+            assert_relative_eq!(forward_moneyness(k, f).ln(), x, epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let smile = VolSmile::new(vec![(0.2, 0.22), (-0.2, 0.3), (0.0, 0.25)]);
+        assert_relative_eq!(smile.vol(0.0), 0.25);
+        assert_relative_eq!(smile.vol(-0.1), 0.275, epsilon = 1e-12);
+        assert_relative_eq!(smile.vol(0.1), 0.235, epsilon = 1e-12);
+        assert_eq!(smile.vol(-1.0), 0.3);
+        assert_eq!(smile.vol(1.0), 0.22);
+    }
+
+    #[test]
+    fn test_lognormal_smile_is_symmetric() {
+        let (s, t, r, sigma): (f64, f64, f64, f64) = (100.0, 1.0, 0.03, 0.2);
+        let f = s * (r * t).exp();
+        let xs = [-0.3, -0.15, 0.0, 0.15, 0.3];
+        let strikes: Vec<f64> = xs
+            .iter()
+            .map(|&x| strike_from_log_moneyness(x, f))
+            .collect();
+        let vols: Vec<f64> = strikes
+            .iter()
+            .map(|&k| implied_volatility(black_scholes(s, k, t, r, sigma).0, s, k, t, r, true))
+            .collect();
+        let smile = VolSmile::from_strikes(&strikes, &vols, f);
+        for &x in &[0.05, 0.1, 0.2, 0.3] {
+            assert_relative_eq!(smile.vol(x), smile.vol(-x), epsilon = 1e-5);
+        }
+    }
+}
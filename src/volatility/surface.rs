@@ -0,0 +1,477 @@
+use crate::optimization::SecantError;
+use crate::options::chain::OptionChain;
+use crate::volatility::smile::{log_moneyness, VolSmile};
+use crate::volatility::svi::fit_svi;
+
+/// Implied volatility surface built from one [`VolSmile`] per expiry.
+///
+/// Each smile is keyed by log-moneyness against its own forward. Between expiries the surface
+/// is interpolated linearly in total variance `sigma^2 * t` at fixed strike, with zero variance
+/// at `t = 0`; beyond the last expiry the last smile is held flat in volatility.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolatilitySurface {
+    expiries: Vec<f64>,
+    forwards: Vec<f64>,
+    smiles: Vec<VolSmile>,
+}
+
+impl VolatilitySurface {
+    /// Creates a surface from expiries (in years), the forward at each expiry and its smile.
+    ///
+    /// # Returns
+    /// An error if the inputs are empty or of different lengths, or the expiries are not
+    /// positive and strictly increasing
+    pub fn new(
+        expiries: Vec<f64>,
+        forwards: Vec<f64>,
+        smiles: Vec<VolSmile>,
+    ) -> Result<Self, &'static str> {
+        if expiries.is_empty() || expiries.len() != forwards.len() || expiries.len() != smiles.len()
+        {
+            return Err("expiries, forwards and smiles must be non-empty and of equal length");
+        }
+        if expiries[0] <= 0.0 || expiries.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("expiries must be positive and strictly increasing");
+        }
+        Ok(Self {
+            expiries,
+            forwards,
+            smiles,
+        })
+    }
+
+    /// Expiries of the smiles, increasing.
+    pub fn expiries(&self) -> &[f64] {
+        &self.expiries
+    }
+
+    /// Forward price at each expiry.
+    pub fn forwards(&self) -> &[f64] {
+        &self.forwards
+    }
+
+    /// The smile at each expiry.
+    pub fn smiles(&self) -> &[VolSmile] {
+        &self.smiles
+    }
+
+    /// Total implied variance of the smile at index `i` for strike `k`.
+    fn slice_variance(&self, i: usize, k: f64) -> f64 {
+        let v = self.smiles[i].vol_at_strike(k, self.forwards[i]);
+        v * v * self.expiries[i]
+    }
+
+    /// Implied volatility at strike `k` and expiry `t`.
+    pub fn vol(&self, k: f64, t: f64) -> f64 {
+        let n = self.expiries.len();
+        let i = self.expiries.partition_point(|&e| e < t);
+        if t <= 0.0 || i == 0 {
+            return self.smiles[0].vol_at_strike(k, self.forwards[0]);
+        }
+        if i == n {
+            return self.smiles[n - 1].vol_at_strike(k, self.forwards[n - 1]);
+        }
+        let (t0, t1) = (self.expiries[i - 1], self.expiries[i]);
+        let (w0, w1) = (self.slice_variance(i - 1, k), self.slice_variance(i, k));
+        ((w0 + (w1 - w0) * (t - t0) / (t1 - t0)) / t).sqrt()
+    }
+}
+
+/// Calibrates a [`VolatilitySurface`] to the mid prices of a set of option chains.
+///
+/// Each strike is inverted with [`OptionChain::implied_vols`], i.e. from the out-of-the-money
+/// option. Strikes whose implied volatility cannot be solved for (prices outside the
+/// no-arbitrage bounds, or no convergence) are skipped, as are expiries left with no strikes.
+/// The chains are sorted by expiry; chains sharing an expiry are merged into one smile at the
+/// forward of the first of them.
+///
+/// # Arguments
+///  - chains: option chains, one per expiry
+///
+/// # Returns
+/// [`Result`]<[`VolatilitySurface`], [`SecantError`]>, with the first inversion error if no
+/// strike at all could be inverted, or [`SecantError::OutOfBounds`] if there are no chains
+pub fn vol_surface_from_chain(chains: &[OptionChain]) -> Result<VolatilitySurface, SecantError> {
+    let mut sorted: Vec<&OptionChain> = chains.iter().collect();
+    sorted.sort_by(|a, b| a.expiry.total_cmp(&b.expiry));
+    let mut first_error = None;
+    let (mut expiries, mut forwards, mut slices) = (Vec::new(), Vec::new(), Vec::<Vec<_>>::new());
+    for chain in sorted {
+        let forward = chain.spot * (chain.rate * chain.expiry).exp();
+        let mut points = Vec::with_capacity(chain.strikes.len());
+        for (&k, iv) in chain.strikes.iter().zip(chain.implied_vols()) {
+            match iv {
+                Ok(vol) => points.push((log_moneyness(k, forward), vol)),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if points.is_empty() {
+            continue;
+        }
+        match slices.last_mut() {
+            Some(slice) if expiries.last() == Some(&chain.expiry) => slice.extend(points),
+            _ => {
+                expiries.push(chain.expiry);
+                forwards.push(forward);
+                slices.push(points);
+            }
+        }
+    }
+    if expiries.is_empty() {
+        return Err(first_error.unwrap_or(SecantError::OutOfBounds));
+    }
+    Ok(VolatilitySurface {
+        expiries,
+        forwards,
+        smiles: slices.into_iter().map(VolSmile::new).collect(),
+    })
+}
+
+/// How [`fill_missing_strikes`] interpolates implied volatility across strikes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationMethod {
+    /// Piecewise linear in strike.
+    Linear,
+    /// Natural cubic spline in strike.
+    CubicSpline,
+    /// Raw SVI slice fitted to the quoted strikes, see [`fit_svi`].
+    SviParametric,
+}
+
+/// Second derivatives of the natural cubic spline through `(xs, ys)`, by the Thomas algorithm.
+fn natural_spline_moments(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut moments = vec![0.0; n];
+    if n < 3 {
+        return moments;
+    }
+    // Tridiagonal system h[i-1] M[i-1] + 2 (h[i-1] + h[i]) M[i] + h[i] M[i+1] = rhs[i].
+    let h: Vec<f64> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+    let mut diag = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        diag[i] = 2.0 * (h[i - 1] + h[i]);
+        rhs[i] = 6.0 * ((ys[i + 1] - ys[i]) / h[i] - (ys[i] - ys[i - 1]) / h[i - 1]);
+    }
+    for i in 2..n - 1 {
+        let factor = h[i - 1] / diag[i - 1];
+        diag[i] -= factor * h[i - 1];
+        rhs[i] -= factor * rhs[i - 1];
+    }
+    for i in (1..n - 1).rev() {
+        moments[i] = (rhs[i] - h[i] * moments[i + 1]) / diag[i];
+    }
+    moments
+}
+
+/// Evaluates the spline with knots `(xs, ys)` and second derivatives `moments` at `x`, which
+/// must lie inside the knots.
+fn spline_value(xs: &[f64], ys: &[f64], moments: &[f64], x: f64) -> f64 {
+    let i = xs.partition_point(|&k| k <= x).clamp(1, xs.len() - 1);
+    let (x0, x1) = (xs[i - 1], xs[i]);
+    let h = x1 - x0;
+    let (a, b) = ((x1 - x) / h, (x - x0) / h);
+    a * ys[i - 1]
+        + b * ys[i]
+        + ((a * a * a - a) * moments[i - 1] + (b * b * b - b) * moments[i]) * h * h / 6.0
+}
+
+/// Fills gaps in a row of implied volatilities quoted across strikes.
+///
+/// Quoted volatilities are kept as they are and every `None` is replaced by the chosen
+/// interpolant through the quoted points. With `Linear` and `CubicSpline`, strikes outside
+/// the quoted range take the volatility of the nearest quote (flat extrapolation); the SVI
+/// fit extends its own wings, which stay linear in total variance. `SviParametric` falls back
+/// to `Linear` when fewer than five quotes are available or no admissible slice is found.
+///
+/// # Arguments
+///  - strikes: increasing strikes
+///  - vols: implied volatility at each strike, `None` where there is no quote
+///  - method: interpolation scheme
+///
+/// # Returns
+/// Implied volatility at every strike, all NaN if nothing is quoted
+pub fn fill_missing_strikes(
+    strikes: &[f64],
+    vols: &[Option<f64>],
+    method: InterpolationMethod,
+) -> Vec<f64> {
+    let (known_k, known_v): (Vec<f64>, Vec<f64>) = strikes
+        .iter()
+        .zip(vols)
+        .filter_map(|(&k, v)| v.map(|v| (k, v)))
+        .unzip();
+    if known_k.is_empty() {
+        return vec![f64::NAN; strikes.len()];
+    }
+    // SVI only needs log-moneyness up to a shift, which its m parameter absorbs.
+    let reference = known_k[known_k.len() / 2];
+    let svi = match method {
+        InterpolationMethod::SviParametric => {
+            let xs: Vec<f64> = known_k.iter().map(|&k| (k / reference).ln()).collect();
+            fit_svi(&xs, &known_v, 1.0).ok().map(|fit| fit.params)
+        }
+        _ => None,
+    };
+    let moments = match method {
+        InterpolationMethod::CubicSpline => natural_spline_moments(&known_k, &known_v),
+        _ => Vec::new(),
+    };
+    let interpolate = |k: f64| {
+        if let Some(params) = svi {
+            return params.implied_vol((k / reference).ln());
+        }
+        let n = known_k.len();
+        if k <= known_k[0] {
+            return known_v[0];
+        }
+        if k >= known_k[n - 1] {
+            return known_v[n - 1];
+        }
+        if !moments.is_empty() {
+            return spline_value(&known_k, &known_v, &moments, k);
+        }
+        let i = known_k.partition_point(|&x| x <= k);
+        let (k0, k1) = (known_k[i - 1], known_k[i]);
+        known_v[i - 1] + (known_v[i] - known_v[i - 1]) * (k - k0) / (k1 - k0)
+    };
+    strikes
+        .iter()
+        .zip(vols)
+        .map(|(&k, v)| v.unwrap_or_else(|| interpolate(k)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{black_scholes, try_implied_volatility};
+    use crate::volatility::svi::SviParams;
+    use approx::assert_relative_eq;
+
+    const SPOT: f64 = 100.0;
+    const RATE: f64 = 0.02;
+    const STRIKES: [f64; 7] = [70.0, 80.0, 90.0, 100.0, 110.0, 120.0, 135.0];
+
+    /// Skewed smile whose ATM level falls with expiry.
+    fn true_vol(k: f64, t: f64) -> f64 {
+        let x = (k / (SPOT * (RATE * t).exp())).ln();
+        0.18 + 0.04 / (1.0 + t) - 0.1 * x + 0.3 * x * x
+    }
+
+    fn chain(expiry: f64) -> OptionChain {
+        let strikes = STRIKES.to_vec();
+        let prices: Vec<(f64, f64)> = strikes
+            .iter()
+            .map(|&k| black_scholes(SPOT, k, expiry, RATE, true_vol(k, expiry)))
+            .collect();
+        OptionChain {
+            expiry,
+            spot: SPOT,
+            rate: RATE,
+            call_bids: prices.iter().map(|p| p.0).collect(),
+            call_asks: prices.iter().map(|p| p.0).collect(),
+            put_bids: prices.iter().map(|p| p.1).collect(),
+            put_asks: prices.iter().map(|p| p.1).collect(),
+            strikes,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_reprices_chain() {
+        let chains = [chain(1.0), chain(0.25), chain(0.5)];
+        let surface = vol_surface_from_chain(&chains).unwrap();
+        assert_eq!(surface.expiries(), &[0.25, 0.5, 1.0]);
+        for c in &chains {
+            for (i, &k) in c.strikes.iter().enumerate() {
+                let vol = surface.vol(k, c.expiry);
+                let (call, put) = black_scholes(SPOT, k, c.expiry, RATE, vol);
+                assert_relative_eq!(call, c.call_bids[i], epsilon = 1e-4);
+                assert_relative_eq!(put, c.put_bids[i], epsilon = 1e-4);
+                let iv = try_implied_volatility(call, SPOT, k, c.expiry, RATE, true).unwrap();
+                assert!(
+                    (iv - true_vol(k, c.expiry)).abs() < 1e-4,
+                    "k={} iv={}",
+                    k,
+                    iv
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_interpolates_between_expiries() {
+        let surface = vol_surface_from_chain(&[chain(0.25), chain(1.0)]).unwrap();
+        let (w0, w1) = (
+            surface.vol(100.0, 0.25).powi(2) * 0.25,
+            surface.vol(100.0, 1.0).powi(2),
+        );
+        let mid = surface.vol(100.0, 0.625);
+        assert_relative_eq!(mid * mid * 0.625, 0.5 * (w0 + w1), epsilon = 1e-12);
+        assert_eq!(surface.vol(100.0, 3.0), surface.vol(100.0, 1.0));
+    }
+
+    #[test]
+    fn test_skips_unsolvable_quotes() {
+        let mut bad = chain(0.5);
+        // A call worth more than the stock: no implied volatility exists.
+        bad.call_bids[6] = 2.0 * SPOT;
+        bad.call_asks[6] = 2.0 * SPOT;
+        let surface = vol_surface_from_chain(&[bad]).unwrap();
+        assert_eq!(surface.smiles()[0].points().len(), STRIKES.len() - 1);
+
+        let mut empty = chain(0.5);
+        empty.call_bids = vec![1e6; STRIKES.len()];
+        empty.call_asks = vec![1e6; STRIKES.len()];
+        empty.put_bids = vec![1e6; STRIKES.len()];
+        empty.put_asks = vec![1e6; STRIKES.len()];
+        assert_eq!(
+            vol_surface_from_chain(&[empty]),
+            Err(SecantError::OutOfBounds)
+        );
+        assert_eq!(vol_surface_from_chain(&[]), Err(SecantError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_merges_chains_with_the_same_expiry() {
+        // The same expiry quoted in two halves, e.g. from two venues.
+        let (mut low, mut high) = (chain(0.5), chain(0.5));
+        for (c, range) in [(&mut low, 0..4), (&mut high, 4..STRIKES.len())] {
+            c.strikes = c.strikes[range.clone()].to_vec();
+            c.call_bids = c.call_bids[range.clone()].to_vec();
+            c.call_asks = c.call_asks[range.clone()].to_vec();
+            c.put_bids = c.put_bids[range.clone()].to_vec();
+            c.put_asks = c.put_asks[range].to_vec();
+        }
+        let merged = vol_surface_from_chain(&[chain(1.0), high, low]).unwrap();
+        let whole = vol_surface_from_chain(&[chain(0.5), chain(1.0)]).unwrap();
+        assert_eq!(merged, whole);
+        for &t in &[0.25, 0.5, 0.75] {
+            assert!(merged.vol(100.0, t).is_finite());
+        }
+
+        let doubled = vol_surface_from_chain(&[chain(0.5), chain(0.5)]).unwrap();
+        assert_eq!(doubled.expiries(), &[0.5]);
+        assert_eq!(doubled.smiles()[0].points().len(), 2 * STRIKES.len());
+        assert_relative_eq!(
+            doubled.vol(95.0, 0.5),
+            whole.vol(95.0, 0.5),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_new_validates_inputs() {
+        let smile = VolSmile::new(vec![(0.0, 0.2)]);
+        assert!(VolatilitySurface::new(vec![], vec![], vec![]).is_err());
+        assert!(VolatilitySurface::new(
+            vec![1.0, 0.5],
+            vec![100.0, 100.0],
+            vec![smile.clone(), smile.clone()]
+        )
+        .is_err());
+        assert!(VolatilitySurface::new(vec![1.0], vec![100.0], vec![smile]).is_ok());
+    }
+
+    /// Strikes 60, 65, ..., 150 with every third quote missing and the wings unquoted.
+    fn gappy_row(vol: impl Fn(f64) -> f64) -> (Vec<f64>, Vec<Option<f64>>) {
+        let strikes: Vec<f64> = (0..19).map(|i| 60.0 + 5.0 * i as f64).collect();
+        let vols = strikes
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| (i > 1 && i < 17 && i % 3 != 0).then(|| vol(k)))
+            .collect();
+        (strikes, vols)
+    }
+
+    fn svi() -> SviParams {
+        SviParams {
+            a: 0.02,
+            b: 0.1,
+            rho: -0.4,
+            m: 0.05,
+            sigma: 0.15,
+            t: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_fills_gaps_per_method() {
+        let (strikes, vols) = gappy_row(|k| 0.2 + 0.001 * (k - 100.0));
+        let linear = fill_missing_strikes(&strikes, &vols, InterpolationMethod::Linear);
+        let spline = fill_missing_strikes(&strikes, &vols, InterpolationMethod::CubicSpline);
+        for (i, &k) in strikes.iter().enumerate().take(17).skip(2) {
+            // Both reproduce a linear smile exactly, quoted or not.
+            assert_relative_eq!(linear[i], 0.2 + 0.001 * (k - 100.0), epsilon = 1e-12);
+            assert_relative_eq!(spline[i], 0.2 + 0.001 * (k - 100.0), epsilon = 1e-12);
+        }
+
+        let params = svi();
+        let vol = |k: f64| params.implied_vol((k / 100.0).ln());
+        let (strikes, vols) = gappy_row(vol);
+        let filled = fill_missing_strikes(&strikes, &vols, InterpolationMethod::SviParametric);
+        for (&k, &v) in strikes.iter().zip(&filled) {
+            assert_relative_eq!(v, vol(k), epsilon = 1e-5);
+        }
+        let spline = fill_missing_strikes(&strikes, &vols, InterpolationMethod::CubicSpline);
+        let linear = fill_missing_strikes(&strikes, &vols, InterpolationMethod::Linear);
+        for i in (3..17).step_by(3) {
+            // The smile is convex, so chords lie above it and the spline does better.
+            assert!(linear[i] > vol(strikes[i]));
+            assert!((spline[i] - vol(strikes[i])).abs() < linear[i] - vol(strikes[i]));
+        }
+        for (v, f) in vols.iter().zip(&spline) {
+            if let Some(v) = v {
+                assert_eq!(v, f);
+            }
+        }
+    }
+
+    #[test]
+    fn test_filled_smile_is_arbitrage_free() {
+        let params = svi();
+        let (strikes, vols) = gappy_row(|k| params.implied_vol((k / 100.0).ln()));
+        for method in [
+            InterpolationMethod::Linear,
+            InterpolationMethod::CubicSpline,
+            InterpolationMethod::SviParametric,
+        ] {
+            let filled = fill_missing_strikes(&strikes, &vols, method);
+            let calls: Vec<f64> = strikes
+                .iter()
+                .zip(&filled)
+                .map(|(&k, &v)| black_scholes(100.0, k, 1.0, 0.0, v).0)
+                .collect();
+            // Call prices decrease and are convex in strike on the uniform grid. Flat wings
+            // meet a steep skew at a kink, so only the quoted range is checked.
+            for w in calls[2..17].windows(3) {
+                assert!(w[1] < w[0] && w[2] < w[1], "{:?}", method);
+                assert!(w[0] - 2.0 * w[1] + w[2] > 0.0, "{:?}", method);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flat_extrapolation() {
+        let (strikes, vols) = gappy_row(|k| 0.25 - 0.002 * (k - 100.0));
+        for method in [
+            InterpolationMethod::Linear,
+            InterpolationMethod::CubicSpline,
+        ] {
+            let filled = fill_missing_strikes(&strikes, &vols, method);
+            assert_eq!(&filled[..2], &[vols[2].unwrap(); 2]);
+            assert_eq!(&filled[17..], &[vols[16].unwrap(); 2]);
+        }
+        // Too few quotes for SVI: falls back to linear.
+        let sparse = [None, Some(0.3), None, Some(0.2), None];
+        let filled = fill_missing_strikes(
+            &[80.0, 90.0, 100.0, 110.0, 120.0],
+            &sparse,
+            InterpolationMethod::SviParametric,
+        );
+        assert_eq!(filled, vec![0.3, 0.3, 0.25, 0.2, 0.2]);
+        assert!(fill_missing_strikes(&[100.0], &[None], InterpolationMethod::Linear)[0].is_nan());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::options::chain::OptionChain;
+use crate::volatility::smile::strike_from_log_moneyness;
+use crate::volatility::surface::vol_surface_from_chain;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Calibrates a volatility surface to mid prices given per expiry and returns its nodes as
+/// `(expiry, strike, implied_vol)` triples.
+#[pyfunction]
+pub fn vol_surface_from_chain_py(
+    spot: f64,
+    rate: f64,
+    expiries: Vec<f64>,
+    strikes: Vec<Vec<f64>>,
+    call_prices: Vec<Vec<f64>>,
+    put_prices: Vec<Vec<f64>>,
+) -> PyResult<Vec<(f64, f64, f64)>> {
+    let chains: Vec<OptionChain> = expiries
+        .into_iter()
+        .zip(strikes)
+        .zip(call_prices.into_iter().zip(put_prices))
+        .map(|((expiry, strikes), (calls, puts))| OptionChain {
+            expiry,
+            spot,
+            rate,
+            strikes,
+            call_bids: calls.clone(),
+            call_asks: calls,
+            put_bids: puts.clone(),
+            put_asks: puts,
+        })
+        .collect();
+    let surface = vol_surface_from_chain(&chains).map_err(|e| {
+        PyValueError::new_err(format!("volatility surface calibration failed: {:?}", e))
+    })?;
+    let mut nodes = Vec::new();
+    for ((&t, &f), smile) in surface
+        .expiries()
+        .iter()
+        .zip(surface.forwards())
+        .zip(surface.smiles())
+    {
+        for &(x, vol) in smile.points() {
+            nodes.push((t, strike_from_log_moneyness(x, f), vol));
+        }
+    }
+    Ok(nodes)
+}
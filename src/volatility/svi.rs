@@ -0,0 +1,429 @@
+use crate::math::{even_grid, linear_regression};
+use crate::optimization::{nelder_mead, CalibrationResult};
+use crate::options::OptionChain;
+use crate::volatility::smile::log_moneyness;
+
+/// Raw SVI parametrisation of one implied volatility slice (Gatheral, 2004).
+///
+/// Total implied variance at log-moneyness `k = ln(K / F)` is
+/// `w(k) = a + b * (rho * (k - m) + sqrt((k - m)^2 + sigma^2))`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SviParams {
+    /// Overall level of total variance.
+    pub a: f64,
+    /// Slope of the wings, `b >= 0`.
+    pub b: f64,
+    /// Skew, `-1 < rho < 1`.
+    pub rho: f64,
+    /// Horizontal shift of the smile.
+    pub m: f64,
+    /// Curvature at the minimum, `sigma > 0`.
+    pub sigma: f64,
+    /// Time to expiry of the slice in years.
+    pub t: f64,
+}
+
+impl SviParams {
+    /// Total implied variance `w(k)` at log-moneyness `k`.
+    pub fn total_variance(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    /// Black-Scholes implied volatility `sqrt(w(k) / t)` at log-moneyness `k`.
+    pub fn implied_vol(&self, k: f64) -> f64 {
+        (self.total_variance(k).max(0.0) / self.t).sqrt()
+    }
+}
+
+/// Implied volatility smile of an SVI slice, for plotting or quick inspection.
+///
+/// # Arguments
+///  - params: SVI slice
+///  - k_range: lowest and highest log-moneyness `ln(K / F)`
+///  - n_strikes: number of evenly spaced points
+///
+/// # Returns
+/// `(log_moneyness, implied_vol)` pairs in increasing order of log-moneyness
+pub fn svi_smile_data(
+    params: &SviParams,
+    k_range: (f64, f64),
+    n_strikes: usize,
+) -> Vec<(f64, f64)> {
+    even_grid(k_range.0, k_range.1, n_strikes)
+        .map(|k| (k, params.implied_vol(k)))
+        .collect()
+}
+
+/// Best SVI slice for fixed `(m, sigma)`, where total variance is linear in the remaining
+/// parameters (the quasi-explicit method of Zeliade, 2009).
+///
+/// # Returns
+/// The slice and its sum of squared total variance errors, or `None` if the least-squares
+/// solution violates `b >= 0`, `|rho| <= 1` or non-negative total variance
+fn fit_svi_given(k: &[f64], w: &[f64], m: f64, sigma: f64, t: f64) -> Option<(SviParams, f64)> {
+    // w = a + d * y + c * sqrt(y^2 + 1) with y = (k - m) / sigma, c = b * sigma, d = rho * c.
+    let design: Vec<Vec<f64>> = k
+        .iter()
+        .map(|&ki| {
+            let y = (ki - m) / sigma;
+            vec![1.0, y, (y * y + 1.0).sqrt()]
+        })
+        .collect();
+    let beta = linear_regression(&design, w).ok()?;
+    let (a, d, c) = (beta[0], beta[1], beta[2]);
+    if c <= 0.0 || d.abs() > c {
+        return None;
+    }
+    let params = SviParams {
+        a,
+        b: c / sigma,
+        rho: d / c,
+        m,
+        sigma,
+        t,
+    };
+    let rho = params.rho;
+    if a + params.b * sigma * (1.0 - rho * rho).sqrt() < 0.0 {
+        return None;
+    }
+    let sse = k
+        .iter()
+        .zip(w)
+        .map(|(&ki, &wi)| (params.total_variance(ki) - wi).powi(2))
+        .sum();
+    Some((params, sse))
+}
+
+/// Fits a raw SVI slice to implied volatilities at one expiry.
+///
+/// Minimises squared total variance errors. For fixed `(m, sigma)` the other three parameters
+/// follow by linear least squares, so Nelder-Mead only searches over `m` and `ln(sigma)`.
+///
+/// # Arguments
+///  - log_moneyness: `ln(K / F)` of each quote
+///  - implied_vols: Black-Scholes implied volatility of each quote
+///  - t: time to expiry in years
+///
+/// # Returns
+/// [`Result`]<[`CalibrationResult`]<[`SviParams`]>, `&'static str`> with implied vol errors, an
+/// error if fewer than five quotes are given or no admissible slice is found
+pub fn fit_svi(
+    log_moneyness: &[f64],
+    implied_vols: &[f64],
+    t: f64,
+) -> Result<CalibrationResult<SviParams>, &'static str> {
+    if log_moneyness.len() != implied_vols.len() || log_moneyness.len() < 5 {
+        return Err("at least five quotes of equal length are needed");
+    }
+    if t <= 0.0 {
+        return Err("time to expiry must be positive");
+    }
+    let w: Vec<f64> = implied_vols.iter().map(|v| v * v * t).collect();
+    let objective = |x: &[f64]| {
+        fit_svi_given(log_moneyness, &w, x[0], x[1].exp(), t).map_or(f64::INFINITY, |(_, sse)| sse)
+    };
+    // Start the smile minimum at the lowest quoted variance and try a few curvatures.
+    let i_min = (0..w.len()).min_by(|&i, &j| w[i].total_cmp(&w[j])).unwrap();
+    let span = log_moneyness
+        .iter()
+        .fold(0.0_f64, |acc, k| acc.max(k.abs()));
+    let best = [0.1, 0.5, 1.0]
+        .iter()
+        .map(|&scale| {
+            let x0 = [log_moneyness[i_min], (scale * span.max(0.05)).ln()];
+            nelder_mead(objective, &x0, 0.1, 1e-10, 1e-16, 2_000)
+        })
+        .min_by(|a, b| a.fx.total_cmp(&b.fx))
+        .unwrap();
+    let (params, _) = fit_svi_given(log_moneyness, &w, best.x[0], best.x[1].exp(), t)
+        .ok_or("no admissible SVI slice found")?;
+    let residuals: Vec<f64> = log_moneyness
+        .iter()
+        .zip(implied_vols)
+        .map(|(&k, &v)| params.implied_vol(k) - v)
+        .collect();
+    Ok(CalibrationResult::from_residuals(
+        params,
+        &residuals,
+        best.iterations,
+        best.converged,
+    ))
+}
+
+/// Whether SVI slices are free of calendar spread arbitrage on a grid of log-moneyness.
+///
+/// Total variance must be non-decreasing in expiry at every `k` in `log_moneyness`. Slices
+/// are compared in order of `t`.
+pub fn check_calendar_arbitrage(slices: &[SviParams], log_moneyness: &[f64]) -> bool {
+    let mut sorted = slices.to_vec();
+    sorted.sort_by(|a, b| a.t.total_cmp(&b.t));
+    sorted.windows(2).all(|pair| {
+        log_moneyness
+            .iter()
+            .all(|&k| pair[1].total_variance(k) >= pair[0].total_variance(k))
+    })
+}
+
+/// Largest amount by which `slice` falls below `prev` in total variance on `grid`.
+fn calendar_violation(prev: &SviParams, slice: &SviParams, grid: &[f64]) -> f64 {
+    grid.iter()
+        .map(|&k| prev.total_variance(k) - slice.total_variance(k))
+        .fold(0.0, f64::max)
+}
+
+/// Refits `slice` to the quotes `(k, vols)` subject to lying above `prev` on `grid`.
+///
+/// The calendar constraint enters as a quadratic penalty in a Nelder-Mead search over all five
+/// parameters, started from the unconstrained fit. Any remaining violation is removed by
+/// raising `a`, a parallel shift of total variance.
+fn repair_calendar(
+    prev: &SviParams,
+    slice: &SviParams,
+    k: &[f64],
+    vols: &[f64],
+    grid: &[f64],
+) -> SviParams {
+    const PENALTY: f64 = 1e6;
+    let t = slice.t;
+    let unpack = |x: &[f64]| SviParams {
+        a: x[0],
+        b: x[1].exp(),
+        rho: x[2].tanh(),
+        m: x[3],
+        sigma: x[4].exp(),
+        t,
+    };
+    let objective = |x: &[f64]| {
+        let candidate = unpack(x);
+        let fit: f64 = k
+            .iter()
+            .zip(vols)
+            .map(|(&ki, &v)| (candidate.implied_vol(ki) - v).powi(2))
+            .sum();
+        let violation: f64 = grid
+            .iter()
+            .map(|&kg| (prev.total_variance(kg) - candidate.total_variance(kg)).max(0.0))
+            .map(|v| v * v)
+            .sum();
+        fit + PENALTY * violation
+    };
+    let x0 = [
+        slice.a,
+        slice.b.ln(),
+        slice.rho.atanh(),
+        slice.m,
+        slice.sigma.ln(),
+    ];
+    let result = nelder_mead(objective, &x0, 0.05, 1e-12, 1e-18, 20_000);
+    let mut repaired = unpack(&result.x);
+    repaired.a += calendar_violation(prev, &repaired, grid);
+    repaired
+}
+
+/// Fits an SVI slice to each option chain and removes calendar spread arbitrage.
+///
+/// Every slice is first fitted independently with [`fit_svi`] to the out-of-the-money implied
+/// volatilities of its chain. Walking up in expiry, any slice whose total variance dips below
+/// the previous slice's is refitted to its quotes with the calendar constraint imposed on a
+/// grid extending one unit of log-moneyness beyond the quoted strikes.
+///
+/// # Arguments
+///  - chain: option chains, one per expiry, all on the same underlying
+///
+/// # Returns
+/// [`Result`]<`Vec<SviParams>`, `&'static str`> with one slice per chain, sorted by expiry
+pub fn fit_svi_surface_no_arb(chain: &[OptionChain]) -> Result<Vec<SviParams>, &'static str> {
+    let mut quotes: Vec<(f64, Vec<f64>, Vec<f64>)> = chain
+        .iter()
+        .map(|c| {
+            let forward = c.spot * (c.rate * c.expiry).exp();
+            let (k, vols): (Vec<f64>, Vec<f64>) = c
+                .strikes
+                .iter()
+                .zip(c.implied_vols())
+                .filter_map(|(&strike, iv)| iv.ok().map(|v| (log_moneyness(strike, forward), v)))
+                .unzip();
+            (c.expiry, k, vols)
+        })
+        .collect();
+    quotes.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let (k_min, k_max) = quotes
+        .iter()
+        .flat_map(|(_, k, _)| k.iter())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &k| {
+            (lo.min(k), hi.max(k))
+        });
+    let grid: Vec<f64> = (0..=200)
+        .map(|i| k_min - 1.0 + (k_max - k_min + 2.0) * i as f64 / 200.0)
+        .collect();
+
+    let mut slices: Vec<SviParams> = Vec::with_capacity(quotes.len());
+    for (t, k, vols) in &quotes {
+        let mut slice = fit_svi(k, vols, *t)?.params;
+        if let Some(prev) = slices.last() {
+            if calendar_violation(prev, &slice, &grid) > 0.0 {
+                slice = repair_calendar(prev, &slice, k, vols, &grid);
+            }
+        }
+        slices.push(slice);
+    }
+    if !check_calendar_arbitrage(&slices, &grid) {
+        return Err("calendar spread arbitrage could not be removed");
+    }
+    Ok(slices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::black_scholes;
+    use approx::assert_relative_eq;
+
+    fn chain_from_svi(params: &SviParams, spot: f64, rate: f64) -> OptionChain {
+        let forward = spot * (rate * params.t).exp();
+        let strikes: Vec<f64> = (0..11).map(|i| 70.0 + 6.0 * i as f64).collect();
+        let prices: Vec<(f64, f64)> = strikes
+            .iter()
+            .map(|&k| {
+                let vol = params.implied_vol(log_moneyness(k, forward));
+                black_scholes(spot, k, params.t, rate, vol)
+            })
+            .collect();
+        OptionChain {
+            expiry: params.t,
+            spot,
+            rate,
+            call_bids: prices.iter().map(|p| p.0).collect(),
+            call_asks: prices.iter().map(|p| p.0).collect(),
+            put_bids: prices.iter().map(|p| p.1).collect(),
+            put_asks: prices.iter().map(|p| p.1).collect(),
+            strikes,
+        }
+    }
+
+    fn assert_fits_chain(slice: &SviParams, chain: &OptionChain, tol: f64) {
+        let forward = chain.spot * (chain.rate * chain.expiry).exp();
+        for (&k, iv) in chain.strikes.iter().zip(chain.implied_vols()) {
+            let market = iv.unwrap();
+            let model = slice.implied_vol(log_moneyness(k, forward));
+            assert!(
+                (model - market).abs() < tol,
+                "k={} model={} market={}",
+                k,
+                model,
+                market
+            );
+        }
+    }
+
+    #[test]
+    fn test_svi_smile_data() {
+        let params = SviParams {
+            a: 0.02,
+            b: 0.1,
+            rho: -0.4,
+            m: 0.05,
+            sigma: 0.2,
+            t: 1.0,
+        };
+        let data = svi_smile_data(&params, (-0.5, 0.5), 21);
+        assert_eq!(data.len(), 21);
+        assert_relative_eq!(data[0].0, -0.5);
+        assert_relative_eq!(data[20].0, 0.5);
+        for pair in data.windows(2) {
+            assert!(pair[1].0 > pair[0].0);
+        }
+        for &(k, iv) in &data {
+            assert!(iv > 0.0);
+            assert_eq!(iv, params.implied_vol(k));
+        }
+        // Negative rho skews the smile towards low strikes.
+        assert!(data[0].1 > data[20].1);
+    }
+
+    #[test]
+    fn test_fit_svi_recovers_parameters() {
+        let truth = SviParams {
+            a: 0.02,
+            b: 0.1,
+            rho: -0.4,
+            m: 0.05,
+            sigma: 0.2,
+            t: 1.0,
+        };
+        let k: Vec<f64> = (0..15).map(|i| -0.5 + 0.07 * i as f64).collect();
+        let vols: Vec<f64> = k.iter().map(|&x| truth.implied_vol(x)).collect();
+        let fit = fit_svi(&k, &vols, 1.0).unwrap();
+        let fitted = fit.params;
+        for &x in &k {
+            assert_relative_eq!(fitted.implied_vol(x), truth.implied_vol(x), epsilon = 1e-6);
+        }
+        let errors: Vec<f64> = k
+            .iter()
+            .zip(&vols)
+            .map(|(&x, &v)| fitted.implied_vol(x) - v)
+            .collect();
+        let rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        assert_relative_eq!(fit.rmse_vol, rmse, max_relative = 1e-12);
+        assert!(fit.max_error_vol < 1e-6 && fit.converged);
+        assert!(fit_svi(&k[..4], &vols[..4], 1.0).is_err());
+    }
+
+    #[test]
+    fn test_surface_without_arbitrage_is_fitted_slice_by_slice() {
+        let slices = [(0.25, 0.01), (0.5, 0.02), (1.0, 0.04)].map(|(t, a)| SviParams {
+            a,
+            b: 0.08,
+            rho: -0.5,
+            m: 0.0,
+            sigma: 0.15,
+            t,
+        });
+        let chains: Vec<OptionChain> = slices
+            .iter()
+            .map(|p| chain_from_svi(p, 100.0, 0.02))
+            .collect();
+        let fitted = fit_svi_surface_no_arb(&chains).unwrap();
+        let grid: Vec<f64> = (0..=100).map(|i| -1.0 + 0.02 * i as f64).collect();
+        assert!(check_calendar_arbitrage(&fitted, &grid));
+        for (slice, chain) in fitted.iter().zip(&chains) {
+            assert_fits_chain(slice, chain, 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_calendar_arbitrage_removed() {
+        // The later slice has a steeper skew, so beyond the quoted strikes its independent fit
+        // dips below the earlier slice in the right wing.
+        let early = SviParams {
+            a: 0.018,
+            b: 0.1,
+            rho: -0.1,
+            m: 0.0,
+            sigma: 0.2,
+            t: 0.5,
+        };
+        let late = SviParams {
+            a: 0.038,
+            b: 0.1,
+            rho: -0.6,
+            m: 0.0,
+            sigma: 0.2,
+            t: 0.6,
+        };
+        let chains = vec![
+            chain_from_svi(&late, 100.0, 0.02),
+            chain_from_svi(&early, 100.0, 0.02),
+        ];
+        let grid: Vec<f64> = (0..=100).map(|i| -1.0 + 0.02 * i as f64).collect();
+        assert!(!check_calendar_arbitrage(&[early, late], &grid));
+
+        let fitted = fit_svi_surface_no_arb(&chains).unwrap();
+        assert!(fitted[0].t < fitted[1].t);
+        assert!(check_calendar_arbitrage(&fitted, &grid));
+        assert_fits_chain(&fitted[0], &chains[1], 0.005);
+        assert_fits_chain(&fitted[1], &chains[0], 0.005);
+    }
+}
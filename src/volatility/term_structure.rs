@@ -0,0 +1,121 @@
+/// Term structure of at-the-money implied volatility.
+///
+/// Interpolation is linear in total variance `w(t) = sigma(t)^2 * t`, with `w(0) = 0` before the
+/// first maturity and the last vol held flat beyond the final one. This keeps forward variance
+/// piecewise constant and positive whenever the nodes are free of calendar arbitrage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolTermStructure {
+    maturities: Vec<f64>,
+    atm_vols: Vec<f64>,
+}
+
+impl VolTermStructure {
+    /// Creates a term structure from maturities (in years) and ATM vols.
+    ///
+    /// # Returns
+    /// An error if the inputs are empty or of different lengths, the maturities are not
+    /// positive and strictly increasing, or total variance decreases between nodes (calendar
+    /// arbitrage)
+    pub fn new(maturities: Vec<f64>, atm_vols: Vec<f64>) -> Result<Self, &'static str> {
+        if maturities.is_empty() || maturities.len() != atm_vols.len() {
+            return Err("maturities and vols must be non-empty and of equal length");
+        }
+        if maturities[0] <= 0.0 || maturities.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("maturities must be positive and strictly increasing");
+        }
+        if atm_vols.iter().any(|&v| v < 0.0) {
+            return Err("vols must be non-negative");
+        }
+        let curve = Self {
+            maturities,
+            atm_vols,
+        };
+        let variances: Vec<f64> = curve.nodes().map(|(t, v)| v * v * t).collect();
+        if variances.windows(2).any(|w| w[1] < w[0]) {
+            return Err("total variance must be non-decreasing in maturity");
+        }
+        Ok(curve)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.maturities
+            .iter()
+            .copied()
+            .zip(self.atm_vols.iter().copied())
+    }
+
+    /// Total implied variance `sigma(t)^2 * t` at maturity `t`.
+    pub fn total_variance(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        let n = self.maturities.len();
+        let i = self.maturities.partition_point(|&m| m < t);
+        if i == n {
+            let v = self.atm_vols[n - 1];
+            return v * v * t;
+        }
+        let (t1, v1) = (self.maturities[i], self.atm_vols[i]);
+        let (t0, w0) = if i == 0 {
+            (0.0, 0.0)
+        } else {
+            let v0 = self.atm_vols[i - 1];
+            (self.maturities[i - 1], v0 * v0 * self.maturities[i - 1])
+        };
+        let w1 = v1 * v1 * t1;
+        w0 + (w1 - w0) * (t - t0) / (t1 - t0)
+    }
+
+    /// ATM implied volatility at maturity `t`.
+    pub fn interpolate(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return self.atm_vols[0];
+        }
+        (self.total_variance(t) / t).sqrt()
+    }
+
+    /// Forward volatility between `t1` and `t2`, `sqrt((w(t2) - w(t1)) / (t2 - t1))`.
+    pub fn forward_vol(&self, t1: f64, t2: f64) -> f64 {
+        ((self.total_variance(t2) - self.total_variance(t1)) / (t2 - t1)).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn curve() -> VolTermStructure {
+        VolTermStructure::new(
+            vec![0.25, 0.5, 1.0, 2.0, 5.0],
+            vec![0.3, 0.26, 0.22, 0.21, 0.2],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_matches_nodes() {
+        let c = curve();
+        for (t, v) in c.nodes() {
+            assert_relative_eq!(c.interpolate(t), v, epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_total_variance_increasing_and_forward_vol_positive() {
+        let c = curve();
+        let grid: Vec<f64> = (1..=80).map(|i| i as f64 * 0.1).collect();
+        for w in grid.windows(2) {
+            assert!(c.total_variance(w[1]) >= c.total_variance(w[0]));
+            assert!(c.forward_vol(w[0], w[1]) > 0.0);
+        }
+        // Forward vol over [0, t] is the spot vol.
+        assert_relative_eq!(c.forward_vol(0.0, 1.5), c.interpolate(1.5), epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_rejects_calendar_arbitrage() {
+        assert!(VolTermStructure::new(vec![1.0, 2.0], vec![0.3, 0.2]).is_err());
+        assert!(VolTermStructure::new(vec![1.0, 1.0], vec![0.2, 0.2]).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_price_option"))
+        .args(args)
+        .output()
+        .expect("failed to run price_option")
+}
+
+/// Value printed after `key` on its own line.
+fn field(output: &Output, key: &str) -> f64 {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.trim().parse().ok())
+        .unwrap_or_else(|| panic!("no {} in output:\n{}", key, stdout))
+}
+
+const BASE: [&str; 8] = [
+    "--spot", "100", "--strike", "100", "--time", "1.0", "--rate", "0.05",
+];
+
+#[test]
+fn test_prices_and_greeks() {
+    let out = run(&[&BASE[..], &["--vol", "0.2", "--type", "call"]].concat());
+    assert!(out.status.success());
+    let call = field(&out, "call ");
+    assert!((call - 10.4506).abs() < 1e-4, "call={}", call);
+    assert!(field(&out, "put ") > 0.0);
+    for key in ["delta ", "gamma ", "vega ", "theta ", "rho "] {
+        assert!(field(&out, key).is_finite());
+    }
+}
+
+#[test]
+fn test_implied_vol_mode() {
+    let out = run(&[&BASE[..], &["--implied-vol", "--market-price", "10.4506"]].concat());
+    assert!(out.status.success());
+    assert!((field(&out, "implied_vol ") - 0.2).abs() < 1e-4);
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let negative_time = [
+        "--spot", "100", "--strike", "100", "--time", "-1", "--rate", "0.05", "--vol", "0.2",
+    ];
+    assert!(!run(&negative_time).status.success());
+    assert!(!run(&[&BASE[..], &["--vol", "abc"]].concat())
+        .status
+        .success());
+    assert!(
+        !run(&[&BASE[..], &["--vol", "0.2", "--type", "straddle"]].concat())
+            .status
+            .success()
+    );
+    assert!(!run(&BASE).status.success());
+}